@@ -0,0 +1,45 @@
+//! Benchmarks every registered day's parse, part 1 and part 2 separately against the inputs under
+//! `files/`, falling back to the embedded ones.
+//!
+//! Run with `cargo bench`, or `cargo bench -- 2020/day05` for a single day. Criterion keeps the
+//! previous run under `target/criterion` to compare against and writes HTML reports there too.
+
+use advent_of_code_2020::input::{self, Source};
+use advent_of_code_2020::solution::{DynSolution, Puzzle};
+use advent_of_code_2020::years;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::time::Duration;
+
+/// Days whose phases take longer than this together get fewer samples, so the whole suite still
+/// finishes in a few minutes.
+const SLOW: Duration = Duration::from_millis(20);
+
+fn bench_day(c: &mut Criterion, puzzle: Puzzle, solution: &dyn DynSolution) {
+    let input = input::load(puzzle, &Source::default(), solution.input())
+        .unwrap_or_else(|err| panic!("{}: {}", puzzle, err));
+    let run = solution
+        .run(&input)
+        .unwrap_or_else(|err| panic!("{}: {}", puzzle, err));
+
+    let mut group = c.benchmark_group(puzzle.to_string());
+    if run.timings.total() > SLOW {
+        group.sample_size(10);
+    }
+    solution
+        .phases(&input, &mut |name, phase| {
+            group.bench_function(name, |b| b.iter(&mut *phase));
+        })
+        .unwrap_or_else(|err| panic!("{}: {}", puzzle, err));
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    let calendar = years::calendar();
+    for puzzle in calendar.puzzles() {
+        let solution = calendar.get(puzzle).expect("puzzles are registered");
+        bench_day(c, puzzle, solution);
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
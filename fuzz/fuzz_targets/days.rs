@@ -0,0 +1,26 @@
+#![no_main]
+
+use advent_of_code_2020::years;
+use libfuzzer_sys::fuzz_target;
+
+/// Year and day of the puzzles whose parts play millions of turns whatever the input, far too
+/// slow to fuzz, so only their parsing is.
+const PARSE_ONLY: &[(usize, usize)] = &[(2020, 15), (2020, 23)];
+
+// the first byte picks the puzzle, the rest is its input
+fuzz_target!(|data: &[u8]| {
+    let (pick, input) = match data.split_first() {
+        Some((&pick, input)) => (pick, input),
+        None => return,
+    };
+    let input = match std::str::from_utf8(input) {
+        Ok(input) => input,
+        Err(_) => return,
+    };
+    let calendar = years::calendar();
+    let puzzles = calendar.puzzles().collect::<Vec<_>>();
+    let puzzle = puzzles[usize::from(pick) % puzzles.len()];
+    let solution = calendar.get(puzzle).expect("puzzles are registered");
+    let solve = !PARSE_ONLY.contains(&(puzzle.year, puzzle.day));
+    let _ = solution.check(input, solve);
+});
@@ -0,0 +1,263 @@
+//! Expected answers for each puzzle and input, and checking solutions against them.
+
+use crate::solution::Puzzle;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// Where answers are kept unless `--answers` says otherwise.
+pub const DEFAULT_PATH: &str = "answers.txt";
+
+/// Why answers couldn't be loaded or saved.
+#[derive(Debug)]
+pub enum AnswersError {
+    /// Reading or writing the file failed.
+    Io(PathBuf, std::io::Error),
+    /// A line of the file, counting from 1, isn't a valid entry.
+    Parse(PathBuf, usize, String),
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Io(path, err) => {
+                write!(f, "could not access answers {}: {}", path.display(), err)
+            }
+            AnswersError::Parse(path, line, err) => {
+                write!(f, "{}:{}: {}", path.display(), line, err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+/// FNV-1a hash of the normalised input, used to tell apart answers for different inputs to the
+/// same day. Unlike `DefaultHasher` its output is stable across Rust releases.
+pub fn input_key(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Whether an answer matches the expected one.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Status {
+    /// It matches.
+    Pass,
+    /// It doesn't match.
+    Fail,
+    /// There's no expected answer to compare against.
+    Unknown,
+}
+
+impl Status {
+    fn check(expected: Option<&str>, actual: &str) -> Status {
+        match expected {
+            Some(expected) if expected == actual => Status::Pass,
+            Some(_) => Status::Fail,
+            None => Status::Unknown,
+        }
+    }
+
+    /// The status as written in reports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Unknown => "unknown",
+        }
+    }
+}
+
+/// The expected answers to both parts of a puzzle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expected {
+    /// The answer to part 1.
+    pub part1: String,
+    /// The answer to part 2.
+    pub part2: String,
+}
+
+/// How a day's answers compare with the expected ones.
+pub struct Verification {
+    /// The puzzle answered.
+    pub puzzle: Puzzle,
+    /// The [`input_key`] of the input it was answered for.
+    pub input: u64,
+    /// The expected answers, if known.
+    pub expected: Option<Expected>,
+    /// How the part 1 answer compares.
+    pub part1: Status,
+    /// How the part 2 answer compares.
+    pub part2: Status,
+}
+
+impl Verification {
+    /// Fails if either part fails, and passes only if both pass.
+    pub fn status(&self) -> Status {
+        match (self.part1, self.part2) {
+            (Status::Fail, _) | (_, Status::Fail) => Status::Fail,
+            (Status::Pass, Status::Pass) => Status::Pass,
+            _ => Status::Unknown,
+        }
+    }
+}
+
+/// Expected answers keyed by puzzle and input hash, stored one per line as tab separated
+/// `year`, `day`, `input`, `part 1` and `part 2` columns.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(Puzzle, u64), Expected>,
+}
+
+fn parse_entry(line: &str) -> Result<((Puzzle, u64), Expected), String> {
+    match line.split('\t').collect::<Vec<_>>().as_slice() {
+        &[year, day, input, part1, part2] => {
+            let year = year
+                .parse::<usize>()
+                .map_err(|err| format!("invalid year {}: {}", year, err))?;
+            let day = day
+                .parse::<usize>()
+                .map_err(|err| format!("invalid day {}: {}", day, err))?;
+            let input = u64::from_str_radix(input, 16)
+                .map_err(|err| format!("invalid input hash {}: {}", input, err))?;
+            let expected = Expected {
+                part1: part1.to_string(),
+                part2: part2.to_string(),
+            };
+            Ok(((Puzzle::new(year, day), input), expected))
+        }
+        fields => Err(format!("expected 5 fields, found {}", fields.len())),
+    }
+}
+
+impl Answers {
+    /// Loads answers from `path`, treating a missing file as having no answers at all.
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Answers::parse(&contents)
+                .map_err(|(line, err)| AnswersError::Parse(path.to_path_buf(), line, err)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(AnswersError::Io(path.to_path_buf(), err)),
+        }
+    }
+
+    /// Writes the answers to `path`, replacing what was there.
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        std::fs::write(path, self.to_string())
+            .map_err(|err| AnswersError::Io(path.to_path_buf(), err))
+    }
+
+    fn parse(contents: &str) -> Result<Answers, (usize, String)> {
+        let mut entries = BTreeMap::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, expected) = parse_entry(line).map_err(|err| (i + 1, err))?;
+            entries.insert(key, expected);
+        }
+        Ok(Answers { entries })
+    }
+
+    /// The expected answers for `puzzle` on the input with key `input`, if known.
+    pub fn get(&self, puzzle: Puzzle, input: u64) -> Option<&Expected> {
+        self.entries.get(&(puzzle, input))
+    }
+
+    /// Records the expected answers for `puzzle` on the input with key `input`.
+    pub fn insert(&mut self, puzzle: Puzzle, input: u64, expected: Expected) {
+        self.entries.insert((puzzle, input), expected);
+    }
+
+    /// Compares answers for `puzzle` with the expected ones.
+    pub fn verify(&self, puzzle: Puzzle, input: u64, part1: &str, part2: &str) -> Verification {
+        let expected = self.get(puzzle, input).cloned();
+        Verification {
+            puzzle,
+            input,
+            part1: Status::check(expected.as_ref().map(|e| e.part1.as_str()), part1),
+            part2: Status::check(expected.as_ref().map(|e| e.part2.as_str()), part2),
+            expected,
+        }
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# year\tday\tinput\tpart 1\tpart 2")?;
+        for ((puzzle, input), expected) in &self.entries {
+            writeln!(
+                f,
+                "{}\t{}\t{:016x}\t{}\t{}",
+                puzzle.year, puzzle.day, input, expected.part1, expected.part2
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::answers::{input_key, Answers, Expected, Status};
+    use crate::solution::Puzzle;
+
+    #[test]
+    fn test_input_key() {
+        assert_eq!(input_key(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(input_key("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(input_key("389125467"), input_key("219347865"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.insert(
+            Puzzle::new(2020, 21),
+            input_key("example"),
+            Expected {
+                part1: "5".to_string(),
+                part2: "mxmxvkd,sqjhc,fvjkl".to_string(),
+            },
+        );
+        answers.insert(
+            Puzzle::new(2020, 25),
+            1,
+            Expected {
+                part1: "14897079".to_string(),
+                part2: "there is no part 2 lol".to_string(),
+            },
+        );
+        assert_eq!(Answers::parse(&answers.to_string()), Ok(answers));
+        assert_eq!(
+            Answers::parse("2020\t1\tzz\t1\t2"),
+            Err((
+                1,
+                "invalid input hash zz: invalid digit found in string".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let mut answers = Answers::default();
+        let expected = Expected {
+            part1: "228".to_string(),
+            part2: "175".to_string(),
+        };
+        let day04 = Puzzle::new(2020, 4);
+        answers.insert(day04, 7, expected);
+
+        let pass = answers.verify(day04, 7, "228", "175");
+        assert_eq!(pass.status(), Status::Pass);
+
+        let fail = answers.verify(day04, 7, "228", "176");
+        assert_eq!((fail.part1, fail.part2), (Status::Pass, Status::Fail));
+        assert_eq!(fail.status(), Status::Fail);
+
+        let unknown = answers.verify(day04, 8, "228", "175");
+        assert_eq!(unknown.status(), Status::Unknown);
+        assert!(unknown.expected.is_none());
+    }
+}
@@ -0,0 +1,359 @@
+//! Saved timings to compare later runs against, one CSV file per named baseline.
+
+use crate::output::DayResult;
+use crate::solution::Puzzle;
+use crate::stats::Summary;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Where baselines are saved, relative to the working directory.
+pub const DIR: &str = "baselines";
+
+/// Why a baseline couldn't be loaded or saved.
+#[derive(Debug)]
+pub enum BaselineError {
+    /// The name isn't safe to use as a file name.
+    Name(String),
+    /// Reading or writing the file failed.
+    Io(PathBuf, std::io::Error),
+    /// A line of the file, counting from 1, isn't a valid entry.
+    Parse(PathBuf, usize, String),
+}
+
+impl Display for BaselineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BaselineError::Name(name) => write!(f, "invalid baseline name: {}", name),
+            BaselineError::Io(path, err) => {
+                write!(f, "could not access baseline {}: {}", path.display(), err)
+            }
+            BaselineError::Parse(path, line, err) => {
+                write!(f, "{}:{}: {}", path.display(), line, err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BaselineError {}
+
+/// A day's total runtime as summarised when the baseline was saved.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Entry {
+    /// How many runs were timed.
+    pub samples: usize,
+    /// Mean runtime.
+    pub mean: Duration,
+    /// Standard deviation of the runtime.
+    pub std_dev: Duration,
+    /// Median runtime.
+    pub median: Duration,
+}
+
+impl From<&Summary> for Entry {
+    fn from(summary: &Summary) -> Self {
+        Entry {
+            samples: summary.samples,
+            mean: summary.mean,
+            std_dev: summary.std_dev,
+            median: summary.median,
+        }
+    }
+}
+
+/// Timings for each puzzle run when the baseline was saved.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    /// Each puzzle's timings.
+    pub puzzles: BTreeMap<Puzzle, Entry>,
+}
+
+fn parse_entry(line: &str) -> Result<(Puzzle, Entry), String> {
+    let fields = line
+        .split(',')
+        .map(|f| {
+            f.trim()
+                .parse::<u64>()
+                .map_err(|err| format!("{}: {}", f, err))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    match fields.as_slice() {
+        &[year, day, samples, mean, std_dev, median] => Ok((
+            Puzzle::new(year as usize, day as usize),
+            Entry {
+                samples: samples as usize,
+                mean: Duration::from_nanos(mean),
+                std_dev: Duration::from_nanos(std_dev),
+                median: Duration::from_nanos(median),
+            },
+        )),
+        _ => Err(format!("expected 6 fields, found {}", fields.len())),
+    }
+}
+
+impl Baseline {
+    /// Collects the total runtimes of `results`.
+    pub fn from_results(results: &[DayResult]) -> Baseline {
+        let puzzles = results
+            .iter()
+            .map(|r| (r.puzzle, Entry::from(&r.summary().total)))
+            .collect();
+        Baseline { puzzles }
+    }
+
+    /// The file the baseline `name` is saved in, failing if the name isn't just letters, digits,
+    /// `-`, `_` and `.`.
+    pub fn path(name: &str) -> Result<PathBuf, BaselineError> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+            && !name.starts_with('.');
+        match valid {
+            true => Ok(Path::new(DIR).join(format!("{}.csv", name))),
+            false => Err(BaselineError::Name(name.to_string())),
+        }
+    }
+
+    /// Loads the baseline `name`.
+    pub fn load(name: &str) -> Result<Baseline, BaselineError> {
+        let path = Baseline::path(name)?;
+        let contents =
+            std::fs::read_to_string(&path).map_err(|err| BaselineError::Io(path.clone(), err))?;
+        Baseline::parse(&contents).map_err(|(line, err)| BaselineError::Parse(path, line, err))
+    }
+
+    /// Saves the baseline as `name`, returning the file it was saved to.
+    pub fn save(&self, name: &str) -> Result<PathBuf, BaselineError> {
+        let path = Baseline::path(name)?;
+        std::fs::create_dir_all(DIR).map_err(|err| BaselineError::Io(PathBuf::from(DIR), err))?;
+        std::fs::write(&path, self.to_string())
+            .map_err(|err| BaselineError::Io(path.clone(), err))?;
+        Ok(path)
+    }
+
+    fn parse(contents: &str) -> Result<Baseline, (usize, String)> {
+        let mut puzzles = BTreeMap::new();
+        for (i, line) in contents.lines().enumerate().skip(1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (puzzle, entry) = parse_entry(line).map_err(|err| (i + 1, err))?;
+            puzzles.insert(puzzle, entry);
+        }
+        Ok(Baseline { puzzles })
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "year,day,samples,mean_ns,std_dev_ns,median_ns")?;
+        for (puzzle, entry) in &self.puzzles {
+            writeln!(
+                f,
+                "{},{},{},{},{},{}",
+                puzzle.year,
+                puzzle.day,
+                entry.samples,
+                entry.mean.as_nanos(),
+                entry.std_dev.as_nanos(),
+                entry.median.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// How a day's runtime changed since the baseline.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Verdict {
+    /// Significantly faster by more than the threshold.
+    Faster,
+    /// Significantly slower by more than the threshold.
+    Slower,
+    /// Within the threshold, or not significantly different.
+    Unchanged,
+    /// Not in the baseline.
+    New,
+}
+
+/// One day's runtime against the baseline.
+pub struct Comparison {
+    /// The puzzle timed.
+    pub puzzle: Puzzle,
+    /// Its timings in the baseline, if it was there.
+    pub before: Option<Entry>,
+    /// Its timings now.
+    pub after: Entry,
+    /// Relative change in mean runtime, e.g. `0.1` for 10% slower.
+    pub change: f64,
+    /// Whether it got faster or slower.
+    pub verdict: Verdict,
+}
+
+/// Two-tailed 95% critical values of Student's t distribution, indexed by degrees of freedom.
+fn t_critical(df: f64) -> f64 {
+    const TABLE: [(f64, f64); 14] = [
+        (1.0, 12.706),
+        (2.0, 4.303),
+        (3.0, 3.182),
+        (4.0, 2.776),
+        (5.0, 2.571),
+        (6.0, 2.447),
+        (7.0, 2.365),
+        (8.0, 2.306),
+        (9.0, 2.262),
+        (10.0, 2.228),
+        (15.0, 2.131),
+        (20.0, 2.086),
+        (30.0, 2.042),
+        (60.0, 2.000),
+    ];
+    TABLE
+        .iter()
+        .find(|&&(d, _)| df <= d)
+        .map_or(1.960, |&(_, t)| t)
+}
+
+/// Welch's t-test on the two means. With fewer than two samples on either side there is no
+/// variance to test against, so any change past the threshold counts.
+fn significant(before: &Entry, after: &Entry) -> bool {
+    if before.samples < 2 || after.samples < 2 {
+        return true;
+    }
+
+    let var = |e: &Entry| e.std_dev.as_secs_f64().powi(2) / e.samples as f64;
+    let (va, vb) = (var(before), var(after));
+    if va + vb == 0.0 {
+        return before.mean != after.mean;
+    }
+
+    let t = (after.mean.as_secs_f64() - before.mean.as_secs_f64()).abs() / (va + vb).sqrt();
+    let df = (va + vb).powi(2)
+        / (va.powi(2) / (before.samples - 1) as f64 + vb.powi(2) / (after.samples - 1) as f64);
+    t > t_critical(df)
+}
+
+/// Compares each day in `current` with `baseline`, counting changes smaller than `threshold`
+/// (relative, e.g. `0.05` for 5%) or not statistically significant as unchanged.
+pub fn compare(baseline: &Baseline, current: &Baseline, threshold: f64) -> Vec<Comparison> {
+    current
+        .puzzles
+        .iter()
+        .map(|(&puzzle, &after)| {
+            let before = baseline.puzzles.get(&puzzle).copied();
+            let (change, verdict) = match before {
+                None => (0.0, Verdict::New),
+                Some(before) => {
+                    let change = match before.mean.as_secs_f64() {
+                        mean if mean > 0.0 => after.mean.as_secs_f64() / mean - 1.0,
+                        _ => 0.0,
+                    };
+                    let verdict = if change.abs() < threshold || !significant(&before, &after) {
+                        Verdict::Unchanged
+                    } else if change > 0.0 {
+                        Verdict::Slower
+                    } else {
+                        Verdict::Faster
+                    };
+                    (change, verdict)
+                }
+            };
+            Comparison {
+                puzzle,
+                before,
+                after,
+                change,
+                verdict,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::baseline::{compare, Baseline, Entry, Verdict};
+    use crate::solution::Puzzle;
+    use std::time::Duration;
+
+    fn entry(samples: usize, mean_us: u64, std_dev_us: u64) -> Entry {
+        Entry {
+            samples,
+            mean: Duration::from_micros(mean_us),
+            std_dev: Duration::from_micros(std_dev_us),
+            median: Duration::from_micros(mean_us),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline
+            .puzzles
+            .insert(Puzzle::new(2020, 3), entry(10, 30, 2));
+        baseline
+            .puzzles
+            .insert(Puzzle::new(2020, 15), entry(1, 500_000, 0));
+        let parsed = Baseline::parse(&baseline.to_string()).expect("should parse");
+        assert_eq!(parsed, baseline);
+
+        assert_eq!(
+            Baseline::parse("year,day,samples,mean_ns,std_dev_ns,median_ns\n2020,1,2,3"),
+            Err((2, "expected 6 fields, found 4".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_names() {
+        assert!(Baseline::path("main").is_ok());
+        assert!(Baseline::path("before-fix_2").is_ok());
+        assert!(Baseline::path("../main").is_err());
+        assert!(Baseline::path("").is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut before = Baseline::default();
+        before
+            .puzzles
+            .insert(Puzzle::new(2020, 1), entry(50, 100, 5));
+        before
+            .puzzles
+            .insert(Puzzle::new(2020, 2), entry(50, 100, 5));
+        before
+            .puzzles
+            .insert(Puzzle::new(2020, 3), entry(50, 100, 5));
+        before
+            .puzzles
+            .insert(Puzzle::new(2020, 4), entry(3, 100, 60));
+
+        let mut after = Baseline::default();
+        after
+            .puzzles
+            .insert(Puzzle::new(2020, 1), entry(50, 150, 5));
+        after.puzzles.insert(Puzzle::new(2020, 2), entry(50, 60, 5));
+        after
+            .puzzles
+            .insert(Puzzle::new(2020, 3), entry(50, 102, 5));
+        after
+            .puzzles
+            .insert(Puzzle::new(2020, 4), entry(3, 150, 60));
+        after.puzzles.insert(Puzzle::new(2020, 5), entry(1, 10, 0));
+
+        let verdicts = compare(&before, &after, 0.05)
+            .iter()
+            .map(|c| c.verdict)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            verdicts,
+            vec![
+                Verdict::Slower,
+                Verdict::Faster,
+                Verdict::Unchanged,
+                Verdict::Unchanged,
+                Verdict::New
+            ]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Runs days repeatedly and times them, keeping a panicking day from taking down the rest.
+
+use crate::answers;
+use crate::error::{Error, Result};
+use crate::memory;
+use crate::output::DayResult;
+use crate::solution::{DynSolution, Puzzle, Run};
+use std::any::Any;
+use std::panic::AssertUnwindSafe;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// How many times to run a day.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Repeats {
+    /// Exactly this many times.
+    Count(usize),
+    /// As many times as fit in this much time, and at least once.
+    Budget(Duration),
+}
+
+/// Parses durations like `250ms`, `5s` or `1.5m`.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = s.split_at(split);
+    let value = value.parse::<f64>().ok()?;
+    let secs = match unit {
+        "ns" => value / 1e9,
+        "us" | "µs" => value / 1e6,
+        "ms" => value / 1e3,
+        "s" => value,
+        "m" => value * 60.0,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+impl FromStr for Repeats {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<usize>() {
+            Ok(0) => Err("repeats must be at least 1".to_string()),
+            Ok(count) => Ok(Repeats::Count(count)),
+            Err(_) => match parse_duration(s) {
+                Some(budget) if budget > Duration::default() => Ok(Repeats::Budget(budget)),
+                _ => Err(format!("illegal value for repeats: {}", s)),
+            },
+        }
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    match panic.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => panic
+            .downcast_ref::<String>()
+            .map_or("unknown panic", |message| message.as_str()),
+    }
+}
+
+/// Turns any panic in `f` into an error.
+pub fn catch<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    std::panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        Err(Error::new(format!(
+            "panicked: {}",
+            panic_message(panic.as_ref())
+        )))
+    })
+}
+
+/// Runs the solution, turning any panic that slipped through into an error so that one bad day
+/// doesn't take down the rest.
+fn run(solution: &dyn DynSolution, input: &str) -> Result<Run> {
+    catch(|| solution.run(input))
+}
+
+/// Runs a solution `warmup` times without recording anything, then either a fixed number of times
+/// or until the time budget is spent. A budget always gets at least one measured run. When
+/// allocations are being counted, the first measured run's are recorded too.
+pub fn measure(
+    puzzle: Puzzle,
+    solution: &dyn DynSolution,
+    input: &str,
+    warmup: usize,
+    repeats: Repeats,
+) -> Result<DayResult> {
+    let start = Instant::now();
+    for _ in 0..warmup {
+        run(solution, input)?;
+    }
+
+    let mut result = DayResult {
+        puzzle,
+        title: solution.title(),
+        part1: String::new(),
+        part2: String::new(),
+        input: answers::input_key(input),
+        warmup,
+        runs: Vec::new(),
+        memory: None,
+        elapsed: Duration::default(),
+    };
+
+    let measured = Instant::now();
+    loop {
+        let run = if result.runs.is_empty() && memory::enabled() {
+            let (run, usage) = memory::measure(|| run(solution, input));
+            result.memory = Some(usage);
+            run?
+        } else {
+            run(solution, input)?
+        };
+        if result.runs.is_empty() {
+            result.part1 = run.part1;
+            result.part2 = run.part2;
+        }
+        result.runs.push(run.timings);
+
+        let done = match repeats {
+            Repeats::Count(count) => result.runs.len() >= count,
+            Repeats::Budget(budget) => measured.elapsed() >= budget,
+        };
+        if done {
+            break;
+        }
+    }
+
+    result.elapsed = start.elapsed();
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::{measure, parse_duration, Repeats};
+    use crate::solution::{DynSolution, Puzzle};
+    use crate::years::y2020::day06::Day06;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_repeats() {
+        assert_eq!("50".parse(), Ok(Repeats::Count(50)));
+        assert_eq!("5s".parse(), Ok(Repeats::Budget(Duration::from_secs(5))));
+        assert_eq!(
+            "250ms".parse(),
+            Ok(Repeats::Budget(Duration::from_millis(250)))
+        );
+        assert_eq!("1.5m".parse(), Ok(Repeats::Budget(Duration::from_secs(90))));
+        assert!("0".parse::<Repeats>().is_err());
+        assert!("5x".parse::<Repeats>().is_err());
+        assert!("ms".parse::<Repeats>().is_err());
+        assert!("99999999999999999999999s".parse::<Repeats>().is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("1.5s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("99999999999999999999999s"), None);
+        assert_eq!(parse_duration("1e400m"), None);
+    }
+
+    #[test]
+    fn test_measure() {
+        let puzzle = Puzzle::new(2020, 6);
+        let result =
+            measure(puzzle, &Day06, Day06.input(), 2, Repeats::Count(3)).expect("valid input");
+        assert_eq!(result.puzzle, puzzle);
+        assert_eq!(result.runs.len(), 3);
+        assert_eq!(result.warmup, 2);
+        assert_eq!(
+            (result.part1.as_str(), result.part2.as_str()),
+            ("6297", "3158")
+        );
+
+        let result = measure(
+            puzzle,
+            &Day06,
+            Day06.input(),
+            0,
+            Repeats::Budget(Duration::from_nanos(1)),
+        )
+        .expect("valid input");
+        assert_eq!(result.runs.len(), 1);
+    }
+}
@@ -0,0 +1,393 @@
+//! Checks solvers against slow but straightforward reference solvers, on the real input and on
+//! generated ones, and shrinks any input they disagree on to something small enough to read.
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::DynSolution;
+
+/// How many generated inputs each day is checked on by default.
+pub const DEFAULT_CASES: usize = 20;
+/// Smaller than [`crate::generate::DEFAULT_SIZE`], since reference solvers can be slow.
+pub const DEFAULT_SIZE: usize = 20;
+
+/// Most candidate inputs tried while shrinking a single disagreement.
+pub const MAX_ATTEMPTS: usize = 2000;
+
+/// The one answer among `answers`, for reference solvers that find every candidate. Fails if there
+/// are none or they differ, since then the input has no single right answer.
+pub fn only<T: PartialEq>(answers: impl IntoIterator<Item = T>) -> Result<T> {
+    let mut answers = answers.into_iter();
+    let first = answers.next().ok_or_else(|| Error::new("no answer"))?;
+    match answers.all(|answer| answer == first) {
+        true => Ok(first),
+        false => Err(Error::new("more than one answer")),
+    }
+}
+
+/// One part's reference answer next to the solver's.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Check {
+    /// Which part, 1 or 2.
+    pub part: usize,
+    /// The reference solver's answer, or why the input has no single right answer.
+    pub reference: Result<String>,
+    /// The solver's answer, only worked out when there's a reference answer to compare it with,
+    /// since solvers may never finish on inputs without one, such as bags that contain themselves.
+    pub answer: Option<Result<String>>,
+}
+
+impl Check {
+    /// The reference answer, if the answer doesn't match it.
+    pub fn disagreement(&self) -> Option<&str> {
+        match (&self.reference, &self.answer) {
+            (Ok(reference), Some(answer)) if answer.as_ref() != Ok(reference) => Some(reference),
+            _ => None,
+        }
+    }
+}
+
+/// An input a solver and its reference solver disagree on, shrunk as far as it would go.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Disagreement {
+    /// Which part, 1 or 2.
+    pub part: usize,
+    /// Where the input came from, such as `puzzle input` or `seed 7, size 20`.
+    pub source: String,
+    /// The shrunk input.
+    pub input: String,
+    /// The solver's answer on it.
+    pub answer: Result<String>,
+    /// The reference solver's answer on it.
+    pub reference: String,
+}
+
+/// Everything found checking one day.
+#[derive(Debug, Default)]
+pub struct Outcome {
+    /// Parts with a reference solver.
+    pub parts: Vec<usize>,
+    /// Inputs that were checked, which leaves out those no reference solver had an answer for.
+    pub checked: usize,
+    /// Every input the solvers disagreed on.
+    pub disagreements: Vec<Disagreement>,
+}
+
+/// Checks `solution` on `input` and then on `cases` inputs generated from consecutive seeds
+/// starting at `seed`, with sizes going up from 1 to `size` and round again, since small and
+/// odd-sized inputs find as much as big ones. A real input that doesn't parse is an error, while
+/// generated ones that don't are skipped.
+pub fn run(
+    solution: &dyn DynSolution,
+    input: &str,
+    seed: u64,
+    cases: usize,
+    size: usize,
+) -> Result<Outcome> {
+    let mut outcome = Outcome::default();
+    let checks = solution.crosscheck(input)?;
+    outcome.parts = checks.iter().map(|c| c.part).collect();
+    if outcome.parts.is_empty() {
+        return Ok(outcome);
+    }
+    add(&mut outcome, solution, input, &checks, "puzzle input");
+
+    for (i, seed) in (seed..).take(cases).enumerate() {
+        let size = 1 + i % size.max(1);
+        let generated = match solution.generate(&mut Rng::new(seed), size) {
+            Some(generated) => generated,
+            None => break,
+        };
+        if let Ok(checks) = solution.crosscheck(&generated) {
+            add(
+                &mut outcome,
+                solution,
+                &generated,
+                &checks,
+                &format!("seed {}, size {}", seed, size),
+            );
+        }
+    }
+    Ok(outcome)
+}
+
+fn add(
+    outcome: &mut Outcome,
+    solution: &dyn DynSolution,
+    input: &str,
+    checks: &[Check],
+    source: &str,
+) {
+    if checks.iter().all(|c| c.reference.is_err()) {
+        return;
+    }
+    outcome.checked += 1;
+    for check in checks {
+        let (answer, reference) = match (&check.answer, check.disagreement()) {
+            (Some(answer), Some(reference)) => (answer.clone(), reference.to_string()),
+            _ => continue,
+        };
+        let minimised = minimise(solution, input, check.part);
+        // solvers that don't always give the same answer may not disagree again, so this falls
+        // back to what was first found
+        let (input, answer, reference) = match disagreement(solution, &minimised, check.part) {
+            Some((answer, reference)) => (minimised, answer, reference),
+            None => (input.to_string(), answer, reference),
+        };
+        outcome.disagreements.push(Disagreement {
+            part: check.part,
+            source: source.to_string(),
+            input,
+            answer,
+            reference,
+        });
+    }
+}
+
+/// The answer and reference answer for `part` of `input`, if they differ.
+fn disagreement(
+    solution: &dyn DynSolution,
+    input: &str,
+    part: usize,
+) -> Option<(Result<String>, String)> {
+    let check = solution
+        .crosscheck(input)
+        .ok()?
+        .into_iter()
+        .find(|c| c.part == part)?;
+    let reference = check.disagreement()?.to_string();
+    Some((check.answer?, reference))
+}
+
+/// Shrinks an input `part` disagrees on for as long as it keeps disagreeing, by dropping runs of
+/// lines and then runs of characters, halving the run length whenever none can go, until neither
+/// shrinks it further. Gives up after [`MAX_ATTEMPTS`] candidates, returning the smallest input
+/// found to disagree, which is `input` itself if it doesn't.
+pub fn minimise(solution: &dyn DynSolution, input: &str, part: usize) -> String {
+    let mut smallest = input.to_string();
+    let mut attempts = 0;
+    let mut disagrees = |candidate: &str| {
+        attempts += 1;
+        let found = attempts <= MAX_ATTEMPTS && disagreement(solution, candidate, part).is_some();
+        if found {
+            smallest = candidate.to_string();
+        }
+        found
+    };
+
+    if disagrees(input) {
+        let mut input = input.to_string();
+        loop {
+            let lines = input.split('\n').map(str::to_string).collect();
+            let lines = shrink(lines, "\n", &mut disagrees).join("\n");
+            let chars = lines.chars().map(String::from).collect();
+            let shrunk = shrink(chars, "", &mut disagrees).concat();
+            if shrunk == input {
+                break;
+            }
+            input = shrunk;
+        }
+    }
+    smallest
+}
+
+fn shrink(
+    mut units: Vec<String>,
+    separator: &str,
+    disagrees: &mut impl FnMut(&str) -> bool,
+) -> Vec<String> {
+    let mut len = units.len() / 2;
+    while len > 0 {
+        let mut start = 0;
+        while start < units.len() {
+            let end = (start + len).min(units.len());
+            let candidate = units[..start]
+                .iter()
+                .chain(&units[end..])
+                .cloned()
+                .collect::<Vec<_>>();
+            if disagrees(&candidate.join(separator)) {
+                units = candidate;
+            } else {
+                start = end;
+            }
+        }
+        len /= 2;
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::crosscheck::{minimise, only, run, Check, Disagreement};
+    use crate::error::{Error, Result};
+    use crate::generate::Rng;
+    use crate::input::{self, Source};
+    use crate::solution::Solution;
+    use crate::years;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Sums numbers, except that it forgets any 7 that comes straight after a 3.
+    struct Buggy;
+
+    impl Solution for Buggy {
+        type Parsed<'a> = Vec<u64>;
+
+        fn day(&self) -> usize {
+            1
+        }
+
+        fn title(&self) -> &'static str {
+            "Buggy"
+        }
+
+        fn input(&self) -> &'static str {
+            ""
+        }
+
+        fn parse(&self, input: &str) -> Result<Vec<u64>> {
+            input
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|_| Error::new("not a number")))
+                .collect()
+        }
+
+        fn part1(&self, numbers: &Vec<u64>) -> Result<String> {
+            let forgotten = numbers.windows(2).filter(|w| w == &[3, 7]).count() as u64;
+            Ok((numbers.iter().sum::<u64>() - 7 * forgotten).to_string())
+        }
+
+        fn part2(&self, _numbers: &Vec<u64>) -> Result<String> {
+            Ok(String::new())
+        }
+
+        fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+            let numbers = (0..size).map(|_| rng.below(10).to_string());
+            Some(numbers.collect::<Vec<_>>().join("\n"))
+        }
+
+        fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+            let numbers = self.parse(input);
+            Some(numbers.map(|numbers| numbers.iter().sum::<u64>().to_string()))
+        }
+    }
+
+    /// Gets part 1 wrong the first time only.
+    struct Flaky(AtomicBool);
+
+    impl Solution for Flaky {
+        type Parsed<'a> = ();
+
+        fn day(&self) -> usize {
+            1
+        }
+
+        fn title(&self) -> &'static str {
+            "Flaky"
+        }
+
+        fn input(&self) -> &'static str {
+            ""
+        }
+
+        fn parse(&self, _input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(&self, _parsed: &()) -> Result<String> {
+            match self.0.swap(false, Ordering::Relaxed) {
+                true => Ok("wrong".to_string()),
+                false => Ok("right".to_string()),
+            }
+        }
+
+        fn part2(&self, _parsed: &()) -> Result<String> {
+            Ok(String::new())
+        }
+
+        fn reference_part1(&self, _input: &str) -> Option<Result<String>> {
+            Some(Ok("right".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let check = |answer: Result<&str>, reference: Result<&str>| Check {
+            part: 1,
+            reference: reference.map(str::to_string),
+            answer: Some(answer.map(str::to_string)),
+        };
+        assert_eq!(check(Ok("1"), Ok("1")).disagreement(), None);
+        assert_eq!(check(Ok("1"), Ok("2")).disagreement(), Some("2"));
+        assert_eq!(
+            check(Err(Error::new("no")), Ok("2")).disagreement(),
+            Some("2")
+        );
+        assert_eq!(check(Ok("1"), Err(Error::new("no"))).disagreement(), None);
+    }
+
+    #[test]
+    fn test_only() {
+        assert_eq!(only(vec![3, 3]), Ok(3));
+        assert!(only(Vec::<u8>::new()).is_err());
+        assert!(only(vec![3, 4, 3]).is_err());
+    }
+
+    #[test]
+    fn test_minimise() {
+        assert_eq!(minimise(&Buggy, "1\n2\n3\n7\n4\n3\n5", 1), "3\n7");
+        assert_eq!(minimise(&Buggy, "1\n2", 1), "1\n2");
+    }
+
+    #[test]
+    fn test_run() {
+        let outcome = run(&Buggy, "1 3 7", 0, 20, 20).expect("parses");
+        assert_eq!(outcome.parts, vec![1]);
+        assert_eq!(outcome.checked, 21);
+        assert_eq!(
+            outcome.disagreements[0],
+            Disagreement {
+                part: 1,
+                source: "puzzle input".to_string(),
+                input: "3 7".to_string(),
+                answer: Ok("3".to_string()),
+                reference: "10".to_string(),
+            }
+        );
+        assert!(outcome.disagreements.len() > 1);
+        assert!(outcome.disagreements[1..]
+            .iter()
+            .all(|d| d.input == "3\n7" && d.source.starts_with("seed ")));
+        assert!(run(&Buggy, "x", 0, 20, 20).is_err());
+    }
+
+    #[test]
+    fn test_run_flaky() {
+        let outcome = run(&Flaky(AtomicBool::new(true)), "a\nb", 0, 0, 1).expect("parses");
+        assert_eq!(
+            outcome.disagreements,
+            vec![Disagreement {
+                part: 1,
+                source: "puzzle input".to_string(),
+                input: "a\nb".to_string(),
+                answer: Ok("wrong".to_string()),
+                reference: "right".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_every_day_agrees() {
+        let calendar = years::calendar();
+        for puzzle in calendar.puzzles() {
+            let solution = calendar.get(puzzle).expect("registered");
+            let input = input::load(puzzle, &Source::default(), solution.input()).expect("loads");
+            let outcome = run(solution, &input, 1, 3, 10).expect("parses");
+            assert!(
+                outcome.disagreements.is_empty(),
+                "{}: {:?}",
+                puzzle,
+                outcome.disagreements
+            );
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! The error type shared by every day, which can point at where in the input things went wrong.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Where in the input an error is, both starting at 1.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Position {
+    /// Line number.
+    pub line: usize,
+    /// Column number, counting characters.
+    pub column: usize,
+}
+
+impl Position {
+    /// Line and column (both starting at 1) of the byte at `offset` in `input`.
+    pub fn of_offset(input: &str, offset: usize) -> Position {
+        let before = &input.as_bytes()[..offset.min(input.len())];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+        Position {
+            line: bytecount::count(before, b'\n') + 1,
+            column: String::from_utf8_lossy(&before[line_start..])
+                .chars()
+                .count()
+                + 1,
+        }
+    }
+
+    /// Position of `token` in `input`, if `token` is a slice of `input`.
+    pub fn of(input: &str, token: &str) -> Option<Position> {
+        let start = input.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).checked_sub(start)?;
+        match offset + token.len() <= input.len() {
+            true => Some(Position::of_offset(input, offset)),
+            false => None,
+        }
+    }
+}
+
+/// The error returned by every day's parse and solve functions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Error {
+    /// What went wrong.
+    pub message: String,
+    /// Where in the input it went wrong, if anywhere in particular.
+    pub position: Option<Position>,
+    /// The phase that failed, such as `parse` or `part 1`.
+    pub phase: Option<&'static str>,
+}
+
+/// [`std::result::Result`] defaulting to [`Error`].
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    /// An error with `message` and no position or phase.
+    pub fn new(message: impl Into<String>) -> Error {
+        Error {
+            message: message.into(),
+            position: None,
+            phase: None,
+        }
+    }
+
+    /// An error pointing at `token`, which should be a slice of `input`.
+    pub fn at(input: &str, token: &str, message: impl Into<String>) -> Error {
+        Error::new(message).located(input, token)
+    }
+
+    /// Points the error at `token` unless it already has a more precise position.
+    pub fn located(mut self, input: &str, token: &str) -> Error {
+        if self.position.is_none() {
+            self.position = Position::of(input, token);
+        }
+        self
+    }
+
+    /// Records the phase the error happened in, unless it already has one.
+    pub fn during(mut self, phase: &'static str) -> Error {
+        self.phase.get_or_insert(phase);
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(phase) = self.phase {
+            write!(f, "{}: ", phase)?;
+        }
+        if let Some(position) = self.position {
+            write!(f, "line {}, column {}: ", position.line, position.column)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Parses `token`, a slice of `input`, reporting failures at its position.
+pub fn parse<T>(input: &str, token: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token
+        .parse()
+        .map_err(|err| Error::at(input, token, format!("invalid value {:?}: {}", token, err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{parse, Error, Position};
+
+    #[test]
+    fn test_position() {
+        let input = "abc\ndéf\n\nxyz";
+        assert_eq!(
+            Position::of_offset(input, 0),
+            Position { line: 1, column: 1 }
+        );
+        assert_eq!(
+            Position::of(input, &input[7..8]),
+            Some(Position { line: 2, column: 3 })
+        );
+        assert_eq!(
+            Position::of(input, &input[11..]),
+            Some(Position { line: 4, column: 2 })
+        );
+        let elsewhere = String::from("abc");
+        assert_eq!(Position::of(input, &elsewhere), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let input = "1\n2\nx3";
+        let err = parse::<u32>(input, &input[4..])
+            .expect_err("not a number")
+            .during("parse");
+        assert_eq!(
+            err.to_string(),
+            "parse: line 3, column 1: invalid value \"x3\": invalid digit found in string"
+        );
+        assert_eq!(
+            Error::new("no answer").during("part 2").to_string(),
+            "part 2: no answer"
+        );
+    }
+}
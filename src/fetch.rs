@@ -0,0 +1,212 @@
+//! Downloads puzzle inputs from the Advent of Code site and caches them on disk.
+
+use crate::input;
+use crate::solution::Puzzle;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// The site inputs are downloaded from.
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+/// Where inputs are cached, one directory per year, so they're read from there by default.
+pub const DEFAULT_DIR: &str = input::DEFAULT_DIR;
+/// Environment variable overriding [`DEFAULT_URL`].
+pub const URL_VAR: &str = "AOC_URL";
+/// Environment variable holding the session token to download with.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Why an input couldn't be downloaded.
+#[derive(Debug)]
+pub enum FetchError {
+    /// There's no session token to log in with.
+    NoSession,
+    /// The server answered with this HTTP status and body.
+    Status(Puzzle, u16, String),
+    /// The request didn't get an answer.
+    Transport(Puzzle, String),
+    /// The input couldn't be saved.
+    Io(PathBuf, std::io::Error),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::NoSession => {
+                write!(f, "no session token, pass --session or set {}", SESSION_VAR)
+            }
+            FetchError::Status(puzzle, status, body) => {
+                write!(f, "{}: server returned {}", puzzle, status)?;
+                match body.lines().next() {
+                    Some(line) if !line.trim().is_empty() => write!(f, ": {}", line.trim()),
+                    _ => Ok(()),
+                }
+            }
+            FetchError::Transport(puzzle, err) => write!(f, "{}: {}", puzzle, err),
+            FetchError::Io(path, err) => {
+                write!(f, "could not write input {}: {}", path.display(), err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Where an input came from.
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    /// It was already saved here.
+    Cached(PathBuf),
+    /// It was downloaded and saved here, this many bytes long.
+    Downloaded(PathBuf, usize),
+}
+
+/// Where to download inputs from and where to keep them.
+pub struct Fetcher {
+    /// The site to download from.
+    pub url: String,
+    /// The session cookie to log in with.
+    pub session: Option<String>,
+    /// The directory inputs are cached in.
+    pub dir: PathBuf,
+}
+
+impl Default for Fetcher {
+    /// Uses `AOC_URL` and `AOC_SESSION` from the environment when they are set.
+    fn default() -> Self {
+        Fetcher {
+            url: std::env::var(URL_VAR).unwrap_or_else(|_| DEFAULT_URL.to_string()),
+            session: std::env::var(SESSION_VAR).ok(),
+            dir: PathBuf::from(DEFAULT_DIR),
+        }
+    }
+}
+
+impl Fetcher {
+    /// The URL of `puzzle`'s input.
+    pub fn url(&self, puzzle: Puzzle) -> String {
+        format!(
+            "{}/{}/day/{}/input",
+            self.url.trim_end_matches('/'),
+            puzzle.year,
+            puzzle.day
+        )
+    }
+
+    /// Where `puzzle`'s input is cached.
+    pub fn path(&self, puzzle: Puzzle) -> PathBuf {
+        self.dir.join(input::path(puzzle))
+    }
+
+    /// Downloads the input for `puzzle` unless it is already cached. Nothing is written unless the
+    /// whole input arrives, so a failed download is retried next time. Empty files are the
+    /// placeholders `new` creates, so they don't count as cached.
+    pub fn fetch(&self, puzzle: Puzzle) -> Result<Fetched, FetchError> {
+        let path = self.path(puzzle);
+        if path.metadata().is_ok_and(|m| m.len() > 0) {
+            return Ok(Fetched::Cached(path));
+        }
+
+        let session = self.session.as_ref().ok_or(FetchError::NoSession)?;
+        let response = ureq::get(&self.url(puzzle))
+            .set("Cookie", &format!("session={}", session.trim()))
+            .set(
+                "User-Agent",
+                concat!(env!("CARGO_PKG_NAME"), " input fetcher"),
+            )
+            .call()
+            .map_err(|err| match err {
+                ureq::Error::Status(status, response) => {
+                    FetchError::Status(puzzle, status, response.into_string().unwrap_or_default())
+                }
+                ureq::Error::Transport(err) => FetchError::Transport(puzzle, err.to_string()),
+            })?;
+
+        let mut body = String::new();
+        response
+            .into_reader()
+            .read_to_string(&mut body)
+            .map_err(|err| FetchError::Transport(puzzle, err.to_string()))?;
+
+        save(&path, &body)?;
+        Ok(Fetched::Downloaded(path, body.len()))
+    }
+}
+
+fn save(path: &Path, contents: &str) -> Result<(), FetchError> {
+    let io_err = |err| FetchError::Io(path.to_path_buf(), err);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(io_err)?;
+    }
+    let partial = path.with_extension("txt.part");
+    std::fs::write(&partial, contents).map_err(io_err)?;
+    std::fs::rename(&partial, path).map_err(io_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fetch::{FetchError, Fetched, Fetcher};
+    use crate::mock::serve_once;
+    use crate::solution::Puzzle;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_and_cache() {
+        let (url, server) = serve_once("200 OK", "1721\n979\n");
+        let fetcher = Fetcher {
+            url: format!("{}/", url),
+            session: Some("abc123".to_string()),
+            dir: temp_dir("cache"),
+        };
+        let day01 = Puzzle::new(2020, 1);
+        let path = fetcher.path(day01);
+        assert!(path.ends_with("2020/01.txt"));
+        assert_eq!(
+            fetcher.fetch(day01).ok(),
+            Some(Fetched::Downloaded(path.clone(), 9))
+        );
+
+        let head = server.join().expect("server ran");
+        assert!(head.starts_with("GET /2020/day/1/input HTTP/1.1\r\n"));
+        assert!(head.contains("\r\nCookie: session=abc123\r\n"));
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("input was saved"),
+            "1721\n979\n"
+        );
+
+        // the server is gone, so this only works if nothing is fetched
+        assert_eq!(fetcher.fetch(day01).ok(), Some(Fetched::Cached(path)));
+        std::fs::remove_dir_all(&fetcher.dir).expect("can clean up");
+    }
+
+    #[test]
+    fn test_errors() {
+        let (url, server) = serve_once("400 Bad Request", "Puzzle inputs differ by user.\n");
+        let fetcher = Fetcher {
+            url,
+            session: Some("expired".to_string()),
+            dir: temp_dir("errors"),
+        };
+        let day02 = Puzzle::new(2020, 2);
+        match fetcher.fetch(day02) {
+            Err(err @ FetchError::Status(_, 400, _)) => assert_eq!(
+                err.to_string(),
+                "2020/day02: server returned 400: Puzzle inputs differ by user."
+            ),
+            other => panic!("expected a 400, got {:?}", other),
+        }
+        server.join().expect("server ran");
+        assert!(!fetcher.path(day02).exists());
+
+        let fetcher = Fetcher {
+            session: None,
+            ..fetcher
+        };
+        assert!(matches!(fetcher.fetch(day02), Err(FetchError::NoSession)));
+    }
+}
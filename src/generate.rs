@@ -0,0 +1,130 @@
+//! Random but valid puzzle inputs, for checking and timing solvers on more than the real inputs.
+//!
+//! Every day's module has a `generate` function taking an [`Rng`] and a size, which each day
+//! interprets as whatever makes its input bigger: lines, passports, tickets and so on. The same
+//! seed and size always give the same input. Days whose inputs can't grow forever say where they
+//! stop with [`Solution::max_size`](crate::solution::Solution::max_size).
+
+use crate::solution::{Calendar, Puzzle};
+use std::ops::RangeInclusive;
+
+/// The seed used unless `--seed` says otherwise.
+pub const DEFAULT_SEED: u64 = 2020;
+/// The size used unless `--size` says otherwise.
+pub const DEFAULT_SIZE: usize = 100;
+
+/// A small [SplitMix64](https://prng.di.unimi.it/splitmix64.c) generator. It is here rather than
+/// a dependency so that a seed gives the same input whatever version of anything is in use.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// A generator starting from `seed`.
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    /// The next number, uniformly distributed over all of `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, which must not be empty.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "can't pick from an empty range");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// A number in `range`, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (low, high) = range.into_inner();
+        low + self.below(high - low + 1)
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// One of `items`, which must not be empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Puts `items` in a random order.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// A random lowercase word with a length in `len`.
+    pub fn word(&mut self, len: RangeInclusive<usize>) -> String {
+        let len = self.range(len);
+        (0..len)
+            .map(|_| (b'a' + self.below(26) as u8) as char)
+            .collect()
+    }
+}
+
+/// An input for `puzzle` generated from `seed`, or `None` if it has no solution or generator.
+pub fn input(calendar: &Calendar, puzzle: Puzzle, seed: u64, size: usize) -> Option<String> {
+    calendar.get(puzzle)?.generate(&mut Rng::new(seed), size)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::{input, Rng};
+    use crate::solution::Puzzle;
+    use crate::years;
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        let picks = (0..100).map(|_| a.range(3..=5)).collect::<Vec<_>>();
+        assert!(picks.iter().all(|p| (3..=5).contains(p)));
+        assert_eq!(picks, (0..100).map(|_| b.range(3..=5)).collect::<Vec<_>>());
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+
+        let mut items = (0..10).collect::<Vec<_>>();
+        a.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_every_day() {
+        let calendar = years::calendar();
+        for puzzle in calendar.puzzles() {
+            let generated = input(&calendar, puzzle, 1, 20)
+                .unwrap_or_else(|| panic!("{}: no generator", puzzle));
+            assert_eq!(input(&calendar, puzzle, 1, 20), Some(generated.clone()));
+            let solution = calendar.get(puzzle).expect("registered");
+            if let Err(err) = solution.run(&generated) {
+                panic!("{}: {}\n{}", puzzle, err, generated);
+            }
+        }
+        assert_eq!(input(&calendar, Puzzle::new(2020, 26), 1, 20), None);
+    }
+
+    #[test]
+    fn test_max_size() {
+        let calendar = years::calendar();
+        for puzzle in calendar.puzzles() {
+            let solution = calendar.get(puzzle).expect("registered");
+            if let Some(max) = solution.max_size() {
+                assert_eq!(
+                    input(&calendar, puzzle, 1, max + 100),
+                    input(&calendar, puzzle, 1, max),
+                    "{}",
+                    puzzle
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Reads puzzle inputs from wherever the command line says they are.
+
+use crate::solution::Puzzle;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Where inputs are read from unless the command line says otherwise.
+pub const DEFAULT_DIR: &str = "files";
+
+/// Where to read an input from.
+pub enum Source {
+    /// This directory, laid out like [`path`], or the built-in input for puzzles missing from it.
+    Fallback(PathBuf),
+    /// This file, whatever the puzzle.
+    File(PathBuf),
+    /// This directory, laid out like [`path`].
+    Dir(PathBuf),
+    /// Standard input.
+    Stdin,
+}
+
+/// Why an input couldn't be read.
+#[derive(Debug)]
+pub enum InputError {
+    /// The file couldn't be read.
+    Io(PathBuf, std::io::Error),
+    /// Standard input couldn't be read.
+    Stdin(std::io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Io(path, err) => {
+                write!(f, "could not read input {}: {}", path.display(), err)
+            }
+            InputError::Stdin(err) => write!(f, "could not read input from stdin: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+fn file_name(day: usize) -> String {
+    format!("{:02}.txt", day)
+}
+
+/// Where a puzzle's input lives relative to an input directory, e.g. `2020/05.txt`.
+pub fn path(puzzle: Puzzle) -> PathBuf {
+    Path::new(&puzzle.year.to_string()).join(file_name(puzzle.day))
+}
+
+impl Default for Source {
+    fn default() -> Source {
+        Source::Fallback(PathBuf::from(DEFAULT_DIR))
+    }
+}
+
+impl Source {
+    /// The file `puzzle`'s input is read from, if it comes from one.
+    pub fn file(&self, puzzle: Puzzle) -> Option<PathBuf> {
+        match self {
+            Source::File(path) => Some(path.clone()),
+            Source::Fallback(dir) | Source::Dir(dir) => Some(dir.join(path(puzzle))),
+            Source::Stdin => None,
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    std::fs::read_to_string(path).map_err(|err| InputError::Io(path.to_path_buf(), err))
+}
+
+fn normalise(input: String) -> String {
+    input.replace("\r\n", "\n").trim_end().to_string()
+}
+
+/// Reads `puzzle`'s input from `source`, using `embedded` when [`Source::Fallback`]'s directory
+/// doesn't have it. Line endings are normalised to `\n` and trailing whitespace is dropped.
+pub fn load(puzzle: Puzzle, source: &Source, embedded: &str) -> Result<String, InputError> {
+    let input = match source {
+        Source::Fallback(dir) => match read_file(&dir.join(path(puzzle))) {
+            Err(InputError::Io(_, err)) if err.kind() == std::io::ErrorKind::NotFound => {
+                embedded.to_string()
+            }
+            res => res?,
+        },
+        Source::File(path) => read_file(path)?,
+        Source::Dir(dir) => read_file(&dir.join(path(puzzle)))?,
+        Source::Stdin => {
+            let mut res = String::new();
+            std::io::stdin()
+                .read_to_string(&mut res)
+                .map_err(InputError::Stdin)?;
+            res
+        }
+    };
+    Ok(normalise(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::{file_name, load, path, InputError, Source};
+    use crate::solution::Puzzle;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name(5), "05.txt");
+        assert_eq!(file_name(25), "25.txt");
+        assert_eq!(path(Puzzle::new(2019, 3)), PathBuf::from("2019/03.txt"));
+    }
+
+    #[test]
+    fn test_missing_dir() {
+        let source = Source::Dir(PathBuf::from("does/not/exist"));
+        match load(Puzzle::new(2020, 3), &source, "") {
+            Err(InputError::Io(path, _)) => {
+                assert_eq!(path, PathBuf::from("does/not/exist/2020/03.txt"))
+            }
+            _ => panic!("should not find input"),
+        }
+    }
+
+    #[test]
+    fn test_fallback() {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("2020")).expect("can create dir");
+        std::fs::write(dir.join("2020/01.txt"), "3\n4\n").expect("can write input");
+        let source = Source::Fallback(dir.clone());
+        assert_eq!(
+            load(Puzzle::new(2020, 1), &source, "1\n2").expect("from the dir"),
+            "3\n4"
+        );
+        assert_eq!(
+            load(Puzzle::new(2020, 2), &source, "1\n2").expect("embedded"),
+            "1\n2"
+        );
+        std::fs::remove_dir_all(&dir).expect("can clean up");
+    }
+
+    #[test]
+    fn test_embedded() {
+        let source = Source::Fallback(PathBuf::from("does/not/exist"));
+        assert_eq!(
+            load(Puzzle::new(2020, 1), &source, "1\r\n2\n").expect("embedded"),
+            "1\n2"
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! Advent of Code solutions and the tooling used to run, time and check them.
+//!
+//! Every year lives under [`years`], with one module per day exposing that day's parsers and
+//! solvers. [`years::calendar`] collects them all behind the [`solution::Solution`] trait, which is
+//! how the `advent_of_code_2020` binary runs them:
+//!
+//! ```
+//! use advent_of_code_2020::solution::Puzzle;
+//! use advent_of_code_2020::years::{self, y2020::day08};
+//!
+//! let mut machine = "nop +0\nacc +1\njmp -2".parse::<day08::Machine>().unwrap();
+//! assert_eq!(day08::part1(&mut machine), Ok(1));
+//!
+//! let calendar = years::calendar();
+//! let day01 = calendar.get(Puzzle::new(2020, 1)).unwrap();
+//! let run = day01.run("1721\n979\n366\n299\n675\n1456").unwrap();
+//! assert_eq!(run.part1, "514579");
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod crosscheck;
+pub mod error;
+pub mod fetch;
+pub mod generate;
+pub mod input;
+pub mod memory;
+#[cfg(test)]
+mod mock;
+pub mod output;
+pub mod parallel;
+pub mod report;
+pub mod scaffold;
+pub mod scale;
+pub mod selector;
+pub mod solution;
+pub mod stats;
+pub mod submit;
+pub mod watch;
+pub mod years;
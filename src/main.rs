@@ -1,158 +1,972 @@
-use std::time::Duration;
+use advent_of_code_2020::answers::{self, Answers, Expected, Status};
+use advent_of_code_2020::baseline::{self, Baseline, Verdict};
+use advent_of_code_2020::bench::{self, Repeats};
+use advent_of_code_2020::crosscheck;
+use advent_of_code_2020::fetch::{self, Fetched, Fetcher};
+use advent_of_code_2020::generate;
+use advent_of_code_2020::input::{self, Source};
+use advent_of_code_2020::memory;
+use advent_of_code_2020::output::{self, DayResult, Format, Mode};
+use advent_of_code_2020::report::{self, Report};
+use advent_of_code_2020::scale;
+use advent_of_code_2020::selector::{self, Selection};
+use advent_of_code_2020::solution::{Calendar, Puzzle, Registry, Timings};
+use advent_of_code_2020::submit::{Outcome, Submitter};
+use advent_of_code_2020::watch::{self, Watcher};
+use advent_of_code_2020::{parallel, scaffold, years};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-mod days;
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: memory::Counting = memory::Counting;
 
 enum Runnable {
-    Single(usize),
-    Range(usize, usize),
-    Repeat(usize, usize),
-    AllRep(usize),
+    Days(Vec<Puzzle>),
+    Repeat(Vec<Puzzle>, Repeats),
+    AllRep(Vec<Puzzle>, Repeats),
 }
 
-type RunFunc = fn() -> (String, String, Duration);
+/// `fetch [DAYS] [--url URL] [--session TOKEN] [--input-dir DIR]` downloads any inputs that are
+/// not cached yet, for every solved puzzle by default.
+fn fetch(mut args: impl Iterator<Item = String>, calendar: &Calendar, year: usize) {
+    let mut fetcher = Fetcher::default();
+    let mut puzzles = Vec::new();
+    let mut invalid = false;
+
+    while let Some(arg) = args.next() {
+        if arg == "--url" || arg == "--session" || arg == "--input-dir" {
+            match args.next() {
+                Some(url) if arg == "--url" => fetcher.url = url,
+                Some(session) if arg == "--session" => fetcher.session = Some(session),
+                Some(dir) => fetcher.dir = PathBuf::from(dir),
+                None => {
+                    println!("missing value for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else {
+            match selector::parse(&arg, year, |y| calendar.year(y).map(Registry::days)) {
+                Ok(Selection {
+                    puzzles: selected,
+                    repeats: None,
+                }) => puzzles.extend(selected),
+                Ok(_) => {
+                    println!("repeats can not be used with fetch: {}", arg);
+                    invalid = true;
+                }
+                Err(err) => {
+                    println!("{}", err);
+                    invalid = true;
+                }
+            }
+        }
+    }
+
+    if invalid {
+        std::process::exit(1);
+    }
+    if puzzles.is_empty() {
+        puzzles.extend(calendar.puzzles());
+    }
+
+    let mut failed = false;
+    for puzzle in puzzles {
+        match fetcher.fetch(puzzle) {
+            Ok(Fetched::Cached(path)) => {
+                println!("{}: already have {}", puzzle, path.display())
+            }
+            Ok(Fetched::Downloaded(path, bytes)) => {
+                println!("{}: saved {} bytes to {}", puzzle, bytes, path.display())
+            }
+            Err(err @ fetch::FetchError::NoSession) => {
+                println!("{}", err);
+                std::process::exit(1);
+            }
+            Err(err) => {
+                println!("{}", err);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// `submit [YEAR/]DAY PART [--url URL] [--session TOKEN] [--history PATH] [--input PATH]` runs a
+/// day and submits the answer to one of its parts.
+fn submit(mut args: impl Iterator<Item = String>, calendar: &Calendar, year: usize) {
+    let mut submitter = Submitter::default();
+    let mut source = Source::default();
+    let mut positional = Vec::new();
+    let mut invalid = false;
+
+    while let Some(arg) = args.next() {
+        if arg == "--url" || arg == "--session" || arg == "--history" || arg == "--input" {
+            match args.next() {
+                Some(url) if arg == "--url" => submitter.url = url,
+                Some(session) if arg == "--session" => submitter.session = Some(session),
+                Some(path) if arg == "--history" => submitter.history = PathBuf::from(path),
+                Some(path) if path == "-" => source = Source::Stdin,
+                Some(path) => source = Source::File(PathBuf::from(path)),
+                None => {
+                    println!("missing value for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    let (puzzle, part) = match positional.as_slice() {
+        [day, part] => match (selector::parse_puzzle(day, year), part.as_str()) {
+            (Ok(puzzle), "1") | (Ok(puzzle), "2") if !invalid => (puzzle, part == "2"),
+            _ => {
+                println!("expected a day and a part (1 or 2), found {} {}", day, part);
+                std::process::exit(1);
+            }
+        },
+        _ => {
+            println!("usage: submit [YEAR/]DAY PART");
+            std::process::exit(1);
+        }
+    };
+
+    let solution = match calendar.get(puzzle) {
+        Some(solution) => solution,
+        None => {
+            println!("{}: no solution registered", puzzle);
+            std::process::exit(1);
+        }
+    };
+    let answer = input::load(puzzle, &source, solution.input())
+        .map_err(|err| err.to_string())
+        .and_then(|input| solution.run(&input).map_err(|err| err.to_string()))
+        .map(|run| if part { run.part2 } else { run.part1 });
+    let answer = match answer {
+        Ok(answer) => answer,
+        Err(err) => {
+            println!("{}: {}", puzzle, err);
+            std::process::exit(1);
+        }
+    };
+
+    let part = if part { 2 } else { 1 };
+    println!("{} part {}: submitting {}", puzzle, part, answer);
+    match submitter.submit(puzzle, part, &answer) {
+        Ok(reply) => {
+            println!("{}: {}", reply.outcome.name(), reply.message);
+            if reply.outcome != Outcome::Right {
+                std::process::exit(1);
+            }
+        }
+        Err(err) => {
+            println!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `new [YEAR/]DAY` generates, registers and creates an input file for a day's module.
+fn new_day(args: impl Iterator<Item = String>, year: usize) {
+    let args = args.collect::<Vec<_>>();
+    let puzzle = match args.as_slice() {
+        [day] => selector::parse_puzzle(day, year).ok(),
+        _ => None,
+    };
+    let puzzle = match puzzle {
+        Some(puzzle) => puzzle,
+        None => {
+            println!("usage: new [YEAR/]DAY");
+            std::process::exit(1);
+        }
+    };
+
+    match scaffold::create(Path::new("."), puzzle) {
+        Ok(paths) => paths
+            .iter()
+            .for_each(|path| println!("wrote {}", path.display())),
+        Err(err) => {
+            println!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `report [DAYS] [--out DIR] [--redact] [--warmup N] [--jobs N] [--input-dir DIR]` runs days,
+/// every solved day of the year by default, and writes their answers and timings as Markdown and
+/// HTML. Selectors take repeats as usual, e.g. `report .:100`.
+fn report(mut args: impl Iterator<Item = String>, calendar: &Calendar, year: usize) {
+    let mut selected = Vec::new();
+    let mut dir = PathBuf::from(report::DEFAULT_DIR);
+    let mut source = Source::default();
+    let mut redact = false;
+    let mut warmup = 0;
+    let mut jobs = 1;
+    let mut invalid = false;
+
+    while let Some(arg) = args.next() {
+        if arg == "--out" || arg == "--input-dir" {
+            match args.next() {
+                Some(path) if arg == "--out" => dir = PathBuf::from(path),
+                Some(path) => source = Source::Dir(PathBuf::from(path)),
+                None => {
+                    println!("missing path for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else if arg == "--warmup" || arg == "--jobs" || arg == "-j" {
+            match args.next().map(|n| n.parse::<usize>()) {
+                Some(Ok(n)) if arg == "--warmup" => warmup = n,
+                Some(Ok(0)) => jobs = parallel::available_jobs(),
+                Some(Ok(n)) => jobs = n,
+                _ => {
+                    println!("illegal value for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else if arg == "--redact" {
+            redact = true;
+        } else {
+            match selector::parse(&arg, year, |y| calendar.year(y).map(Registry::days)) {
+                Ok(Selection { puzzles, repeats }) => {
+                    selected.push((puzzles, repeats.unwrap_or(Repeats::Count(1))))
+                }
+                Err(err) => {
+                    println!("{}", err);
+                    invalid = true;
+                }
+            }
+        }
+    }
+
+    if invalid {
+        std::process::exit(1);
+    }
+    if selected.is_empty() {
+        let puzzles = calendar
+            .year(year)
+            .map_or_else(Vec::new, |registry| registry.puzzles().collect());
+        selected.push((puzzles, Repeats::Count(1)));
+    }
+
+    let mut results = Vec::new();
+    let mut errored = false;
+    for (puzzles, repeats) in selected {
+        parallel::run_ordered(
+            &puzzles,
+            jobs,
+            |&puzzle| {
+                let solution = calendar
+                    .get(puzzle)
+                    .ok_or_else(|| "no solution registered".to_string())?;
+                let input = input::load(puzzle, &source, solution.input())
+                    .map_err(|err| err.to_string())?;
+                bench::measure(puzzle, solution, &input, warmup, repeats)
+                    .map_err(|err| err.to_string())
+            },
+            |&puzzle, result| match result {
+                Ok(result) => {
+                    println!("{}: {:?}", puzzle, result.summary().total.median);
+                    results.push(result);
+                }
+                Err(err) => {
+                    println!("{}: {}", puzzle, err);
+                    errored = true;
+                }
+            },
+        );
+    }
+
+    let report = Report {
+        results: &results,
+        redact,
+    };
+    match report.write(&dir) {
+        Ok(paths) => paths
+            .iter()
+            .for_each(|path| println!("wrote {}", path.display())),
+        Err(err) => {
+            println!("{}", err);
+            std::process::exit(1);
+        }
+    }
+    if errored {
+        std::process::exit(1);
+    }
+}
+
+/// `generate [YEAR/]DAY [--seed N] [--size N]` prints a random input for a day, which is the same
+/// every time for the same seed and size.
+fn generate(mut args: impl Iterator<Item = String>, calendar: &Calendar, year: usize) {
+    let mut seed = generate::DEFAULT_SEED;
+    let mut size = generate::DEFAULT_SIZE;
+    let mut positional = Vec::new();
+    let mut invalid = false;
+
+    while let Some(arg) = args.next() {
+        if arg == "--seed" || arg == "--size" {
+            match args.next().map(|n| n.parse::<u64>()) {
+                Some(Ok(n)) if arg == "--seed" => seed = n,
+                Some(Ok(n)) => size = n as usize,
+                _ => {
+                    println!("illegal value for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    let puzzle = match positional.as_slice() {
+        [day] if !invalid => selector::parse_puzzle(day, year).ok(),
+        _ => None,
+    };
+    let puzzle = match puzzle {
+        Some(puzzle) => puzzle,
+        None => {
+            println!("usage: generate [YEAR/]DAY [--seed N] [--size N]");
+            std::process::exit(1);
+        }
+    };
+
+    if let Some(max) = calendar.get(puzzle).and_then(|s| s.max_size()) {
+        if size > max {
+            eprintln!("{}: inputs stop growing at size {}", puzzle, max);
+        }
+    }
+    match generate::input(calendar, puzzle, seed, size) {
+        Some(input) => println!("{}", input),
+        None => {
+            println!("{}: no generator registered", puzzle);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `scale [YEAR/]DAY [--from N] [--to N] [--steps N] [--seed N] [--repeats N] [--out PATH]` times
+/// a day on generated inputs of growing size, estimates how each phase grows and plots the times
+/// on log-log axes as SVG.
+fn scale(mut args: impl Iterator<Item = String>, calendar: &Calendar, year: usize) {
+    let (mut from, mut to, mut steps) =
+        (scale::DEFAULT_FROM, scale::DEFAULT_TO, scale::DEFAULT_STEPS);
+    let mut seed = generate::DEFAULT_SEED;
+    let mut repeats = Repeats::Count(3);
+    let mut out = None;
+    let mut positional = Vec::new();
+    let mut invalid = false;
+
+    while let Some(arg) = args.next() {
+        if arg == "--from" || arg == "--to" || arg == "--steps" || arg == "--seed" {
+            match args.next().map(|n| n.parse::<u64>()) {
+                Some(Ok(n)) if arg == "--from" => from = n as usize,
+                Some(Ok(n)) if arg == "--to" => to = n as usize,
+                Some(Ok(n)) if arg == "--steps" => steps = n as usize,
+                Some(Ok(n)) => seed = n,
+                _ => {
+                    println!("illegal value for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else if arg == "--repeats" {
+            match args.next().map(|r| r.parse::<Repeats>()) {
+                Some(Ok(r)) => repeats = r,
+                Some(Err(err)) => {
+                    println!("{}", err);
+                    invalid = true;
+                }
+                None => {
+                    println!("missing value for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else if arg == "--out" {
+            match args.next() {
+                Some(path) => out = Some(PathBuf::from(path)),
+                None => {
+                    println!("missing path for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    let puzzle = match positional.as_slice() {
+        [day] if !invalid => selector::parse_puzzle(day, year).ok(),
+        _ => None,
+    };
+    let (puzzle, solution) = match puzzle.and_then(|p| Some((p, calendar.get(p)?))) {
+        Some(found) => found,
+        None => {
+            println!("usage: scale [YEAR/]DAY [--from N] [--to N] [--steps N] [--seed N] [--repeats N] [--out PATH]");
+            std::process::exit(1);
+        }
+    };
+
+    println!("{}: {}", puzzle, solution.title());
+    let mut sizes = scale::sizes(from, to, steps);
+    if let Some(max) = solution.max_size().filter(|&max| to > max) {
+        println!("inputs stop growing at size {}, so sizes stop there", max);
+        sizes.retain(|&size| size < max);
+        sizes.push(max);
+    }
+    if let Some(unscaled) = solution.unscaled() {
+        println!(
+            "size doesn't grow {}, so these estimates say little about the solver",
+            unscaled
+        );
+    }
+    let points = scale::measure(puzzle, solution, &sizes, seed, repeats, |point| {
+        let timings = &point.timings;
+        println!(
+            "size {:>6} ({} bytes): parse {:?}, part 1 {:?}, part 2 {:?}, total {:?}",
+            point.size,
+            point.bytes,
+            timings.parse,
+            timings.part1,
+            timings.part2,
+            timings.total()
+        );
+    });
+    let points = match points {
+        Ok(points) => points,
+        Err(err) => {
+            println!("{}", err);
+            std::process::exit(1);
+        }
+    };
+    if points.len() < sizes.len() {
+        println!("stopped early, a run took longer than {:?}", scale::SLOW);
+    }
+
+    for ((name, _), fit) in scale::PHASES.iter().zip(scale::fit_phases(&points)) {
+        match fit {
+            Some(fit) => println!(
+                "{}: {} (log-log slope {:.2}){}",
+                name,
+                fit.class.name(),
+                fit.exponent,
+                if fit.poor() { ", poor fit" } else { "" }
+            ),
+            None => println!("{}: too few sizes to estimate", name),
+        }
+    }
+
+    let out = out.unwrap_or_else(|| {
+        Path::new(report::DEFAULT_DIR)
+            .join(format!("scale-{}-day{:02}.svg", puzzle.year, puzzle.day))
+    });
+    let written = match out.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => std::fs::create_dir_all(dir),
+        _ => Ok(()),
+    }
+    .and_then(|_| std::fs::write(&out, scale::plot(puzzle, &points)));
+    match written {
+        Ok(()) => println!("wrote {}", out.display()),
+        Err(err) => {
+            println!("could not write {}: {}", out.display(), err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `crosscheck [DAYS...] [--cases N] [--size N] [--seed N] [--input-dir DIR]` checks each day's
+/// answers against its reference solvers on the real input and on generated ones, printing every
+/// disagreement with the smallest input it could shrink it to. Checks every day by default.
+fn crosscheck(mut args: impl Iterator<Item = String>, calendar: &Calendar, year: usize) {
+    let mut puzzles = Vec::new();
+    let mut cases = crosscheck::DEFAULT_CASES;
+    let mut size = crosscheck::DEFAULT_SIZE;
+    let mut seed = generate::DEFAULT_SEED;
+    let mut source = Source::default();
+    let mut invalid = false;
+
+    while let Some(arg) = args.next() {
+        if arg == "--cases" || arg == "--size" || arg == "--seed" {
+            match args.next().map(|n| n.parse::<u64>()) {
+                Some(Ok(n)) if arg == "--cases" => cases = n as usize,
+                Some(Ok(n)) if arg == "--size" => size = n as usize,
+                Some(Ok(n)) => seed = n,
+                _ => {
+                    println!("illegal value for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else if arg == "--input-dir" {
+            match args.next() {
+                Some(path) => source = Source::Dir(PathBuf::from(path)),
+                None => {
+                    println!("missing path for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else {
+            match selector::parse(&arg, year, |y| calendar.year(y).map(Registry::days)) {
+                Ok(selection) => puzzles.extend(selection.puzzles),
+                Err(err) => {
+                    println!("{}", err);
+                    invalid = true;
+                }
+            }
+        }
+    }
+
+    if invalid {
+        println!("usage: crosscheck [DAYS...] [--cases N] [--size N] [--seed N] [--input-dir DIR]");
+        std::process::exit(1);
+    }
+    if puzzles.is_empty() {
+        puzzles = calendar.puzzles().collect();
+    }
+
+    // panics are reported as errors, and shrinking an input can cause a great many of them
+    std::panic::set_hook(Box::new(|_| {}));
+    let mut failed = false;
+    for puzzle in puzzles {
+        let solution = match calendar.get(puzzle) {
+            Some(solution) => solution,
+            None => {
+                println!("{}: no solution registered", puzzle);
+                failed = true;
+                continue;
+            }
+        };
+        let outcome = input::load(puzzle, &source, solution.input())
+            .map_err(|err| err.to_string())
+            .and_then(|input| {
+                crosscheck::run(solution, &input, seed, cases, size).map_err(|err| err.to_string())
+            });
+        let outcome = match outcome {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                println!("{}: {}", puzzle, err);
+                failed = true;
+                continue;
+            }
+        };
+
+        if outcome.parts.is_empty() {
+            println!("{}: no reference solvers", puzzle);
+            continue;
+        }
+        let parts = outcome
+            .parts
+            .iter()
+            .map(usize::to_string)
+            .collect::<Vec<_>>()
+            .join(" and ");
+        println!(
+            "{}: part {} checked on {} inputs, {} disagreements",
+            puzzle,
+            parts,
+            outcome.checked,
+            outcome.disagreements.len()
+        );
+        for disagreement in &outcome.disagreements {
+            failed = true;
+            let answer = match &disagreement.answer {
+                Ok(answer) => answer.clone(),
+                Err(err) => format!("error: {}", err),
+            };
+            println!(
+                "  part {} on {}: answered {}, reference answered {}, minimised input:",
+                disagreement.part, disagreement.source, answer, disagreement.reference
+            );
+            for line in disagreement.input.lines() {
+                println!("    {}", line);
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// `--watch` re-runs a single day every time its input file changes, until interrupted. Only the
+/// input is watched: changing a day's code means rebuilding, which `cargo watch -x run` covers.
+fn watch_day(
+    format: Format,
+    mode: Mode,
+    puzzle: Puzzle,
+    path: PathBuf,
+    run: impl Fn() -> Result<DayResult, String>,
+) -> ! {
+    let mut watcher = Watcher::new(path);
+    let mut previous: Option<DayResult> = None;
+    output::print_header(format);
+    loop {
+        match run() {
+            Ok(result) => {
+                output::print_result(format, mode, &result);
+                if let Some(previous) = &previous {
+                    output::print_diff(format, &watch::diff(previous, &result));
+                }
+                previous = Some(result);
+            }
+            Err(err) => output::print_error(format, puzzle, &err),
+        }
+        output::print_note(
+            format,
+            &format!("watching {} for changes", watcher.path().display()),
+        );
+        watcher.wait(watch::POLL_INTERVAL);
+    }
+}
 
 fn main() {
-    let opts: Vec<RunFunc> = vec![
-        days::day01::run,
-        days::day02::run,
-        days::day03::run,
-        days::day04::run,
-        days::day05::run,
-        days::day06::run,
-        days::day07::run,
-        days::day08::run,
-        days::day09::run,
-        days::day10::run,
-        days::day11::run,
-        days::day12::run,
-        days::day13::run,
-        days::day14::run,
-        days::day15::run,
-        days::day16::run,
-        days::day17::run,
-        days::day18::run,
-        days::day19::run,
-        days::day20::run,
-        days::day21::run,
-        days::day22::run,
-        days::day23::run,
-        days::day24::run,
-        days::day25::run,
-    ];
-
-    let args = std::env::args().skip(1);
+    // leaked so that days can run on threads that may be abandoned, see --timeout
+    let calendar: &'static Calendar = Box::leak(Box::new(years::calendar()));
+    let mut year = calendar
+        .latest_year()
+        .expect("at least one year is registered");
+
+    let mut args = std::env::args().skip(1).peekable();
+    if args.peek().map(String::as_str) == Some("fetch") {
+        args.next();
+        return fetch(args, calendar, year);
+    }
+    if args.peek().map(String::as_str) == Some("submit") {
+        args.next();
+        return submit(args, calendar, year);
+    }
+    if args.peek().map(String::as_str) == Some("new") {
+        args.next();
+        return new_day(args, year);
+    }
+    if args.peek().map(String::as_str) == Some("report") {
+        args.next();
+        return report(args, calendar, year);
+    }
+    if args.peek().map(String::as_str) == Some("generate") {
+        args.next();
+        return generate(args, calendar, year);
+    }
+    if args.peek().map(String::as_str) == Some("scale") {
+        args.next();
+        return scale(args, calendar, year);
+    }
+    if args.peek().map(String::as_str) == Some("crosscheck") {
+        args.next();
+        return crosscheck(args, calendar, year);
+    }
 
     let mut actions = Vec::new();
+    let mut source = Source::default();
+    let mut format = Format::Text;
+    let mut warmup = 0;
+    let mut save_baseline = None;
+    let mut compare_baseline = None;
+    let mut threshold = 0.05;
+    let mut fail_on_regression = false;
+    let mut answers_path = None;
+    let mut record_answers = false;
+    let mut jobs = 1;
+    let mut timeout = None;
+    let mut watching = false;
+    let mut invalid = false;
 
-    for arg in args {
-        if arg == "!" {
-            actions.push(Runnable::Single(opts.len()))
-        } else if let Some(repeats) = arg.strip_prefix('@') {
-            match repeats.parse::<usize>() {
-                Ok(i) => actions.push(Runnable::AllRep(i)),
-                _ => println!("illegal value for repeats: {}", repeats),
-            }
-        } else if arg == "." {
-            actions.push(Runnable::Range(1, opts.len()))
-        } else if arg.contains(':') {
-            let parts = arg.split(':').collect::<Vec<_>>();
-            if parts.len() != 2 {
-                println!("too many parts: {}", arg);
-                continue;
+    while let Some(arg) = args.next() {
+        if arg == "--format" {
+            match args.next().map(|f| f.parse::<Format>()) {
+                Some(Ok(f)) => format = f,
+                Some(Err(err)) => {
+                    println!("{}", err);
+                    invalid = true;
+                }
+                None => {
+                    println!("missing value for --format");
+                    invalid = true;
+                }
+            }
+        } else if arg == "--year" {
+            match args.next().map(|y| y.parse::<usize>()) {
+                Some(Ok(y)) if calendar.year(y).is_some() => year = y,
+                Some(Ok(y)) => {
+                    println!("no solutions for year {}", y);
+                    invalid = true;
+                }
+                _ => {
+                    println!("illegal value for --year");
+                    invalid = true;
+                }
+            }
+        } else if arg == "--warmup" {
+            match args.next().map(|w| w.parse::<usize>()) {
+                Some(Ok(w)) => warmup = w,
+                _ => {
+                    println!("illegal value for --warmup");
+                    invalid = true;
+                }
             }
-            match parts[0].parse::<usize>() {
-                Ok(i) if i > 0 && i <= opts.len() => match parts[1].parse::<usize>() {
-                    Ok(repeats) => actions.push(Runnable::Repeat(i, repeats)),
-                    _ => println!("illegal value for repeats: {}", arg),
-                },
-                _ => println!("illegal repeats arg: {}", arg),
+        } else if arg == "--jobs" || arg == "-j" {
+            match args.next().map(|j| j.parse::<usize>()) {
+                Some(Ok(0)) => jobs = parallel::available_jobs(),
+                Some(Ok(j)) => jobs = j,
+                _ => {
+                    println!("illegal value for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else if arg == "--timeout" {
+            match args.next().map(|t| bench::parse_duration(&t)) {
+                Some(Some(t)) if t > Duration::default() => timeout = Some(t),
+                _ => {
+                    println!("illegal value for --timeout");
+                    invalid = true;
+                }
+            }
+        } else if arg == "--save-baseline" || arg == "--baseline" {
+            match args.next() {
+                Some(name) if arg == "--save-baseline" => save_baseline = Some(name),
+                Some(name) => compare_baseline = Some(name),
+                None => {
+                    println!("missing name for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else if arg == "--threshold" {
+            match args.next().map(|t| t.trim_end_matches('%').parse::<f64>()) {
+                Some(Ok(t)) if t >= 0.0 => threshold = t / 100.0,
+                _ => {
+                    println!("illegal value for --threshold");
+                    invalid = true;
+                }
             }
-        } else if arg.contains('-') {
-            let parts = arg.split('-').collect::<Vec<_>>();
-            match (parts[0].parse::<usize>(), parts[1].parse::<usize>()) {
-                (Ok(a), Ok(b)) if a > 0 && a <= opts.len() && b > 0 && b <= opts.len() && a < b => {
-                    actions.push(Runnable::Range(a, b))
+        } else if arg == "--verify" {
+            answers_path.get_or_insert_with(|| PathBuf::from(answers::DEFAULT_PATH));
+        } else if arg == "--answers" {
+            match args.next() {
+                Some(path) => answers_path = Some(PathBuf::from(path)),
+                None => {
+                    println!("missing path for --answers");
+                    invalid = true;
+                }
+            }
+        } else if arg == "--record-answers" {
+            record_answers = true;
+            answers_path.get_or_insert_with(|| PathBuf::from(answers::DEFAULT_PATH));
+        } else if arg == "--fail-on-regression" {
+            fail_on_regression = true;
+        } else if arg == "--input" || arg == "--input-dir" {
+            match args.next() {
+                Some(path) if path == "-" && arg == "--input" => source = Source::Stdin,
+                Some(path) if arg == "--input" => source = Source::File(PathBuf::from(path)),
+                Some(path) => source = Source::Dir(PathBuf::from(path)),
+                None => {
+                    println!("missing path for {}", arg);
+                    invalid = true;
+                }
+            }
+        } else if arg == "--memory" {
+            if !memory::enable() {
+                println!("--memory needs a build with the count-allocations feature");
+                invalid = true;
+            }
+        } else if arg == "--watch" {
+            watching = true;
+        } else if arg == "--stdin" {
+            source = Source::Stdin;
+        } else if let Some(repeats) = arg.strip_prefix('@') {
+            match repeats.parse::<Repeats>() {
+                Ok(repeats) => {
+                    let puzzles = calendar
+                        .year(year)
+                        .map_or_else(Vec::new, |registry| registry.puzzles().collect());
+                    actions.push(Runnable::AllRep(puzzles, repeats))
+                }
+                Err(err) => {
+                    println!("{}", err);
+                    invalid = true;
                 }
-                _ => println!("invalid range: {}", arg),
             }
         } else {
-            match arg.parse::<usize>() {
-                Ok(i) if i > 0 && i <= opts.len() => {
-                    actions.push(Runnable::Single(i));
+            match selector::parse(&arg, year, |y| calendar.year(y).map(Registry::days)) {
+                Ok(Selection {
+                    puzzles,
+                    repeats: None,
+                }) => actions.push(Runnable::Days(puzzles)),
+                Ok(Selection {
+                    puzzles,
+                    repeats: Some(repeats),
+                }) => actions.push(Runnable::Repeat(puzzles, repeats)),
+                Err(err) => {
+                    println!("{}", err);
+                    invalid = true;
                 }
-                _ => println!("invalid range: {}", arg),
             }
         }
     }
 
-    let mut cum_duration = Duration::default();
+    if invalid {
+        std::process::exit(1);
+    }
+
+    let single = match actions.as_slice() {
+        [Runnable::Days(puzzles)] if puzzles.len() == 1 => {
+            Some((Mode::Single, puzzles[0], 0, Repeats::Count(1)))
+        }
+        [Runnable::Repeat(puzzles, repeats)] if puzzles.len() == 1 => {
+            Some((Mode::Repeat, puzzles[0], warmup, *repeats))
+        }
+        _ => None,
+    };
+    if let Source::File(_) | Source::Stdin = source {
+        if single.is_none() {
+            println!("an input file or stdin can only be used when running a single day");
+            std::process::exit(1);
+        }
+    }
+    if watching {
+        if single.is_none() {
+            println!("--watch can only be used when running a single day");
+            std::process::exit(1);
+        }
+        if let Source::Stdin = source {
+            println!("--watch needs an input file, not stdin");
+            std::process::exit(1);
+        }
+    }
+
+    let run_day = |puzzle: Puzzle, warmup: usize, repeats: Repeats| -> Result<DayResult, String> {
+        let solution = calendar
+            .get(puzzle)
+            .ok_or_else(|| "no solution registered".to_string())?;
+        let input =
+            input::load(puzzle, &source, solution.input()).map_err(|err| err.to_string())?;
+        let measure = move || {
+            bench::measure(puzzle, solution, &input, warmup, repeats).map_err(|err| err.to_string())
+        };
+        match timeout {
+            Some(limit) => parallel::with_timeout(limit, measure)
+                .unwrap_or_else(|| Err(format!("timed out after {:?}", limit))),
+            None => measure(),
+        }
+    };
+
+    if let (true, Some((mode, puzzle, warmup, repeats))) = (watching, single) {
+        let path = source
+            .file(puzzle)
+            .expect("watched inputs come from a file");
+        watch_day(format, mode, puzzle, path, || {
+            run_day(puzzle, warmup, repeats)
+        });
+    }
+
+    let baseline = compare_baseline.map(|name| match Baseline::load(&name) {
+        Ok(baseline) => baseline,
+        Err(err) => {
+            println!("{}", err);
+            std::process::exit(1);
+        }
+    });
+
+    let mut expected = answers_path.as_ref().map(|path| match Answers::load(path) {
+        Ok(answers) => answers,
+        Err(err) => {
+            println!("{}", err);
+            std::process::exit(1);
+        }
+    });
+
+    output::print_header(format);
+
+    let mut cum_duration = Timings::default();
+    let mut cpu_time = Duration::default();
+    let mut results = Vec::new();
+    let mut errored = false;
+
+    let mut report = |mode: Mode, puzzle: Puzzle, result: Result<DayResult, String>| match result {
+        Ok(result) => {
+            output::print_result(format, mode, &result);
+            match mode {
+                Mode::Repeat => result.runs.iter().for_each(|&run| cum_duration += run),
+                Mode::Single | Mode::Fastest => cum_duration += result.min(),
+            }
+            cpu_time += result.elapsed;
+            results.push(result);
+        }
+        Err(err) => {
+            output::print_error(format, puzzle, &err);
+            errored = true;
+        }
+    };
 
+    let start = Instant::now();
     for action in actions {
-        match action {
-            Runnable::Single(i) => {
-                println!();
-                println!("day {}", i);
-                let (p1, p2, duration) = opts[i - 1]();
-                println!("    part 1: {}", p1);
-                println!("    part 2: {}", p2);
-                println!("    time:   {:?}", duration);
-                cum_duration += duration;
-            }
-            Runnable::Range(first, last) => {
-                for i in first..=last {
-                    println!();
-                    println!("day {}", i);
-                    let (p1, p2, duration) = opts[i - 1]();
-                    println!("    part 1: {}", p1);
-                    println!("    part 2: {}", p2);
-                    println!("    time:   {:?}", duration);
-                    cum_duration += duration;
-                }
-            }
-            Runnable::Repeat(i, repeats) => {
-                let mut min = Duration::from_secs(100_000);
-                let mut max = Duration::default();
-                let mut running = Duration::default();
-                println!();
-                println!("day {} - {} runs", i, repeats);
-                for rep in 0..repeats {
-                    let (p1, p2, duration) = opts[i - 1]();
-                    if rep == 0 {
-                        println!("    part 1: {}", p1);
-                        println!("    part 2: {}", p2);
-                    }
-                    running += duration;
-                    min = Duration::min(min, duration);
-                    max = Duration::max(max, duration);
-                    cum_duration += duration;
-                }
-                println!("times:");
-                println!("    minimum: {:?}", min);
-                println!("    average: {:?}", running / repeats as u32);
-                println!("    maximum: {:?}", max);
-            }
-            Runnable::AllRep(repeats) => {
-                let mut total = Duration::default();
-                for (i, t) in opts.iter().enumerate() {
-                    println!();
-                    println!("day {} - {} runs", i, repeats);
-                    let mut min = Duration::from_secs(100_000);
-                    for rep in 0..repeats {
-                        let (p1, p2, duration) = t();
-                        if rep == 0 {
-                            println!("    part 1: {}", p1);
-                            println!("    part 2: {}", p2);
-                        }
-                        min = Ord::min(min, duration);
-                        total += duration;
-                    }
-                    println!("    fastest: {:?}", min);
-                    cum_duration += min;
-                }
-            }
-        }
-    }
-
-    println!();
-    println!("total runtime: {:?}", cum_duration);
+        let (mode, puzzles, warmup, repeats) = match action {
+            Runnable::Days(puzzles) => (Mode::Single, puzzles, 0, Repeats::Count(1)),
+            Runnable::Repeat(puzzles, repeats) => (Mode::Repeat, puzzles, warmup, repeats),
+            Runnable::AllRep(puzzles, repeats) => (Mode::Fastest, puzzles, warmup, repeats),
+        };
+        parallel::run_ordered(
+            &puzzles,
+            jobs,
+            |&puzzle| run_day(puzzle, warmup, repeats),
+            |&puzzle, result| report(mode, puzzle, result),
+        );
+    }
+
+    output::print_total(format, &cum_duration, start.elapsed(), cpu_time, jobs);
+
+    let current = Baseline::from_results(&results);
+    if let Some(name) = save_baseline {
+        match current.save(&name) {
+            Ok(path) => {
+                output::print_note(format, &format!("saved baseline to {}", path.display()))
+            }
+            Err(err) => output::print_note(format, &err),
+        }
+    }
+
+    let mut failed = false;
+    if let Some(answers) = expected.as_mut() {
+        let checks = results
+            .iter()
+            .map(|r| (answers.verify(r.puzzle, r.input, &r.part1, &r.part2), r))
+            .collect::<Vec<_>>();
+        output::print_verification(format, &checks);
+        failed = checks.iter().any(|(v, _)| v.status() == Status::Fail);
+
+        if record_answers {
+            let unknown = checks
+                .iter()
+                .filter(|(v, _)| v.expected.is_none())
+                .map(|&(_, r)| r)
+                .collect::<Vec<_>>();
+            for r in &unknown {
+                let expected = Expected {
+                    part1: r.part1.clone(),
+                    part2: r.part2.clone(),
+                };
+                answers.insert(r.puzzle, r.input, expected);
+            }
+            let path = answers_path.expect("recording implies an answers path");
+            match answers.save(&path) {
+                Ok(()) => output::print_note(
+                    format,
+                    &format!("recorded {} answers to {}", unknown.len(), path.display()),
+                ),
+                Err(err) => output::print_note(format, &err),
+            }
+        }
+    }
+
+    if let Some(baseline) = baseline {
+        let comparisons = baseline::compare(&baseline, &current, threshold);
+        output::print_comparison(format, &comparisons);
+        let regressed = comparisons.iter().any(|c| c.verdict == Verdict::Slower);
+        if regressed && fail_on_regression {
+            std::process::exit(2);
+        }
+    }
+
+    if failed || errored {
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,158 @@
+//! Counts allocations, to see how much memory each day uses alongside how long it takes.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// What a piece of code allocated while it ran.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Usage {
+    /// How many allocations were made.
+    pub allocations: u64,
+    /// Every byte requested, including growing reallocations, whether or not it was freed since.
+    pub bytes: u64,
+    /// The most memory that was live at once, on top of what was live before.
+    pub peak: u64,
+}
+
+#[derive(Copy, Clone)]
+struct Counts {
+    allocations: u64,
+    bytes: u64,
+    live: i64,
+    peak: i64,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // Per thread so that days running in parallel don't count each other's allocations. A const
+    // initialised `Cell` of plain data never allocates itself, which matters inside an allocator.
+    static COUNTS: Cell<Counts> = const {
+        Cell::new(Counts {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn record(allocated: usize, freed: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // fails during thread teardown, when there is nothing left worth counting
+    let _ = COUNTS.try_with(|counts| {
+        let mut c = counts.get();
+        if allocated > 0 {
+            c.allocations += 1;
+            c.bytes += allocated as u64;
+        }
+        c.live += allocated as i64 - freed as i64;
+        c.peak = c.peak.max(c.live);
+        counts.set(c);
+    });
+}
+
+/// The system allocator, counting what each thread allocates once [`enable`] is called. Install it
+/// with `#[global_allocator]`, which the binary does when built with the `count-allocations`
+/// feature. Counting costs a little on every allocation, so timings taken with it are slower.
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            record(new_size, layout.size());
+        }
+        new
+    }
+}
+
+/// Starts counting allocations. Returns false, and leaves counting off, if [`Counting`] is not
+/// the global allocator.
+pub fn enable() -> bool {
+    ENABLED.store(true, Ordering::Relaxed);
+    let before = COUNTS.with(Cell::get).allocations;
+    drop(std::hint::black_box(Box::new(0u64)));
+    let installed = COUNTS.with(Cell::get).allocations > before;
+    ENABLED.store(installed, Ordering::Relaxed);
+    installed
+}
+
+/// Whether allocations are being counted.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f` and returns what it allocated on this thread. Without counting enabled that's nothing.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Usage) {
+    let before = COUNTS.with(|counts| {
+        let mut c = counts.get();
+        c.peak = c.live;
+        counts.set(c);
+        c
+    });
+    let res = f();
+    let after = COUNTS.with(Cell::get);
+    let usage = Usage {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live) as u64,
+    };
+    (res, usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::memory::{enable, measure, Counting, Usage};
+
+    #[global_allocator]
+    static ALLOCATOR: Counting = Counting;
+
+    #[test]
+    fn test_measure() {
+        assert!(enable());
+
+        let (_, usage) = measure(|| {
+            let small = vec![0u8; 1000];
+            drop(small);
+            let mut big = Vec::<u8>::with_capacity(4000);
+            big.extend(std::iter::repeat_n(1, 4000));
+            big.len()
+        });
+        assert_eq!(
+            usage,
+            Usage {
+                allocations: 2,
+                bytes: 5000,
+                peak: 4000,
+            }
+        );
+
+        let (_, usage) = measure(|| 1 + 1);
+        assert_eq!(usage, Usage::default());
+    }
+}
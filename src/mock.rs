@@ -0,0 +1,44 @@
+//! A local stand-in for the puzzle server, for testing fetch and submit.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+/// A stand-in puzzle server that answers a single request with `status` and `body`. Returns its
+/// base URL and a handle that yields the request it received.
+pub fn serve_once(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("can bind");
+    let url = format!("http://{}", listener.local_addr().expect("has address"));
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().expect("gets a request");
+        let mut reader = BufReader::new(stream);
+        let mut request = String::new();
+        let mut length = 0;
+        loop {
+            let start = request.len();
+            if reader.read_line(&mut request).expect("can read") <= 2 {
+                break;
+            }
+            let line = request[start..].to_ascii_lowercase();
+            if let Some(value) = line.strip_prefix("content-length:") {
+                length = value.trim().parse().expect("valid length");
+            }
+        }
+        let mut content = vec![0; length];
+        reader.read_exact(&mut content).expect("can read body");
+        request.push_str(&String::from_utf8_lossy(&content));
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        reader
+            .get_mut()
+            .write_all(response.as_bytes())
+            .expect("can write");
+        request
+    });
+    (url, handle)
+}
@@ -0,0 +1,630 @@
+//! Prints results as text for people or as JSON or CSV for other tools.
+
+use crate::answers::{Status, Verification};
+use crate::baseline::{Comparison, Verdict};
+use crate::memory::Usage;
+use crate::solution::{Puzzle, Timings};
+use crate::stats::{Outliers, PhaseSummary, Summary};
+use crate::watch::{self, Diff};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How results are printed, chosen with `--format`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    /// Readable text.
+    Text,
+    /// One JSON object per line.
+    Json,
+    /// CSV with a header row.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("unknown output format: {}", s)),
+        }
+    }
+}
+
+type Stat = fn(&Summary) -> Duration;
+
+/// How much of a day's timings to print.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Mode {
+    /// The one run.
+    Single,
+    /// Statistics over every run of a single day.
+    Repeat,
+    /// The fastest of several runs, for every day.
+    Fastest,
+}
+
+/// A day's answers and how long it took.
+pub struct DayResult {
+    /// The puzzle run.
+    pub puzzle: Puzzle,
+    /// The puzzle's title.
+    pub title: &'static str,
+    /// The answer to part 1.
+    pub part1: String,
+    /// The answer to part 2.
+    pub part2: String,
+    /// Hash of the input the day was run against, see `answers::input_key`.
+    pub input: u64,
+    /// Runs made before timing started.
+    pub warmup: usize,
+    /// The timings of each measured run.
+    pub runs: Vec<Timings>,
+    /// Allocations made by one run, if they were counted.
+    pub memory: Option<Usage>,
+    /// Time spent measuring the day, including warm-up and timing overhead.
+    pub elapsed: Duration,
+}
+
+impl DayResult {
+    /// The fastest time of each phase across all runs.
+    pub fn min(&self) -> Timings {
+        self.runs
+            .iter()
+            .copied()
+            .reduce(Timings::min)
+            .unwrap_or_default()
+    }
+
+    /// Statistics of each phase across all runs.
+    pub fn summary(&self) -> PhaseSummary {
+        PhaseSummary::new(&self.runs)
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut res = String::with_capacity(s.len() + 2);
+    res.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if (c as u32) < 0x20 => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+fn json_outliers(outliers: &Outliers) -> String {
+    format!(
+        "{{\"low_severe\":{},\"low_mild\":{},\"high_mild\":{},\"high_severe\":{}}}",
+        outliers.low_severe, outliers.low_mild, outliers.high_mild, outliers.high_severe
+    )
+}
+
+fn json_summary(summary: &Summary) -> String {
+    format!(
+        "{{\"samples\":{},\"min_ns\":{},\"max_ns\":{},\"mean_ns\":{},\"median_ns\":{},\"std_dev_ns\":{},\"p50_ns\":{},\"p90_ns\":{},\"p99_ns\":{},\"outliers\":{}}}",
+        summary.samples,
+        summary.min.as_nanos(),
+        summary.max.as_nanos(),
+        summary.mean.as_nanos(),
+        summary.median.as_nanos(),
+        summary.std_dev.as_nanos(),
+        summary.p50.as_nanos(),
+        summary.p90.as_nanos(),
+        summary.p99.as_nanos(),
+        json_outliers(&summary.outliers)
+    )
+}
+
+fn json_timings(timings: &Timings) -> String {
+    format!(
+        "{{\"parse_ns\":{},\"part1_ns\":{},\"part2_ns\":{},\"total_ns\":{}}}",
+        timings.parse.as_nanos(),
+        timings.part1.as_nanos(),
+        timings.part2.as_nanos(),
+        timings.total().as_nanos()
+    )
+}
+
+fn json_memory(memory: &Option<Usage>) -> String {
+    match memory {
+        Some(usage) => format!(
+            "{{\"allocations\":{},\"bytes\":{},\"peak_bytes\":{}}}",
+            usage.allocations, usage.bytes, usage.peak
+        ),
+        None => "null".to_string(),
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn csv_row(result: &DayResult, run: &str, values: [u128; 4]) -> String {
+    let memory = result.memory.map_or(",,".to_string(), |usage| {
+        format!("{},{},{}", usage.allocations, usage.bytes, usage.peak)
+    });
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{}",
+        result.puzzle.year,
+        result.puzzle.day,
+        csv_field(result.title),
+        csv_field(&result.part1),
+        csv_field(&result.part2),
+        run,
+        values[0],
+        values[1],
+        values[2],
+        values[3],
+        memory
+    )
+}
+
+fn csv_stat(summary: &PhaseSummary, stat: Stat) -> [u128; 4] {
+    [
+        stat(&summary.parse).as_nanos(),
+        stat(&summary.part1).as_nanos(),
+        stat(&summary.part2).as_nanos(),
+        stat(&summary.total).as_nanos(),
+    ]
+}
+
+/// Prints the CSV header row, if the format has one.
+pub fn print_header(format: Format) {
+    if format == Format::Csv {
+        println!("year,day,title,part1,part2,run,parse_ns,part1_ns,part2_ns,total_ns,allocations,allocated_bytes,peak_bytes");
+    }
+}
+
+/// Prints a day's answers and timings.
+pub fn print_result(format: Format, mode: Mode, result: &DayResult) {
+    match format {
+        Format::Text => print_text(mode, result),
+        Format::Json => {
+            let runs = result
+                .runs
+                .iter()
+                .map(json_timings)
+                .collect::<Vec<_>>()
+                .join(",");
+            let summary = result.summary();
+            println!(
+                "{{\"year\":{},\"day\":{},\"title\":{},\"part1\":{},\"part2\":{},\"warmup\":{},\"runs\":[{}],\"stats\":{{\"parse\":{},\"part1\":{},\"part2\":{},\"total\":{}}},\"memory\":{}}}",
+                result.puzzle.year,
+                result.puzzle.day,
+                json_string(result.title),
+                json_string(&result.part1),
+                json_string(&result.part2),
+                result.warmup,
+                runs,
+                json_summary(&summary.parse),
+                json_summary(&summary.part1),
+                json_summary(&summary.part2),
+                json_summary(&summary.total),
+                json_memory(&result.memory)
+            );
+        }
+        Format::Csv => {
+            for (i, run) in result.runs.iter().enumerate() {
+                let values = [
+                    run.parse.as_nanos(),
+                    run.part1.as_nanos(),
+                    run.part2.as_nanos(),
+                    run.total().as_nanos(),
+                ];
+                println!("{}", csv_row(result, &(i + 1).to_string(), values));
+            }
+            if result.runs.len() > 1 {
+                let summary = result.summary();
+                let stats: [(&str, Stat); 8] = [
+                    ("min", |s| s.min),
+                    ("mean", |s| s.mean),
+                    ("median", |s| s.median),
+                    ("std_dev", |s| s.std_dev),
+                    ("p50", |s| s.p50),
+                    ("p90", |s| s.p90),
+                    ("p99", |s| s.p99),
+                    ("max", |s| s.max),
+                ];
+                for (name, stat) in stats.iter() {
+                    println!("{}", csv_row(result, name, csv_stat(&summary, *stat)));
+                }
+                let outliers = [
+                    summary.parse.outliers.total() as u128,
+                    summary.part1.outliers.total() as u128,
+                    summary.part2.outliers.total() as u128,
+                    summary.total.outliers.total() as u128,
+                ];
+                println!("{}", csv_row(result, "outliers", outliers));
+            }
+        }
+    }
+}
+
+fn text_stat_row(label: &str, summary: &PhaseSummary, stat: Stat) {
+    println!(
+        "    {:<9}{:>12}{:>12}{:>12}{:>12}",
+        label,
+        format!("{:?}", stat(&summary.total)),
+        format!("{:?}", stat(&summary.parse)),
+        format!("{:?}", stat(&summary.part1)),
+        format!("{:?}", stat(&summary.part2))
+    );
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 MiB`.
+fn bytes(bytes: u64) -> String {
+    let units = ["KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = "B";
+    for next in units.iter() {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+    match unit {
+        "B" => format!("{} B", bytes),
+        _ => format!("{:.1} {}", size, unit),
+    }
+}
+
+fn print_text(mode: Mode, result: &DayResult) {
+    println!();
+    match (mode, result.warmup) {
+        (Mode::Single, _) => println!("{}: {}", result.puzzle, result.title),
+        (_, 0) => println!(
+            "{}: {} - {} runs",
+            result.puzzle,
+            result.title,
+            result.runs.len()
+        ),
+        (_, warmup) => println!(
+            "{}: {} - {} runs ({} warm-up)",
+            result.puzzle,
+            result.title,
+            result.runs.len(),
+            warmup
+        ),
+    }
+    println!("    part 1: {}", result.part1);
+    println!("    part 2: {}", result.part2);
+    match mode {
+        Mode::Single => println!("    time:   {}", result.min()),
+        Mode::Repeat => {
+            let summary = result.summary();
+            println!(
+                "{:<13}{:>12}{:>12}{:>12}{:>12}",
+                "times:", "total", "parse", "part 1", "part 2"
+            );
+            text_stat_row("minimum:", &summary, |s| s.min);
+            text_stat_row("average:", &summary, |s| s.mean);
+            text_stat_row("median:", &summary, |s| s.median);
+            text_stat_row("std dev:", &summary, |s| s.std_dev);
+            text_stat_row("p90:", &summary, |s| s.p90);
+            text_stat_row("p99:", &summary, |s| s.p99);
+            text_stat_row("maximum:", &summary, |s| s.max);
+            let outliers = summary.total.outliers;
+            println!(
+                "    outliers: {} of {} ({} low severe, {} low mild, {} high mild, {} high severe)",
+                outliers.total(),
+                summary.total.samples,
+                outliers.low_severe,
+                outliers.low_mild,
+                outliers.high_mild,
+                outliers.high_severe
+            );
+        }
+        Mode::Fastest => {
+            let total = result.summary().total;
+            println!("    fastest: {}", result.min());
+            println!(
+                "    median:  {:?} ± {:?} ({} outliers)",
+                total.median,
+                total.std_dev,
+                total.outliers.total()
+            );
+        }
+    }
+    if let Some(usage) = result.memory {
+        println!(
+            "    memory: {} allocations, {} allocated, {} peak",
+            usage.allocations,
+            bytes(usage.bytes),
+            bytes(usage.peak)
+        );
+    }
+}
+
+/// Prints why a day couldn't be run.
+pub fn print_error(format: Format, puzzle: Puzzle, err: &dyn Display) {
+    match format {
+        Format::Text => {
+            println!();
+            println!("{}", puzzle);
+            println!("    error: {}", err);
+        }
+        Format::Json | Format::Csv => eprintln!("{}: error: {}", puzzle, err),
+    }
+}
+
+/// Prints the total time of every day run, and the wall clock and CPU time taken with `jobs`
+/// threads.
+pub fn print_total(format: Format, total: &Timings, wall: Duration, cpu: Duration, jobs: usize) {
+    if format == Format::Text {
+        println!();
+        println!("total runtime: {}", total);
+        println!(
+            "wall clock:    {:?} on {} thread{}",
+            wall,
+            jobs,
+            if jobs == 1 { "" } else { "s" }
+        );
+        println!("cpu time:      {:?}", cpu);
+    }
+}
+
+/// Status messages go to stderr for machine readable formats so they don't corrupt the output.
+pub fn print_note(format: Format, note: &dyn Display) {
+    match format {
+        Format::Text => {
+            println!();
+            println!("{}", note);
+        }
+        Format::Json | Format::Csv => eprintln!("{}", note),
+    }
+}
+
+fn verdict_name(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Faster => "faster",
+        Verdict::Slower => "slower",
+        Verdict::Unchanged => "unchanged",
+        Verdict::New => "new",
+    }
+}
+
+fn text_comparison(c: &Comparison) -> String {
+    match c.before {
+        Some(before) => format!(
+            "    {}: {:>12} -> {:>12} ({:+.1}%) {}",
+            c.puzzle,
+            format!("{:?}", before.mean),
+            format!("{:?}", c.after.mean),
+            c.change * 100.0,
+            verdict_name(c.verdict)
+        ),
+        None => format!(
+            "    {}: {:>12}    {:>12}  not in baseline",
+            c.puzzle,
+            "",
+            format!("{:?}", c.after.mean)
+        ),
+    }
+}
+
+/// Prints how each day's runtime compares with the baseline.
+pub fn print_comparison(format: Format, comparisons: &[Comparison]) {
+    let count = |v| comparisons.iter().filter(|c| c.verdict == v).count();
+    let summary = format!(
+        "{} slower, {} faster, {} unchanged, {} new",
+        count(Verdict::Slower),
+        count(Verdict::Faster),
+        count(Verdict::Unchanged),
+        count(Verdict::New)
+    );
+    match format {
+        Format::Text => {
+            println!();
+            println!("baseline comparison (mean runtime):");
+            for c in comparisons {
+                println!("{}", text_comparison(c));
+            }
+            println!("    {}", summary);
+        }
+        Format::Json => {
+            for c in comparisons {
+                println!(
+                    "{{\"year\":{},\"day\":{},\"baseline\":{{\"mean_ns\":{},\"std_dev_ns\":{},\"samples\":{}}},\"mean_ns\":{},\"std_dev_ns\":{},\"samples\":{},\"change\":{},\"verdict\":{}}}",
+                    c.puzzle.year,
+                    c.puzzle.day,
+                    c.before.map_or("null".to_string(), |b| b.mean.as_nanos().to_string()),
+                    c.before.map_or("null".to_string(), |b| b.std_dev.as_nanos().to_string()),
+                    c.before.map_or("null".to_string(), |b| b.samples.to_string()),
+                    c.after.mean.as_nanos(),
+                    c.after.std_dev.as_nanos(),
+                    c.after.samples,
+                    c.change,
+                    json_string(verdict_name(c.verdict))
+                );
+            }
+        }
+        Format::Csv => {
+            eprintln!("baseline comparison (mean runtime):");
+            for c in comparisons {
+                eprintln!("{}", text_comparison(c));
+            }
+            eprintln!("    {}", summary);
+        }
+    }
+}
+
+fn text_diff(diff: &Diff) -> Vec<String> {
+    let answer = |part, changed: Option<(&str, &str)>| match changed {
+        Some((before, _)) => format!("part {} was {}", part, before),
+        None => format!("part {} unchanged", part),
+    };
+    let change = |phase: fn(&Timings) -> Duration| {
+        watch::change(phase(&diff.before), phase(&diff.after)) * 100.0
+    };
+    vec![
+        format!(
+            "    answers: {}, {}",
+            answer(1, diff.part1),
+            answer(2, diff.part2)
+        ),
+        format!(
+            "    timing:  {:+.1}% from {:?} (parse {:+.1}%, part 1 {:+.1}%, part 2 {:+.1}%)",
+            change(Timings::total),
+            diff.before.total(),
+            change(|t| t.parse),
+            change(|t| t.part1),
+            change(|t| t.part2)
+        ),
+    ]
+}
+
+/// Shows how a re-run in watch mode differs from the run before it.
+pub fn print_diff(format: Format, diff: &Diff) {
+    match format {
+        Format::Text => text_diff(diff).iter().for_each(|line| println!("{}", line)),
+        Format::Json => {
+            let answer = |changed: Option<(&str, &str)>| {
+                changed.map_or("null".to_string(), |(before, _)| json_string(before))
+            };
+            println!(
+                "{{\"year\":{},\"day\":{},\"previous\":{{\"part1\":{},\"part2\":{},\"min\":{}}}}}",
+                diff.puzzle.year,
+                diff.puzzle.day,
+                answer(diff.part1),
+                answer(diff.part2),
+                json_timings(&diff.before)
+            );
+        }
+        Format::Csv => text_diff(diff)
+            .iter()
+            .for_each(|line| eprintln!("{}", line)),
+    }
+}
+
+fn text_verification(v: &Verification, result: &DayResult) -> String {
+    let part = |status: Status, actual: &str, expected: Option<&str>| match (status, expected) {
+        (Status::Fail, Some(expected)) => format!("fail (got {}, expected {})", actual, expected),
+        _ => status.name().to_string(),
+    };
+    match &v.expected {
+        Some(expected) => format!(
+            "    {}: {} - part 1 {}, part 2 {}",
+            v.puzzle,
+            v.status().name(),
+            part(v.part1, &result.part1, Some(&expected.part1)),
+            part(v.part2, &result.part2, Some(&expected.part2))
+        ),
+        None => format!(
+            "    {}: unknown - no answers for input {:016x}",
+            v.puzzle, v.input
+        ),
+    }
+}
+
+/// Prints how each day's answers compare with the expected ones.
+pub fn print_verification(format: Format, checks: &[(Verification, &DayResult)]) {
+    let count = |s| checks.iter().filter(|(v, _)| v.status() == s).count();
+    let summary = format!(
+        "{} passed, {} failed, {} unknown",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Unknown)
+    );
+    match format {
+        Format::Text => {
+            println!();
+            println!("answers:");
+            for (v, result) in checks {
+                println!("{}", text_verification(v, result));
+            }
+            println!("    {}", summary);
+        }
+        Format::Json => {
+            for (v, _) in checks {
+                let expected = match &v.expected {
+                    Some(e) => format!(
+                        "{{\"part1\":{},\"part2\":{}}}",
+                        json_string(&e.part1),
+                        json_string(&e.part2)
+                    ),
+                    None => "null".to_string(),
+                };
+                println!(
+                    "{{\"year\":{},\"day\":{},\"input\":\"{:016x}\",\"status\":{},\"part1\":{},\"part2\":{},\"expected\":{}}}",
+                    v.puzzle.year,
+                    v.puzzle.day,
+                    v.input,
+                    json_string(v.status().name()),
+                    json_string(v.part1.name()),
+                    json_string(v.part2.name()),
+                    expected
+                );
+            }
+        }
+        Format::Csv => {
+            eprintln!("answers:");
+            for (v, result) in checks {
+                eprintln!("{}", text_verification(v, result));
+            }
+            eprintln!("    {}", summary);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{bytes, csv_field, json_string, DayResult};
+    use crate::solution::{Puzzle, Timings};
+    use std::time::Duration;
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(json_string("a\"b\\c\n"), "\"a\\\"b\\\\c\\n\"");
+        assert_eq!(csv_field("885"), "885");
+        assert_eq!(csv_field("mxmxvkd,sqjhc"), "\"mxmxvkd,sqjhc\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_bytes() {
+        assert_eq!(bytes(0), "0 B");
+        assert_eq!(bytes(1023), "1023 B");
+        assert_eq!(bytes(1536), "1.5 KiB");
+        assert_eq!(bytes(120_000_000), "114.4 MiB");
+    }
+
+    #[test]
+    fn test_stats() {
+        let timings = |ms| Timings {
+            parse: Duration::from_millis(ms),
+            part1: Duration::from_millis(ms),
+            part2: Duration::from_millis(ms),
+        };
+        let result = DayResult {
+            puzzle: Puzzle::new(2020, 1),
+            title: "",
+            part1: String::new(),
+            part2: String::new(),
+            input: 0,
+            warmup: 0,
+            runs: vec![timings(2), timings(1), timings(6)],
+            memory: None,
+            elapsed: Duration::default(),
+        };
+        assert_eq!(result.min(), timings(1));
+        let summary = result.summary();
+        assert_eq!(summary.parse.median, Duration::from_millis(2));
+        assert_eq!(summary.total.max, Duration::from_millis(18));
+    }
+}
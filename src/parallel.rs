@@ -0,0 +1,121 @@
+//! Runs days on several threads at once, and gives up on days that take too long.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+/// How many threads can usefully run at once, falling back to 1 if that's unknown.
+pub fn available_jobs() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Calls `work` on every item using up to `jobs` threads, and passes each result to `report`
+/// on the calling thread in the original item order as soon as it and everything before it is
+/// done.
+pub fn run_ordered<T, R, W, F>(items: &[T], jobs: usize, work: W, mut report: F)
+where
+    T: Sync,
+    R: Send,
+    W: Fn(&T) -> R + Sync,
+    F: FnMut(&T, R),
+{
+    if jobs <= 1 || items.len() <= 1 {
+        for item in items {
+            report(item, work(item));
+        }
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    std::thread::scope(|s| {
+        for _ in 0..jobs.min(items.len()) {
+            let tx = tx.clone();
+            let (next, work) = (&next, &work);
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= items.len() || tx.send((i, work(&items[i]))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut printed = 0;
+        for (i, result) in rx {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&printed) {
+                report(&items[printed], result);
+                printed += 1;
+            }
+        }
+    });
+}
+
+/// Calls `work` on a thread of its own and waits up to `limit` for it, returning `None` if it
+/// takes longer. There is no safe way to stop a thread, so one that overruns is abandoned: it
+/// keeps running in the background until it finishes or the process exits. Panics in `work` are
+/// passed on to the caller, but a stack overflow aborts the whole process, so days must limit
+/// how deep they recurse themselves.
+pub fn with_timeout<R, W>(limit: Duration, work: W) -> Option<R>
+where
+    R: Send + 'static,
+    W: FnOnce() -> R + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let handle = std::thread::spawn(move || {
+        // the receiver is gone if we were abandoned, so there's no one to tell
+        let _ = tx.send(work());
+    });
+    match rx.recv_timeout(limit) {
+        Ok(result) => Some(result),
+        Err(RecvTimeoutError::Timeout) => None,
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(panic) => std::panic::resume_unwind(panic),
+            Ok(()) => unreachable!("the worker sends a result before it finishes"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parallel::{run_ordered, with_timeout};
+    use std::time::Duration;
+
+    #[test]
+    fn test_run_ordered() {
+        let items = (0..20).collect::<Vec<u64>>();
+        for &jobs in &[1, 3, 8, 50] {
+            let mut seen = Vec::new();
+            run_ordered(
+                &items,
+                jobs,
+                |&i| {
+                    // make early items finish last so results arrive out of order
+                    std::thread::sleep(Duration::from_millis(20 - i));
+                    i * 2
+                },
+                |&i, doubled| seen.push((i, doubled)),
+            );
+            assert_eq!(seen, items.iter().map(|&i| (i, i * 2)).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_with_timeout() {
+        assert_eq!(with_timeout(Duration::from_secs(5), || 6 * 7), Some(42));
+        assert_eq!(
+            with_timeout(Duration::from_millis(10), || loop {
+                std::thread::sleep(Duration::from_millis(50));
+            }),
+            None::<()>
+        );
+
+        let panicked = std::panic::catch_unwind(|| {
+            with_timeout(Duration::from_secs(5), || -> () { panic!("boom") })
+        });
+        assert!(panicked.is_err());
+    }
+}
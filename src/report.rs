@@ -0,0 +1,308 @@
+//! Writes results up as a Markdown or HTML report to share.
+
+use crate::output::DayResult;
+use crate::stats::Summary;
+use std::fmt::{Display, Formatter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Where reports are written unless a path is given.
+pub const DEFAULT_DIR: &str = "reports";
+
+/// Shown in place of an answer when a report is redacted.
+pub const REDACTED: &str = "redacted";
+
+const CHART_WIDTH: usize = 640;
+const LABEL_WIDTH: usize = 90;
+const VALUE_WIDTH: usize = 90;
+const ROW_HEIGHT: usize = 22;
+
+/// Why a report couldn't be written.
+#[derive(Debug)]
+pub enum ReportError {
+    /// Writing the file failed.
+    Io(PathBuf, std::io::Error),
+}
+
+impl Display for ReportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportError::Io(path, err) => {
+                write!(f, "could not write report {}: {}", path.display(), err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+/// A results table for a set of days, rendered as Markdown or as a standalone HTML page.
+pub struct Report<'a> {
+    /// The days to report on.
+    pub results: &'a [DayResult],
+    /// Hides the answers, so a report can be shared without spoiling them.
+    pub redact: bool,
+}
+
+fn answer<'a>(report: &Report<'_>, answer: &'a str) -> &'a str {
+    match report.redact {
+        true => REDACTED,
+        false => answer,
+    }
+}
+
+/// Cells of a row's timing columns: runs, then the total runtime's min, mean, median, std dev,
+/// p90 and max.
+fn timing_cells(summary: &Summary) -> [String; 7] {
+    [
+        summary.samples.to_string(),
+        format!("{:?}", summary.min),
+        format!("{:?}", summary.mean),
+        format!("{:?}", summary.median),
+        format!("{:?}", summary.std_dev),
+        format!("{:?}", summary.p90),
+        format!("{:?}", summary.max),
+    ]
+}
+
+const TIMING_HEADERS: [&str; 7] = ["Runs", "Min", "Mean", "Median", "Std dev", "p90", "Max"];
+
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+fn html_escape(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '&' => res.push_str("&amp;"),
+            '"' => res.push_str("&quot;"),
+            '\'' => res.push_str("&#39;"),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+/// Sum of every day's median runtime, which is what a typical run of them all takes.
+fn total_median(results: &[DayResult]) -> Duration {
+    results.iter().map(|r| r.summary().total.median).sum()
+}
+
+impl Report<'_> {
+    /// The report as a Markdown table.
+    pub fn markdown(&self) -> String {
+        let mut res = String::new();
+        res.push_str("# Advent of Code results\n\n");
+        let mut header = vec!["Day", "Title", "Part 1", "Part 2"];
+        header.extend(TIMING_HEADERS.iter());
+        let _ = writeln!(res, "| {} |", header.join(" | "));
+        let _ = writeln!(res, "|{}", "---|".repeat(header.len()));
+        for result in self.results {
+            let mut row = vec![
+                result.puzzle.to_string(),
+                markdown_cell(result.title),
+                markdown_cell(answer(self, &result.part1)),
+                markdown_cell(answer(self, &result.part2)),
+            ];
+            row.extend(timing_cells(&result.summary().total));
+            let _ = writeln!(res, "| {} |", row.join(" | "));
+        }
+        let _ = writeln!(
+            res,
+            "\nTotal median runtime: {:?}",
+            total_median(self.results)
+        );
+        res
+    }
+
+    /// The report as an HTML page with a chart of each day's runtime.
+    pub fn html(&self) -> String {
+        let mut res = String::new();
+        res.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        res.push_str("<title>Advent of Code results</title>\n<style>\n");
+        res.push_str("body { font-family: sans-serif; margin: 2em; }\n");
+        res.push_str("table { border-collapse: collapse; margin-bottom: 2em; }\n");
+        res.push_str("th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; }\n");
+        res.push_str("td.time { text-align: right; font-family: monospace; }\n");
+        res.push_str("</style>\n</head>\n<body>\n<h1>Advent of Code results</h1>\n");
+
+        res.push_str("<table>\n<tr>");
+        for header in ["Day", "Title", "Part 1", "Part 2"]
+            .iter()
+            .chain(TIMING_HEADERS.iter())
+        {
+            let _ = write!(res, "<th>{}</th>", header);
+        }
+        res.push_str("</tr>\n");
+        for result in self.results {
+            let _ = write!(
+                res,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td>",
+                result.puzzle,
+                html_escape(result.title),
+                html_escape(answer(self, &result.part1)),
+                html_escape(answer(self, &result.part2))
+            );
+            for cell in timing_cells(&result.summary().total).iter() {
+                let _ = write!(res, "<td class=\"time\">{}</td>", html_escape(cell));
+            }
+            res.push_str("</tr>\n");
+        }
+        res.push_str("</table>\n");
+        let _ = writeln!(
+            res,
+            "<p>Total median runtime: {}</p>",
+            html_escape(&format!("{:?}", total_median(self.results)))
+        );
+
+        res.push_str("<h2>Median runtime per day</h2>\n");
+        res.push_str(&chart(self.results));
+        res.push_str("</body>\n</html>\n");
+        res
+    }
+
+    /// Writes `results.md` and `results.html` to `dir`, creating it if needed.
+    pub fn write(&self, dir: &Path) -> Result<Vec<PathBuf>, ReportError> {
+        std::fs::create_dir_all(dir).map_err(|err| ReportError::Io(dir.to_path_buf(), err))?;
+        let files = [
+            ("results.md", self.markdown()),
+            ("results.html", self.html()),
+        ];
+        let mut paths = Vec::new();
+        for (name, contents) in files.iter() {
+            let path = dir.join(name);
+            std::fs::write(&path, contents).map_err(|err| ReportError::Io(path.clone(), err))?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+/// A horizontal bar chart of each day's median runtime, scaled to the slowest day.
+pub fn chart(results: &[DayResult]) -> String {
+    let medians = results
+        .iter()
+        .map(|r| r.summary().total.median)
+        .collect::<Vec<_>>();
+    let slowest = medians.iter().max().copied().unwrap_or_default();
+    let bar_space = CHART_WIDTH - LABEL_WIDTH - VALUE_WIDTH;
+    let height = ROW_HEIGHT * results.len().max(1);
+
+    let mut res = String::new();
+    let _ = writeln!(
+        res,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"12\">",
+        CHART_WIDTH, height
+    );
+    for (i, (result, median)) in results.iter().zip(medians.iter()).enumerate() {
+        let width = match slowest.as_nanos() {
+            0 => 0.0,
+            slowest => median.as_nanos() as f64 / slowest as f64 * bar_space as f64,
+        };
+        let y = i * ROW_HEIGHT;
+        let _ = writeln!(
+            res,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+            LABEL_WIDTH - 6,
+            y + ROW_HEIGHT - 7,
+            result.puzzle
+        );
+        let _ = writeln!(
+            res,
+            "<rect x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"#4a7ebb\"><title>{}: {}</title></rect>",
+            LABEL_WIDTH,
+            y + 3,
+            width,
+            ROW_HEIGHT - 6,
+            result.puzzle,
+            html_escape(result.title)
+        );
+        let _ = writeln!(
+            res,
+            "<text x=\"{:.1}\" y=\"{}\">{}</text>",
+            LABEL_WIDTH as f64 + width + 4.0,
+            y + ROW_HEIGHT - 7,
+            html_escape(&format!("{:?}", median))
+        );
+    }
+    res.push_str("</svg>\n");
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::DayResult;
+    use crate::report::{chart, html_escape, markdown_cell, Report, REDACTED};
+    use crate::solution::{Puzzle, Timings};
+    use std::time::Duration;
+
+    fn result(day: usize, ms: &[u64]) -> DayResult {
+        let timings = |&ms: &u64| Timings {
+            parse: Duration::from_millis(ms),
+            part1: Duration::default(),
+            part2: Duration::default(),
+        };
+        DayResult {
+            puzzle: Puzzle::new(2020, day),
+            title: "A <Title>",
+            part1: "12|34".to_string(),
+            part2: "mxmxvkd,sqjhc".to_string(),
+            input: 0,
+            warmup: 0,
+            runs: ms.iter().map(timings).collect(),
+            memory: None,
+            elapsed: Duration::default(),
+        }
+    }
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(
+            html_escape("<a href=\"x\">&</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+        assert_eq!(markdown_cell("a|b\nc"), "a\\|b c");
+    }
+
+    #[test]
+    fn test_markdown() {
+        let results = [result(1, &[3, 1, 2]), result(7, &[10])];
+        let report = Report {
+            results: &results,
+            redact: false,
+        };
+        let markdown = report.markdown();
+        let lines = markdown.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[2],
+            "| Day | Title | Part 1 | Part 2 | Runs | Min | Mean | Median | Std dev | p90 | Max |"
+        );
+        assert_eq!(
+            lines[4],
+            "| 2020/day01 | A <Title> | 12\\|34 | mxmxvkd,sqjhc | 3 | 1ms | 2ms | 2ms | 1ms | 2.8ms | 3ms |"
+        );
+        assert!(markdown.ends_with("Total median runtime: 12ms\n"));
+
+        let redacted = Report {
+            results: &results,
+            redact: true,
+        };
+        assert!(!redacted.markdown().contains("sqjhc"));
+        assert!(redacted.html().contains(REDACTED));
+        assert!(!redacted.html().contains("sqjhc"));
+    }
+
+    #[test]
+    fn test_chart() {
+        let results = [result(1, &[5]), result(2, &[10])];
+        let svg = chart(&results);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("width=\"230.0\""));
+        assert!(svg.contains("width=\"460.0\""));
+        assert_eq!(svg.matches("<rect ").count(), 2);
+        assert!(chart(&[]).ends_with("</svg>\n"));
+    }
+}
@@ -0,0 +1,255 @@
+//! Creates the module, registration and input file for a new day from `templates/day.rs`.
+
+use crate::input;
+use crate::solution::Puzzle;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+const YEARS_DIR: &str = "src/years";
+const INPUT_DIR: &str = "files";
+
+/// Why a day couldn't be scaffolded.
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The day isn't between 1 and 25.
+    Day(usize),
+    /// The day's module or input already exists.
+    Exists(PathBuf),
+    /// The year's module couldn't be updated to register the day.
+    Registry(PathBuf, String),
+    /// Reading or writing a file failed.
+    Io(PathBuf, std::io::Error),
+}
+
+impl Display for ScaffoldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScaffoldError::Day(day) => write!(f, "day {} is out of range, expected 1-25", day),
+            ScaffoldError::Exists(path) => write!(f, "{} already exists", path.display()),
+            ScaffoldError::Registry(path, err) => write!(f, "{}: {}", path.display(), err),
+            ScaffoldError::Io(path, err) => {
+                write!(f, "could not access {}: {}", path.display(), err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScaffoldError {}
+
+/// The source of a new module for `puzzle`, filled in from the template.
+pub fn module(puzzle: Puzzle) -> String {
+    TEMPLATE
+        .replace("{{YEAR}}", &puzzle.year.to_string())
+        .replace("{{NN}}", &format!("{:02}", puzzle.day))
+        .replace("{{DAY}}", &puzzle.day.to_string())
+}
+
+/// The `mod.rs` of a year whose only solution so far is `puzzle`.
+fn year_module(puzzle: Puzzle) -> String {
+    format!(
+        "//! [Advent of Code {0}](https://adventofcode.com/{0}).
+
+pub mod day{1:02};
+
+use crate::solution::Registry;
+
+/// The solutions for {0}.
+pub fn registry() -> Registry {{
+    let mut registry = Registry::new({0});
+    registry.register(day{1:02}::Day{1:02});
+    registry
+}}
+",
+        puzzle.year, puzzle.day
+    )
+}
+
+/// Inserts `line` among the lines of `contents` that start with `prefix`, keeping them sorted.
+fn insert_sorted(contents: &str, prefix: &str, line: &str) -> Result<String, String> {
+    let mut lines = contents.lines().collect::<Vec<_>>();
+    let matching = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.starts_with(prefix))
+        .collect::<Vec<_>>();
+    if matching.iter().any(|(_, l)| **l == line) {
+        return Err(format!("{} is already there", line.trim()));
+    }
+    let at = match matching.iter().find(|(_, l)| **l > line) {
+        Some(&(i, _)) => i,
+        None => match matching.last() {
+            Some(&(i, _)) => i + 1,
+            None => return Err(format!("no lines starting with {:?}", prefix.trim())),
+        },
+    };
+
+    lines.insert(at, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Declares and registers the module for `day` in the contents of a year's `mod.rs`.
+pub fn register(mod_rs: &str, day: usize) -> Result<String, String> {
+    let declared = insert_sorted(mod_rs, "pub mod day", &format!("pub mod day{:02};", day))?;
+    insert_sorted(
+        &declared,
+        "    registry.register(day",
+        &format!("    registry.register(day{0:02}::Day{0:02});", day),
+    )
+}
+
+/// Declares the module for `year` and adds its registry in the contents of `years/mod.rs`.
+pub fn register_year(mod_rs: &str, year: usize) -> Result<String, String> {
+    let declared = insert_sorted(mod_rs, "pub mod y", &format!("pub mod y{};", year))?;
+    insert_sorted(
+        &declared,
+        "    calendar.add(y",
+        &format!("    calendar.add(y{}::registry());", year),
+    )
+}
+
+fn read(path: &Path) -> Result<String, ScaffoldError> {
+    std::fs::read_to_string(path).map_err(|err| ScaffoldError::Io(path.to_path_buf(), err))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    std::fs::write(path, contents).map_err(|err| ScaffoldError::Io(path.to_path_buf(), err))
+}
+
+fn write_new(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    if path.exists() {
+        return Err(ScaffoldError::Exists(path.to_path_buf()));
+    }
+    write(path, contents)
+}
+
+/// Creates the module for `puzzle` from the template, registers it, and creates an empty input
+/// file for `fetch` to fill in. The first day of a new year also gets the year's module.
+/// Returns the paths it wrote to.
+pub fn create(root: &Path, puzzle: Puzzle) -> Result<Vec<PathBuf>, ScaffoldError> {
+    if !(1..=25).contains(&puzzle.day) {
+        return Err(ScaffoldError::Day(puzzle.day));
+    }
+
+    let years = root.join(YEARS_DIR);
+    let dir = years.join(format!("y{}", puzzle.year));
+    let module_path = dir.join(format!("day{:02}.rs", puzzle.day));
+    let mod_path = dir.join("mod.rs");
+    let input_path = root.join(INPUT_DIR).join(input::path(puzzle));
+    if module_path.exists() {
+        return Err(ScaffoldError::Exists(module_path));
+    }
+
+    let mut written = Vec::new();
+    if mod_path.exists() {
+        let mod_rs = register(&read(&mod_path)?, puzzle.day)
+            .map_err(|err| ScaffoldError::Registry(mod_path.clone(), err))?;
+        write_new(&module_path, &module(puzzle))?;
+        write(&mod_path, &mod_rs)?;
+        written.extend([module_path, mod_path]);
+    } else {
+        let years_path = years.join("mod.rs");
+        let years_rs = register_year(&read(&years_path)?, puzzle.year)
+            .map_err(|err| ScaffoldError::Registry(years_path.clone(), err))?;
+        std::fs::create_dir_all(&dir).map_err(|err| ScaffoldError::Io(dir.clone(), err))?;
+        write_new(&module_path, &module(puzzle))?;
+        write_new(&mod_path, &year_module(puzzle))?;
+        write(&years_path, &years_rs)?;
+        written.extend([module_path, mod_path, years_path]);
+    }
+
+    if !input_path.exists() {
+        if let Some(dir) = input_path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|err| ScaffoldError::Io(dir.to_path_buf(), err))?;
+        }
+        write_new(&input_path, "")?;
+        written.push(input_path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scaffold::{module, register, register_year, year_module};
+    use crate::solution::Puzzle;
+
+    #[test]
+    fn test_module() {
+        let source = module(Puzzle::new(2020, 7));
+        assert!(source.contains("include_str!(\"../../../files/2020/07.txt\")"));
+        assert!(source.contains("pub struct Day07;"));
+        assert!(source.contains("use crate::years::y2020::day07::{"));
+        assert!(!source.contains("{{"));
+    }
+
+    #[test]
+    fn test_register() {
+        let mod_rs = "pub mod day01;
+pub mod day04;
+
+use crate::solution::Registry;
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::new(2020);
+    registry.register(day01::Day01);
+    registry.register(day04::Day04);
+    registry
+}
+";
+        assert_eq!(
+            register(mod_rs, 3),
+            Ok("pub mod day01;
+pub mod day03;
+pub mod day04;
+
+use crate::solution::Registry;
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::new(2020);
+    registry.register(day01::Day01);
+    registry.register(day03::Day03);
+    registry.register(day04::Day04);
+    registry
+}
+"
+            .to_string())
+        );
+
+        let appended = register(mod_rs, 12).expect("can append");
+        assert!(appended.contains("pub mod day04;\npub mod day12;\n"));
+        assert!(appended.contains("(day12::Day12);\n    registry\n"));
+
+        assert_eq!(
+            register(mod_rs, 4),
+            Err("pub mod day04; is already there".to_string())
+        );
+    }
+
+    #[test]
+    fn test_register_year() {
+        let mod_rs = "//! Solutions grouped by year, one module per year.
+
+pub mod y2020;
+
+use crate::solution::Calendar;
+
+/// Every year's solutions.
+pub fn calendar() -> Calendar {
+    let mut calendar = Calendar::new();
+    calendar.add(y2020::registry());
+    calendar
+}
+";
+        let registered = register_year(mod_rs, 2019).expect("can insert");
+        assert!(registered.contains("\npub mod y2019;\npub mod y2020;\n"));
+        assert!(registered.contains(
+            "    calendar.add(y2019::registry());\n    calendar.add(y2020::registry());\n"
+        ));
+
+        let year = year_module(Puzzle::new(2019, 3));
+        assert!(year.contains("\npub mod day03;\n"));
+        assert!(year.contains("Registry::new(2019);\n    registry.register(day03::Day03);\n"));
+        assert_eq!(register(&year, 1).map(|_| ()), Ok(()));
+    }
+}
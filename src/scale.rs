@@ -0,0 +1,451 @@
+//! Estimates how a day's runtime grows with its input, by timing it on generated inputs of
+//! increasing size and fitting the timings against common complexity classes.
+
+use crate::bench::{self, Repeats};
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::{DynSolution, Puzzle, Timings};
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Smallest size tried unless `--from` says otherwise.
+pub const DEFAULT_FROM: usize = 10;
+/// Largest size tried unless `--to` says otherwise.
+pub const DEFAULT_TO: usize = 1000;
+/// How many sizes are tried, spaced evenly on a log scale.
+pub const DEFAULT_STEPS: usize = 7;
+
+/// Sizes stop growing once a run takes longer than this, so that a badly scaling day still
+/// finishes.
+pub const SLOW: Duration = Duration::from_secs(5);
+
+/// Fit errors above this, about a factor of 1.6 either way, make for a poor estimate.
+const POOR_FIT: f64 = 0.5;
+
+const PLOT_WIDTH: usize = 640;
+const PLOT_HEIGHT: usize = 420;
+const MARGIN: usize = 70;
+const COLOURS: [&str; 4] = ["#4a7ebb", "#d9822b", "#3f9c35", "#555555"];
+
+/// Gets one phase's time out of a run's.
+pub type Phase = fn(&Timings) -> Duration;
+
+/// Each phase's name and how to get its time.
+pub const PHASES: [(&str, Phase); 4] = [
+    ("parse", |t| t.parse),
+    ("part1", |t| t.part1),
+    ("part2", |t| t.part2),
+    ("total", Timings::total),
+];
+
+/// A complexity class a phase's runtime can be fitted to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Class {
+    /// Doesn't grow with the input.
+    Constant,
+    /// Grows with the log of the input's size.
+    Logarithmic,
+    /// Grows in proportion to the input.
+    Linear,
+    /// Grows with `n log n`.
+    Linearithmic,
+    /// Grows with the square of the input's size.
+    Quadratic,
+    /// Grows with the cube of the input's size.
+    Cubic,
+    /// Doubles with every step up in size.
+    Exponential,
+}
+
+const POLYNOMIAL: [Class; 6] = [
+    Class::Constant,
+    Class::Logarithmic,
+    Class::Linear,
+    Class::Linearithmic,
+    Class::Quadratic,
+    Class::Cubic,
+];
+
+impl Class {
+    /// The class in big O notation.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Class::Constant => "O(1)",
+            Class::Logarithmic => "O(log n)",
+            Class::Linear => "O(n)",
+            Class::Linearithmic => "O(n log n)",
+            Class::Quadratic => "O(n^2)",
+            Class::Cubic => "O(n^3)",
+            Class::Exponential => "O(2^n)",
+        }
+    }
+
+    /// The natural log of the class's growth function at `n`.
+    fn ln_growth(&self, n: f64) -> f64 {
+        let n = n.max(2.0);
+        match self {
+            Class::Constant => 0.0,
+            Class::Logarithmic => n.ln().ln(),
+            Class::Linear => n.ln(),
+            Class::Linearithmic => n.ln() + n.ln().ln(),
+            Class::Quadratic => 2.0 * n.ln(),
+            Class::Cubic => 3.0 * n.ln(),
+            Class::Exponential => n * std::f64::consts::LN_2,
+        }
+    }
+}
+
+/// One generated input's size and the fastest time of each phase on it.
+#[derive(Copy, Clone, Debug)]
+pub struct Point {
+    /// The size asked of the generator.
+    pub size: usize,
+    /// Length of the generated input, which some days don't grow with `size`.
+    pub bytes: usize,
+    /// The fastest time of each phase.
+    pub timings: Timings,
+}
+
+/// The complexity class that best explains how one phase's times grow.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Fit {
+    /// The best fitting class.
+    pub class: Class,
+    /// Root mean square distance of the log times from the fitted curve.
+    pub error: f64,
+    /// Slope of the times on a log-log plot, which is `k` for `O(n^k)`.
+    pub exponent: f64,
+}
+
+impl Fit {
+    /// Whether the times are too far from every class to trust the estimate, usually because
+    /// they are too short to measure well.
+    pub fn poor(&self) -> bool {
+        self.error > POOR_FIT
+    }
+}
+
+/// `steps` sizes from `from` to `to`, spaced evenly on a log scale.
+pub fn sizes(from: usize, to: usize, steps: usize) -> Vec<usize> {
+    let (from, to) = (from.max(1), to.max(from.max(1)));
+    let mut res = (0..steps.max(2))
+        .map(|i| {
+            let t = i as f64 / (steps.max(2) - 1) as f64;
+            (from as f64 * (to as f64 / from as f64).powf(t)).round() as usize
+        })
+        .collect::<Vec<_>>();
+    res.dedup();
+    res
+}
+
+/// Times the solution on an input generated for each size in turn, stopping early once a run
+/// is slower than [`SLOW`]. `report` sees each point as soon as it is measured.
+pub fn measure(
+    puzzle: Puzzle,
+    solution: &dyn DynSolution,
+    sizes: &[usize],
+    seed: u64,
+    repeats: Repeats,
+    mut report: impl FnMut(&Point),
+) -> Result<Vec<Point>> {
+    let mut res = Vec::new();
+    for &size in sizes {
+        let input = solution
+            .generate(&mut Rng::new(seed), size)
+            .ok_or_else(|| Error::new(format!("{} has no input generator", puzzle)))?;
+        let result = bench::measure(puzzle, solution, &input, 0, repeats)
+            .map_err(|err| Error::new(format!("size {}: {}", size, err)))?;
+        let point = Point {
+            size,
+            bytes: input.len(),
+            timings: result.min(),
+        };
+        report(&point);
+        res.push(point);
+        if point.timings.total() > SLOW {
+            break;
+        }
+    }
+    Ok(res)
+}
+
+fn ln_seconds(time: Duration) -> f64 {
+    time.as_secs_f64().max(1e-9).ln()
+}
+
+/// Mean and root mean square deviation of `values`.
+fn mean_and_deviation(values: &[f64]) -> (f64, f64) {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    (mean, variance.sqrt())
+}
+
+/// Least squares `(slope, intercept, error)` of `ys` against `xs`.
+fn regression(xs: &[f64], ys: &[f64]) -> (f64, f64, f64) {
+    let (x_mean, x_deviation) = mean_and_deviation(xs);
+    let (y_mean, _) = mean_and_deviation(ys);
+    let covariance = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| (x - x_mean) * (y - y_mean))
+        .sum::<f64>()
+        / xs.len() as f64;
+    let slope = match x_deviation {
+        d if d > 0.0 => covariance / (d * d),
+        _ => 0.0,
+    };
+    let intercept = y_mean - slope * x_mean;
+    let residuals = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| y - intercept - slope * x)
+        .collect::<Vec<_>>();
+    let error = (residuals.iter().map(|r| r * r).sum::<f64>() / xs.len() as f64).sqrt();
+    (slope, intercept, error)
+}
+
+/// Fits `time = c * f(size)` for each polynomial class on log scales, where times differ by
+/// orders of magnitude, and `time = a * b^size` for exponential growth. Exponential growth has a
+/// free rate, so it only wins if it fits twice as well as every polynomial class and at least
+/// quadruples over the sizes.
+/// Needs at least three points.
+pub fn fit(points: &[(usize, Duration)]) -> Option<Fit> {
+    if points.len() < 3 {
+        return None;
+    }
+    let ns = points.iter().map(|&(n, _)| n as f64).collect::<Vec<_>>();
+    let ys = points
+        .iter()
+        .map(|&(_, t)| ln_seconds(t))
+        .collect::<Vec<_>>();
+    let ln_ns = ns.iter().map(|n| n.max(1.0).ln()).collect::<Vec<_>>();
+    let (exponent, _, _) = regression(&ln_ns, &ys);
+
+    let mut best = POLYNOMIAL
+        .iter()
+        .map(|&class| {
+            let residuals = ns
+                .iter()
+                .zip(&ys)
+                .map(|(&n, y)| y - class.ln_growth(n))
+                .collect::<Vec<_>>();
+            Fit {
+                class,
+                error: mean_and_deviation(&residuals).1,
+                exponent,
+            }
+        })
+        .min_by(|a, b| a.error.total_cmp(&b.error))?;
+
+    let (rate, _, error) = regression(&ns, &ys);
+    let spread =
+        ns.iter().copied().fold(f64::MIN, f64::max) - ns.iter().copied().fold(f64::MAX, f64::min);
+    if error < best.error / 2.0 && rate * spread > 4f64.ln() {
+        best = Fit {
+            class: Class::Exponential,
+            error,
+            exponent,
+        };
+    }
+    Some(best)
+}
+
+/// The fit for each of [`PHASES`].
+pub fn fit_phases(points: &[Point]) -> Vec<Option<Fit>> {
+    PHASES
+        .iter()
+        .map(|(_, phase)| {
+            fit(&points
+                .iter()
+                .map(|p| (p.size, phase(&p.timings)))
+                .collect::<Vec<_>>())
+        })
+        .collect()
+}
+
+fn powers_of_ten(low: f64, high: f64) -> impl Iterator<Item = i32> {
+    (low.log10().floor() as i32)..=(high.log10().ceil() as i32)
+}
+
+/// A log-log plot of each phase's time against size, labelled with its estimated class.
+pub fn plot(puzzle: Puzzle, points: &[Point]) -> String {
+    let fits = fit_phases(points);
+    let sizes = points.iter().map(|p| p.size.max(1) as f64);
+    let (x_low, x_high) = sizes.fold((f64::MAX, f64::MIN), |(lo, hi), s| (lo.min(s), hi.max(s)));
+    let times = points
+        .iter()
+        .flat_map(|p| PHASES.iter().map(move |(_, phase)| phase(&p.timings)))
+        .map(|t| t.as_secs_f64().max(1e-9));
+    let (y_low, y_high) = times.fold((f64::MAX, f64::MIN), |(lo, hi), t| (lo.min(t), hi.max(t)));
+    let (x_low, x_high) = match points.is_empty() {
+        true => (1.0, 10.0),
+        false => (
+            10f64.powi(x_low.log10().floor() as i32),
+            10f64.powi(x_high.log10().ceil() as i32),
+        ),
+    };
+    let (y_low, y_high) = match points.is_empty() {
+        true => (1e-6, 1e-3),
+        false => (
+            10f64.powi(y_low.log10().floor() as i32),
+            10f64.powi(y_high.log10().ceil() as i32),
+        ),
+    };
+    let (x_high, y_high) = (x_high.max(x_low * 10.0), y_high.max(y_low * 10.0));
+
+    let plot_width = (PLOT_WIDTH - 2 * MARGIN) as f64;
+    let plot_height = (PLOT_HEIGHT - 2 * MARGIN) as f64;
+    let x =
+        |size: f64| MARGIN as f64 + (size / x_low).log10() / (x_high / x_low).log10() * plot_width;
+    let y = |secs: f64| {
+        (PLOT_HEIGHT - MARGIN) as f64
+            - (secs / y_low).log10() / (y_high / y_low).log10() * plot_height
+    };
+
+    let mut res = String::new();
+    let _ = writeln!(
+        res,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"12\">",
+        PLOT_WIDTH, PLOT_HEIGHT
+    );
+    let _ = writeln!(
+        res,
+        "<text x=\"{}\" y=\"20\" text-anchor=\"middle\" font-size=\"14\">{}: time against input size</text>",
+        PLOT_WIDTH / 2,
+        puzzle
+    );
+    for power in powers_of_ten(x_low, x_high) {
+        let at = x(10f64.powi(power));
+        let _ = writeln!(
+            res,
+            "<line x1=\"{0:.1}\" y1=\"{1}\" x2=\"{0:.1}\" y2=\"{2}\" stroke=\"#ddd\"/><text x=\"{0:.1}\" y=\"{3}\" text-anchor=\"middle\">{4}</text>",
+            at,
+            MARGIN,
+            PLOT_HEIGHT - MARGIN,
+            PLOT_HEIGHT - MARGIN + 16,
+            10u64.pow(power.max(0) as u32)
+        );
+    }
+    for power in powers_of_ten(y_low, y_high) {
+        let at = y(10f64.powi(power));
+        let _ = writeln!(
+            res,
+            "<line x1=\"{0}\" y1=\"{1:.1}\" x2=\"{2}\" y2=\"{1:.1}\" stroke=\"#ddd\"/><text x=\"{3}\" y=\"{4:.1}\" text-anchor=\"end\">{5:?}</text>",
+            MARGIN,
+            at,
+            PLOT_WIDTH - MARGIN,
+            MARGIN - 6,
+            at + 4.0,
+            Duration::from_secs_f64(10f64.powi(power))
+        );
+    }
+    let _ = writeln!(
+        res,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">size</text>",
+        PLOT_WIDTH / 2,
+        PLOT_HEIGHT - MARGIN + 34
+    );
+
+    for (i, ((name, phase), fit)) in PHASES.iter().zip(&fits).enumerate() {
+        let coords = points
+            .iter()
+            .map(|p| {
+                let secs = phase(&p.timings).as_secs_f64().max(1e-9);
+                format!("{:.1},{:.1}", x(p.size.max(1) as f64), y(secs))
+            })
+            .collect::<Vec<_>>();
+        let _ = writeln!(
+            res,
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+            coords.join(" "),
+            COLOURS[i]
+        );
+        for coord in &coords {
+            let (cx, cy) = coord.split_once(',').unwrap_or_default();
+            let _ = writeln!(
+                res,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"3\" fill=\"{}\"/>",
+                cx, cy, COLOURS[i]
+            );
+        }
+        let label = match fit {
+            Some(fit) => format!("{} {}", name, fit.class.name()),
+            None => name.to_string(),
+        };
+        let _ = writeln!(
+            res,
+            "<text x=\"{}\" y=\"{}\" fill=\"{}\">{}</text>",
+            MARGIN + 8,
+            MARGIN + 16 + i * 16,
+            COLOURS[i],
+            label
+        );
+    }
+    res.push_str("</svg>\n");
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scale::{fit, plot, sizes, Class, Point};
+    use crate::solution::{Puzzle, Timings};
+    use std::time::Duration;
+
+    fn points(f: impl Fn(f64) -> f64) -> Vec<(usize, Duration)> {
+        [10, 20, 50, 100, 200, 500, 1000]
+            .iter()
+            .map(|&n| (n, Duration::from_secs_f64(f(n as f64) * 1e-7)))
+            .collect()
+    }
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(sizes(10, 1000, 3), vec![10, 100, 1000]);
+        assert_eq!(sizes(1, 2, 5), vec![1, 2]);
+        assert_eq!(sizes(10, 1000, 7).len(), 7);
+    }
+
+    #[test]
+    fn test_fit() {
+        let class = |f: fn(f64) -> f64| fit(&points(f)).map(|fit| fit.class);
+        assert_eq!(class(|_| 1.0), Some(Class::Constant));
+        assert_eq!(class(|n| n.ln()), Some(Class::Logarithmic));
+        assert_eq!(class(|n| n), Some(Class::Linear));
+        assert_eq!(class(|n| n * n.ln()), Some(Class::Linearithmic));
+        assert_eq!(class(|n| n * n), Some(Class::Quadratic));
+        assert_eq!(class(|n| n * n * n), Some(Class::Cubic));
+
+        let exponential = [5, 10, 15, 20, 25]
+            .iter()
+            .map(|&n| (n, Duration::from_nanos(1 << n)))
+            .collect::<Vec<_>>();
+        assert_eq!(fit(&exponential).map(|f| f.class), Some(Class::Exponential));
+
+        let quadratic = fit(&points(|n| n * n)).expect("enough points");
+        assert!((quadratic.exponent - 2.0).abs() < 0.01);
+        assert_eq!(fit(&points(|n| n)[..2]), None);
+    }
+
+    #[test]
+    fn test_plot() {
+        let points = [10, 100, 1000]
+            .iter()
+            .map(|&size| Point {
+                size,
+                bytes: size * 10,
+                timings: Timings {
+                    parse: Duration::from_micros(size as u64),
+                    part1: Duration::from_micros(size as u64 * 2),
+                    part2: Duration::from_nanos(size as u64 * size as u64),
+                },
+            })
+            .collect::<Vec<_>>();
+        let svg = plot(Puzzle::new(2020, 9), &points);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("2020/day09"));
+        assert!(svg.contains("part2 O(n^2)"));
+        assert!(svg.contains("parse O(n)"));
+        assert_eq!(svg.matches("<polyline ").count(), 4);
+        assert!(plot(Puzzle::new(2020, 9), &[]).ends_with("</svg>\n"));
+    }
+}
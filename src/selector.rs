@@ -0,0 +1,286 @@
+//! Parses the day selectors given on the command line into the puzzles to run.
+
+use crate::bench::Repeats;
+use crate::solution::Puzzle;
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+/// Why a day selector couldn't be parsed.
+#[derive(Debug, PartialEq)]
+pub enum SelectorError {
+    /// A selector, named here, has an empty term.
+    Empty(String),
+    /// A day isn't a number.
+    InvalidDay(String),
+    /// A year isn't a number.
+    InvalidYear(String),
+    /// No solutions are registered for the year.
+    UnknownYear(usize),
+    /// The day is past the last one registered for the year.
+    OutOfRange(usize, usize),
+    /// A range's start is after its end.
+    Backwards(usize, usize),
+    /// The repeat count or time budget after `:` is invalid.
+    Repeats(String),
+    /// The selector, named here, picks no days at all.
+    NoDays(String),
+}
+
+impl Display for SelectorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectorError::Empty(arg) => write!(f, "empty day selector in {}", arg),
+            SelectorError::InvalidDay(day) => write!(f, "invalid day: {}", day),
+            SelectorError::InvalidYear(year) => write!(f, "invalid year: {}", year),
+            SelectorError::UnknownYear(year) => write!(f, "no solutions for year {}", year),
+            SelectorError::OutOfRange(day, last) => {
+                write!(f, "day {} is out of range, expected 1-{}", day, last)
+            }
+            SelectorError::Backwards(a, b) => write!(f, "invalid range: {} is after {}", a, b),
+            SelectorError::Repeats(err) => write!(f, "{}", err),
+            SelectorError::NoDays(arg) => write!(f, "{} does not select any days", arg),
+        }
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+/// The puzzles a selector picks and how many times to run them.
+#[derive(Debug, PartialEq)]
+pub struct Selection {
+    /// The puzzles, in order.
+    pub puzzles: Vec<Puzzle>,
+    /// How many times to run them, if the selector said.
+    pub repeats: Option<Repeats>,
+}
+
+fn parse_day(s: &str, last_day: usize) -> Result<usize, SelectorError> {
+    match s.strip_prefix("day").unwrap_or(s).parse::<usize>() {
+        Ok(day) if day > 0 && day <= last_day => Ok(day),
+        Ok(day) => Err(SelectorError::OutOfRange(day, last_day)),
+        Err(_) => Err(SelectorError::InvalidDay(s.to_string())),
+    }
+}
+
+/// Splits off the `YEAR/` prefix of `arg`, if there is one.
+fn split_year(arg: &str, default_year: usize) -> Result<(usize, &str), SelectorError> {
+    match arg.split_once('/') {
+        Some((year, rest)) => match year.parse::<usize>() {
+            Ok(year) => Ok((year, rest)),
+            Err(_) => Err(SelectorError::InvalidYear(year.to_string())),
+        },
+        None => Ok((default_year, arg)),
+    }
+}
+
+/// Parses a single puzzle such as `5`, `day05` or `2019/5`, whether or not it has a solution.
+pub fn parse_puzzle(arg: &str, default_year: usize) -> Result<Puzzle, SelectorError> {
+    let (year, day) = split_year(arg, default_year)?;
+    parse_day(day, 25).map(|day| Puzzle::new(year, day))
+}
+
+/// Parses a single term: `!`, `.`, a day, or a range where either end may be left open. Terms
+/// that don't name their days, which are `.` and open ranges, only pick days in `solved`.
+fn parse_term(term: &str, solved: &BTreeSet<usize>) -> Result<Vec<usize>, SelectorError> {
+    let last_day = solved.iter().next_back().copied().unwrap_or(0);
+    if term == "!" {
+        return Ok(solved.iter().next_back().copied().into_iter().collect());
+    }
+    if term == "." {
+        return Ok(solved.iter().copied().collect());
+    }
+
+    match term.split_once('-') {
+        Some((a, b)) => {
+            let a_day = match a {
+                "" => 1,
+                a => parse_day(a, last_day)?,
+            };
+            let b_day = match b {
+                "" => last_day,
+                b => parse_day(b, last_day)?,
+            };
+            match (a_day <= b_day, a.is_empty() || b.is_empty()) {
+                (false, _) => Err(SelectorError::Backwards(a_day, b_day)),
+                (true, true) => Ok(solved.range(a_day..=b_day).copied().collect()),
+                (true, false) => Ok((a_day..=b_day).collect()),
+            }
+        }
+        None => parse_day(term, last_day).map(|day| vec![day]),
+    }
+}
+
+/// Parses a day selector such as `5`, `day05`, `1-25,^15,^23`, `2019/20-` or `10-20:50`.
+///
+/// A selector is a comma separated list of terms, optionally prefixed with `YEAR/` and followed
+/// by `:` and a repeat count or time budget. Without a year it picks days from `default_year`.
+/// Terms prefixed with `^` are excluded from the selection, and a selector made up only of
+/// exclusions starts from every solved day. `solved` gives the days solved in a year, if any.
+pub fn parse(
+    arg: &str,
+    default_year: usize,
+    solved: impl Fn(usize) -> Option<BTreeSet<usize>>,
+) -> Result<Selection, SelectorError> {
+    let (terms, repeats) = match arg.split_once(':') {
+        Some((terms, repeats)) => {
+            let repeats = repeats.parse::<Repeats>().map_err(SelectorError::Repeats)?;
+            (terms, Some(repeats))
+        }
+        None => (arg, None),
+    };
+    let (year, terms) = split_year(terms, default_year)?;
+    let solved = solved(year).ok_or(SelectorError::UnknownYear(year))?;
+
+    let mut included = BTreeSet::new();
+    let mut excluded = BTreeSet::new();
+    let mut only_exclusions = true;
+    for term in terms.split(',') {
+        let (set, term) = match term.strip_prefix('^') {
+            Some(term) => (&mut excluded, term),
+            None => {
+                only_exclusions = false;
+                (&mut included, term)
+            }
+        };
+        if term.is_empty() {
+            return Err(SelectorError::Empty(arg.to_string()));
+        }
+        set.extend(parse_term(term, &solved)?);
+    }
+
+    if only_exclusions {
+        included.extend(&solved);
+    }
+    let puzzles = included
+        .difference(&excluded)
+        .map(|&day| Puzzle::new(year, day))
+        .collect::<Vec<_>>();
+    if puzzles.is_empty() {
+        return Err(SelectorError::NoDays(arg.to_string()));
+    }
+
+    Ok(Selection { puzzles, repeats })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::Repeats;
+    use crate::selector::{parse, parse_puzzle, Selection, SelectorError};
+    use crate::solution::Puzzle;
+    use std::time::Duration;
+
+    fn parse_2020(arg: &str) -> Result<Selection, SelectorError> {
+        parse(arg, 2020, |year| match year {
+            2019 => Some((1..=10).collect()),
+            2020 => Some((1..=25).collect()),
+            2021 => Some([3, 5, 6].iter().copied().collect()),
+            _ => None,
+        })
+    }
+
+    fn days(arg: &str) -> Vec<usize> {
+        let selection = parse_2020(arg).expect("valid selector");
+        selection.puzzles.iter().map(|p| p.day).collect()
+    }
+
+    #[test]
+    fn test_days() {
+        assert_eq!(days("5"), vec![5]);
+        assert_eq!(days("!"), vec![25]);
+        assert_eq!(days("."), (1..=25).collect::<Vec<_>>());
+        assert_eq!(days("3-6"), vec![3, 4, 5, 6]);
+        assert_eq!(days("7-7"), vec![7]);
+        assert_eq!(days("22-"), vec![22, 23, 24, 25]);
+        assert_eq!(days("-3"), vec![1, 2, 3]);
+        assert_eq!(days("9,2,2,4-5"), vec![2, 4, 5, 9]);
+        assert_eq!(
+            days("1-25,^15,^23"),
+            (1..=25).filter(|&d| d != 15 && d != 23).collect::<Vec<_>>()
+        );
+        assert_eq!(days("^2-25"), vec![1]);
+        assert_eq!(days(".,^!"), (1..=24).collect::<Vec<_>>());
+        assert_eq!(days("day05,day07-day08"), vec![5, 7, 8]);
+    }
+
+    #[test]
+    fn test_years() {
+        let selection = parse_2020("2019/day05").expect("valid selector");
+        assert_eq!(selection.puzzles, vec![Puzzle::new(2019, 5)]);
+        assert_eq!(days("2019/."), (1..=10).collect::<Vec<_>>());
+        assert_eq!(days("2019/!"), vec![10]);
+        assert_eq!(days("2020/!"), vec![25]);
+        assert_eq!(
+            parse_2020("2019/7-:3").expect("valid selector").repeats,
+            Some(Repeats::Count(3))
+        );
+        assert_eq!(
+            parse_2020("2019/11"),
+            Err(SelectorError::OutOfRange(11, 10))
+        );
+        assert_eq!(parse_2020("2015/1"), Err(SelectorError::UnknownYear(2015)));
+        assert_eq!(
+            parse_2020("x/1"),
+            Err(SelectorError::InvalidYear("x".to_string()))
+        );
+        assert_eq!(parse_puzzle("2015/day03", 2020), Ok(Puzzle::new(2015, 3)));
+        assert_eq!(parse_puzzle("4", 2020), Ok(Puzzle::new(2020, 4)));
+        assert_eq!(
+            parse_puzzle("2015/26", 2020),
+            Err(SelectorError::OutOfRange(26, 25))
+        );
+    }
+
+    #[test]
+    fn test_partly_solved() {
+        assert_eq!(days("2021/."), vec![3, 5, 6]);
+        assert_eq!(days("2021/-5"), vec![3, 5]);
+        assert_eq!(days("2021/4-"), vec![5, 6]);
+        assert_eq!(days("2021/^5"), vec![3, 6]);
+        assert_eq!(days("2021/!"), vec![6]);
+        assert_eq!(days("2021/1-3"), vec![1, 2, 3]);
+        assert_eq!(parse_2020("2021/7"), Err(SelectorError::OutOfRange(7, 6)));
+    }
+
+    #[test]
+    fn test_repeats() {
+        let selection = parse_2020("10-20:50").expect("valid selector");
+        assert_eq!(days("10-20:50"), (10..=20).collect::<Vec<_>>());
+        assert_eq!(selection.repeats, Some(Repeats::Count(50)));
+
+        let selection = parse_2020("3:2s").expect("valid selector");
+        assert_eq!(
+            selection.repeats,
+            Some(Repeats::Budget(Duration::from_secs(2)))
+        );
+        assert_eq!(parse_2020("3").expect("valid selector").repeats, None);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse_2020("26"), Err(SelectorError::OutOfRange(26, 25)));
+        assert_eq!(parse_2020("0-3"), Err(SelectorError::OutOfRange(0, 25)));
+        assert_eq!(parse_2020("9-3"), Err(SelectorError::Backwards(9, 3)));
+        assert_eq!(
+            parse_2020("x"),
+            Err(SelectorError::InvalidDay("x".to_string()))
+        );
+        assert_eq!(
+            parse_2020("1-2-3"),
+            Err(SelectorError::InvalidDay("2-3".to_string()))
+        );
+        assert_eq!(
+            parse_2020("1,,2"),
+            Err(SelectorError::Empty("1,,2".to_string()))
+        );
+        assert_eq!(
+            parse_2020("3,^3"),
+            Err(SelectorError::NoDays("3,^3".to_string()))
+        );
+        assert!(matches!(parse_2020("3:0"), Err(SelectorError::Repeats(_))));
+        assert!(matches!(
+            parse_2020("3:1:2"),
+            Err(SelectorError::Repeats(_))
+        ));
+        assert!(parse_2020("--frobnicate").is_err());
+    }
+}
@@ -0,0 +1,504 @@
+//! The [`Solution`] trait every day implements, and the registries that collect them by year.
+
+use crate::bench;
+use crate::crosscheck::Check;
+use crate::error::Result;
+use crate::generate::Rng;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::hint::black_box;
+use std::ops::AddAssign;
+use std::time::{Duration, Instant};
+
+/// A day's puzzle: how to parse its input and solve both parts.
+pub trait Solution {
+    /// The input once parsed, which may borrow from it.
+    type Parsed<'a>;
+
+    /// The day of December, from 1 to 25.
+    fn day(&self) -> usize;
+    /// The puzzle's title.
+    fn title(&self) -> &'static str;
+    /// The input run by default, usually embedded with `include_str!`.
+    fn input(&self) -> &'static str;
+
+    /// Parses the input, failing with a position if it's invalid.
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Parsed<'a>>;
+    /// Solves part 1.
+    fn part1(&self, parsed: &Self::Parsed<'_>) -> Result<String>;
+    /// Solves part 2.
+    fn part2(&self, parsed: &Self::Parsed<'_>) -> Result<String>;
+
+    /// A random valid input that grows with `size`, see [`crate::generate`].
+    fn generate(&self, _rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// The largest `size` that [`Solution::generate`] grows inputs to, for days whose inputs
+    /// can't grow forever. Larger sizes get an input of this size.
+    fn max_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// What drives this day's cost that [`Solution::generate`] leaves the same whatever the size,
+    /// which makes timings against size say little about the solver.
+    fn unscaled(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// A slow but straightforward part 1 to check [`Solution::part1`] against, see
+    /// [`crate::crosscheck`]. It starts from the input rather than [`Solution::parse`], in case
+    /// that's where the trick is, and fails on inputs without a single right answer.
+    fn reference_part1(&self, _input: &str) -> Option<Result<String>> {
+        None
+    }
+
+    /// Like [`Solution::reference_part1`], for part 2.
+    fn reference_part2(&self, _input: &str) -> Option<Result<String>> {
+        None
+    }
+}
+
+/// A single day's puzzle within a year, written as `2020/day05`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    /// The year, such as 2020.
+    pub year: usize,
+    /// The day of December, from 1 to 25.
+    pub day: usize,
+}
+
+impl Puzzle {
+    /// Day `day` of `year`.
+    pub fn new(year: usize, day: usize) -> Puzzle {
+        Puzzle { year, day }
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/day{:02}", self.year, self.day)
+    }
+}
+
+/// How long each phase of a run took.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Timings {
+    /// Time spent parsing.
+    pub parse: Duration,
+    /// Time spent solving part 1.
+    pub part1: Duration,
+    /// Time spent solving part 2.
+    pub part2: Duration,
+}
+
+impl Timings {
+    /// All three phases together.
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+
+    /// The faster of each phase in `self` and `other`.
+    pub fn min(self, other: Timings) -> Timings {
+        Timings {
+            parse: Ord::min(self.parse, other.parse),
+            part1: Ord::min(self.part1, other.part1),
+            part2: Ord::min(self.part2, other.part2),
+        }
+    }
+}
+
+impl AddAssign for Timings {
+    fn add_assign(&mut self, other: Timings) {
+        self.parse += other.parse;
+        self.part1 += other.part1;
+        self.part2 += other.part2;
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} (parse {:?}, part 1 {:?}, part 2 {:?})",
+            self.total(),
+            self.parse,
+            self.part1,
+            self.part2
+        )
+    }
+}
+
+/// The answers from running a day once, and how long it took.
+#[derive(Debug)]
+pub struct Run {
+    /// The answer to part 1.
+    pub part1: String,
+    /// The answer to part 2.
+    pub part2: String,
+    /// How long each phase took.
+    pub timings: Timings,
+}
+
+/// Object safe view of a [`Solution`]. Solutions must be `Sync` so days can run in parallel.
+pub trait DynSolution: Sync {
+    /// See [`Solution::title`].
+    fn title(&self) -> &'static str;
+    /// See [`Solution::input`].
+    fn input(&self) -> &'static str;
+    /// See [`Solution::generate`].
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String>;
+    /// See [`Solution::max_size`].
+    fn max_size(&self) -> Option<usize>;
+    /// See [`Solution::unscaled`].
+    fn unscaled(&self) -> Option<&'static str>;
+    /// Parses `input` and, if `solve`, runs both parts even if the first fails, returning the
+    /// first error. For fuzzing, where every phase should see every input.
+    fn check(&self, input: &str, solve: bool) -> Result<()>;
+    /// Parses `input` and solves both parts once, timing each phase. Fails with the phase that
+    /// failed.
+    fn run(&self, input: &str) -> Result<Run>;
+    /// Parses `input` once, then hands `phase` each of `"parse"`, `"part1"` and `"part2"` with a
+    /// closure that runs just that phase again, for benchmarks that time the phases separately.
+    /// Fails without calling `phase` if any phase fails.
+    fn phases(
+        &self,
+        input: &str,
+        phase: &mut dyn FnMut(&'static str, &mut dyn FnMut()),
+    ) -> Result<()>;
+    /// Each part with a reference solver next to that solver's answer. Panics in either count as
+    /// errors.
+    fn crosscheck(&self, input: &str) -> Result<Vec<Check>>;
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn title(&self) -> &'static str {
+        Solution::title(self)
+    }
+
+    fn input(&self) -> &'static str {
+        Solution::input(self)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Solution::generate(self, rng, size)
+    }
+
+    fn max_size(&self) -> Option<usize> {
+        Solution::max_size(self)
+    }
+
+    fn unscaled(&self) -> Option<&'static str> {
+        Solution::unscaled(self)
+    }
+
+    fn check(&self, input: &str, solve: bool) -> Result<()> {
+        let parsed = self.parse(input).map_err(|err| err.during("parse"))?;
+        if !solve {
+            return Ok(());
+        }
+        let part1 = self.part1(&parsed).map_err(|err| err.during("part 1"));
+        let part2 = self.part2(&parsed).map_err(|err| err.during("part 2"));
+        part1.and(part2).map(drop)
+    }
+
+    fn run(&self, input: &str) -> Result<Run> {
+        let start = Instant::now();
+        let parsed = self.parse(input).map_err(|err| err.during("parse"))?;
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        let part1 = self.part1(&parsed).map_err(|err| err.during("part 1"))?;
+        let part1_time = start.elapsed();
+
+        let start = Instant::now();
+        let part2 = self.part2(&parsed).map_err(|err| err.during("part 2"))?;
+        let part2_time = start.elapsed();
+
+        Ok(Run {
+            part1,
+            part2,
+            timings: Timings {
+                parse,
+                part1: part1_time,
+                part2: part2_time,
+            },
+        })
+    }
+
+    fn phases(
+        &self,
+        input: &str,
+        phase: &mut dyn FnMut(&'static str, &mut dyn FnMut()),
+    ) -> Result<()> {
+        let parsed = self.parse(input).map_err(|err| err.during("parse"))?;
+        self.part1(&parsed).map_err(|err| err.during("part 1"))?;
+        self.part2(&parsed).map_err(|err| err.during("part 2"))?;
+
+        phase("parse", &mut || {
+            let _ = black_box(self.parse(black_box(input)));
+        });
+        phase("part1", &mut || {
+            let _ = black_box(self.part1(black_box(&parsed)));
+        });
+        phase("part2", &mut || {
+            let _ = black_box(self.part2(black_box(&parsed)));
+        });
+        Ok(())
+    }
+
+    fn crosscheck(&self, input: &str) -> Result<Vec<Check>> {
+        let parsed = bench::catch(|| self.parse(input)).map_err(|err| err.during("parse"))?;
+        let mut res = Vec::new();
+        if let Some(reference) =
+            bench::catch(|| self.reference_part1(input).transpose()).transpose()
+        {
+            let answer = reference
+                .is_ok()
+                .then(|| bench::catch(|| self.part1(&parsed)));
+            res.push(Check {
+                part: 1,
+                reference,
+                answer,
+            });
+        }
+        if let Some(reference) =
+            bench::catch(|| self.reference_part2(input).transpose()).transpose()
+        {
+            let answer = reference
+                .is_ok()
+                .then(|| bench::catch(|| self.part2(&parsed)));
+            res.push(Check {
+                part: 2,
+                reference,
+                answer,
+            });
+        }
+        Ok(res)
+    }
+}
+
+/// The solutions for one year, keyed by day.
+pub struct Registry {
+    year: usize,
+    solutions: BTreeMap<usize, Box<dyn DynSolution>>,
+}
+
+impl Registry {
+    /// An empty registry for `year`.
+    pub fn new(year: usize) -> Registry {
+        Registry {
+            year,
+            solutions: BTreeMap::new(),
+        }
+    }
+
+    /// The year the solutions are for.
+    pub fn year(&self) -> usize {
+        self.year
+    }
+
+    /// Registers a solution under its day, replacing any solution already registered for that day
+    /// so that alternate implementations can be swapped in.
+    pub fn register<S: Solution + Sync + 'static>(&mut self, solution: S) {
+        self.solutions
+            .insert(Solution::day(&solution), Box::new(solution));
+    }
+
+    /// The solution for `day`, if there is one.
+    pub fn get(&self, day: usize) -> Option<&dyn DynSolution> {
+        self.solutions.get(&day).map(|s| s.as_ref())
+    }
+
+    /// The days with a solution registered.
+    pub fn days(&self) -> BTreeSet<usize> {
+        self.solutions.keys().copied().collect()
+    }
+
+    /// Every registered puzzle, ordered by day.
+    pub fn puzzles(&self) -> impl Iterator<Item = Puzzle> + '_ {
+        self.solutions
+            .keys()
+            .map(move |&day| Puzzle::new(self.year, day))
+    }
+}
+
+/// Every year's registry, keyed by year.
+#[derive(Default)]
+pub struct Calendar {
+    years: BTreeMap<usize, Registry>,
+}
+
+impl Calendar {
+    /// An empty calendar.
+    pub fn new() -> Calendar {
+        Calendar::default()
+    }
+
+    /// Adds a year, replacing any registry already added for it.
+    pub fn add(&mut self, registry: Registry) {
+        self.years.insert(registry.year(), registry);
+    }
+
+    /// The registry for `year`, if it was added.
+    pub fn year(&self, year: usize) -> Option<&Registry> {
+        self.years.get(&year)
+    }
+
+    /// The solution for `puzzle`, if there is one.
+    pub fn get(&self, puzzle: Puzzle) -> Option<&dyn DynSolution> {
+        self.year(puzzle.year)?.get(puzzle.day)
+    }
+
+    /// The most recent year added.
+    pub fn latest_year(&self) -> Option<usize> {
+        self.years.keys().next_back().copied()
+    }
+
+    /// Every registered puzzle, ordered by year and then day.
+    pub fn puzzles(&self) -> impl Iterator<Item = Puzzle> + '_ {
+        self.years.values().flat_map(Registry::puzzles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{self, Error, Position, Result};
+    use crate::solution::{Calendar, DynSolution, Puzzle, Registry, Solution, Timings};
+    use std::time::Duration;
+
+    struct Sum(usize);
+
+    impl Solution for Sum {
+        type Parsed<'a> = Vec<usize>;
+
+        fn day(&self) -> usize {
+            self.0
+        }
+
+        fn title(&self) -> &'static str {
+            "Sum"
+        }
+
+        fn input(&self) -> &'static str {
+            "1\n2\n3"
+        }
+
+        fn parse(&self, input: &str) -> Result<Vec<usize>> {
+            input.lines().map(|l| error::parse(input, l)).collect()
+        }
+
+        fn part1(&self, nums: &Vec<usize>) -> Result<String> {
+            Ok(nums.iter().sum::<usize>().to_string())
+        }
+
+        fn part2(&self, nums: &Vec<usize>) -> Result<String> {
+            match nums.iter().product::<usize>() {
+                0 => Err(Error::new("zero product")),
+                product => Ok(product.to_string()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new(2020);
+        registry.register(Sum(3));
+        registry.register(Sum(1));
+        assert_eq!(
+            registry.puzzles().collect::<Vec<_>>(),
+            vec![Puzzle::new(2020, 1), Puzzle::new(2020, 3)]
+        );
+        assert_eq!(registry.days().into_iter().collect::<Vec<_>>(), vec![1, 3]);
+        assert!(registry.get(2).is_none());
+
+        let solution = registry.get(3).expect("registered");
+        let run = solution.run(solution.input()).expect("valid input");
+        assert_eq!((run.part1.as_str(), run.part2.as_str()), ("6", "6"));
+    }
+
+    #[test]
+    fn test_calendar() {
+        let mut calendar = Calendar::new();
+        for (year, days) in [(2020, [1, 2]), (2019, [5, 7])] {
+            let mut registry = Registry::new(year);
+            days.iter().for_each(|&day| registry.register(Sum(day)));
+            calendar.add(registry);
+        }
+        assert_eq!(calendar.latest_year(), Some(2020));
+        assert_eq!(
+            calendar
+                .puzzles()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>(),
+            vec!["2019/day05", "2019/day07", "2020/day01", "2020/day02"]
+        );
+        assert!(calendar.get(Puzzle::new(2019, 7)).is_some());
+        assert!(calendar.get(Puzzle::new(2020, 7)).is_none());
+        assert!(calendar.get(Puzzle::new(2018, 1)).is_none());
+    }
+
+    #[test]
+    fn test_run_errors() {
+        let err = Sum(1)
+            .run(
+                "1
+2
+three",
+            )
+            .expect_err("invalid input");
+        assert_eq!(err.phase, Some("parse"));
+        assert_eq!(err.position, Some(Position { line: 3, column: 1 }));
+
+        let err = Sum(1)
+            .run(
+                "1
+0",
+            )
+            .expect_err("no answer");
+        assert_eq!(err.to_string(), "part 2: zero product");
+    }
+
+    #[test]
+    fn test_check_and_phases() {
+        assert_eq!(Sum(1).check("1\n0", false), Ok(()));
+        let err = Sum(1).check("1\n0", true).expect_err("no answer");
+        assert_eq!(err.to_string(), "part 2: zero product");
+        assert_eq!(
+            Sum(1).check("three", false).map_err(|err| err.phase),
+            Err(Some("parse"))
+        );
+
+        let mut names = Vec::new();
+        Sum(1)
+            .phases("1\n2", &mut |name, run| {
+                run();
+                names.push(name);
+            })
+            .expect("valid input");
+        assert_eq!(names, ["parse", "part1", "part2"]);
+        assert!(Sum(1)
+            .phases("1\n0", &mut |_, _| panic!("failed first"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_timings() {
+        let a = Timings {
+            parse: Duration::from_millis(1),
+            part1: Duration::from_millis(6),
+            part2: Duration::from_millis(2),
+        };
+        let b = Timings {
+            parse: Duration::from_millis(3),
+            part1: Duration::from_millis(2),
+            part2: Duration::from_millis(4),
+        };
+        assert_eq!(a.total(), Duration::from_millis(9));
+        assert_eq!(a.min(b).total(), Duration::from_millis(5));
+
+        let mut sum = a;
+        sum += b;
+        assert_eq!(sum.part1, Duration::from_millis(8));
+    }
+}
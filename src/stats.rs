@@ -0,0 +1,201 @@
+//! Summary statistics over repeated runs of a day.
+
+use crate::solution::Timings;
+use std::time::Duration;
+
+/// How many samples were outliers, split by side and by how far out they were.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Outliers {
+    /// More than 3 interquartile ranges below the lower quartile.
+    pub low_severe: usize,
+    /// Between 1.5 and 3 interquartile ranges below the lower quartile.
+    pub low_mild: usize,
+    /// Between 1.5 and 3 interquartile ranges above the upper quartile.
+    pub high_mild: usize,
+    /// More than 3 interquartile ranges above the upper quartile.
+    pub high_severe: usize,
+}
+
+impl Outliers {
+    /// All outliers together.
+    pub fn total(&self) -> usize {
+        self.low_severe + self.low_mild + self.high_mild + self.high_severe
+    }
+}
+
+/// Statistics of a set of timings.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Summary {
+    /// How many timings there were.
+    pub samples: usize,
+    /// The fastest.
+    pub min: Duration,
+    /// The slowest.
+    pub max: Duration,
+    /// The mean.
+    pub mean: Duration,
+    /// The median.
+    pub median: Duration,
+    /// The sample standard deviation, or zero with fewer than two samples.
+    pub std_dev: Duration,
+    /// The 50th percentile.
+    pub p50: Duration,
+    /// The 90th percentile.
+    pub p90: Duration,
+    /// The 99th percentile.
+    pub p99: Duration,
+    /// Timings far from the rest.
+    pub outliers: Outliers,
+}
+
+fn to_duration(secs: f64) -> Duration {
+    if secs.is_finite() && secs >= 0.0 {
+        Duration::from_secs_f64(secs)
+    } else {
+        Duration::default()
+    }
+}
+
+/// Linearly interpolated percentile of already sorted samples, with `p` in `0..=100`.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0],
+        n => {
+            let rank = p / 100.0 * (n - 1) as f64;
+            let low = rank.floor() as usize;
+            let high = rank.ceil() as usize;
+            sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f64)
+        }
+    }
+}
+
+/// Classifies samples using Tukey's fences: anything more than 1.5 interquartile ranges outside
+/// the quartiles is a mild outlier, and more than 3 is a severe one.
+fn outliers(sorted: &[f64]) -> Outliers {
+    let q1 = percentile(sorted, 25.0);
+    let q3 = percentile(sorted, 75.0);
+    let iqr = q3 - q1;
+    let (low_severe, low_mild) = (q1 - 3.0 * iqr, q1 - 1.5 * iqr);
+    let (high_mild, high_severe) = (q3 + 1.5 * iqr, q3 + 3.0 * iqr);
+
+    let mut res = Outliers::default();
+    for &s in sorted {
+        if s < low_severe {
+            res.low_severe += 1;
+        } else if s < low_mild {
+            res.low_mild += 1;
+        } else if s > high_severe {
+            res.high_severe += 1;
+        } else if s > high_mild {
+            res.high_mild += 1;
+        }
+    }
+    res
+}
+
+impl Summary {
+    /// Summarises `samples`, which may be empty.
+    pub fn new(samples: &[Duration]) -> Summary {
+        let mut sorted = samples.iter().map(|d| d.as_secs_f64()).collect::<Vec<_>>();
+        sorted.sort_by(|a, b| a.partial_cmp(b).expect("durations are never NaN"));
+
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = match sorted.len() {
+            0 | 1 => 0.0,
+            _ => sorted.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0),
+        };
+
+        Summary {
+            samples: sorted.len(),
+            min: to_duration(sorted.first().copied().unwrap_or(0.0)),
+            max: to_duration(sorted.last().copied().unwrap_or(0.0)),
+            mean: to_duration(mean),
+            median: to_duration(percentile(&sorted, 50.0)),
+            std_dev: to_duration(variance.sqrt()),
+            p50: to_duration(percentile(&sorted, 50.0)),
+            p90: to_duration(percentile(&sorted, 90.0)),
+            p99: to_duration(percentile(&sorted, 99.0)),
+            outliers: outliers(&sorted),
+        }
+    }
+}
+
+/// Statistics of each phase across a set of runs.
+pub struct PhaseSummary {
+    /// Of parsing.
+    pub parse: Summary,
+    /// Of part 1.
+    pub part1: Summary,
+    /// Of part 2.
+    pub part2: Summary,
+    /// Of all three phases together.
+    pub total: Summary,
+}
+
+impl PhaseSummary {
+    /// Summarises each phase of `runs`.
+    pub fn new(runs: &[Timings]) -> PhaseSummary {
+        let phase =
+            |f: fn(&Timings) -> Duration| Summary::new(&runs.iter().map(f).collect::<Vec<_>>());
+
+        PhaseSummary {
+            parse: phase(|t| t.parse),
+            part1: phase(|t| t.part1),
+            part2: phase(|t| t.part2),
+            total: phase(Timings::total),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::{percentile, Outliers, Summary};
+    use std::time::Duration;
+
+    fn millis(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().map(|&m| Duration::from_millis(m)).collect()
+    }
+
+    #[test]
+    fn test_percentile() {
+        let sorted = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 50.0), 3.0);
+        assert_eq!(percentile(&sorted, 90.0), 4.6);
+        assert_eq!(percentile(&sorted, 100.0), 5.0);
+        assert_eq!(percentile(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = Summary::new(&millis(&[4, 2, 8, 6]));
+        assert_eq!(summary.samples, 4);
+        assert_eq!(summary.min, Duration::from_millis(2));
+        assert_eq!(summary.max, Duration::from_millis(8));
+        assert_eq!(summary.mean, Duration::from_millis(5));
+        assert_eq!(summary.median, Duration::from_millis(5));
+        assert_eq!(summary.std_dev.as_micros(), 2_581);
+        assert_eq!(summary.outliers.total(), 0);
+    }
+
+    #[test]
+    fn test_outliers() {
+        let summary = Summary::new(&millis(&[10, 10, 11, 11, 12, 12, 16, 40, 1]));
+        assert_eq!(
+            summary.outliers,
+            Outliers {
+                low_severe: 1,
+                low_mild: 0,
+                high_mild: 1,
+                high_severe: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        assert_eq!(Summary::new(&[]), Summary::default());
+    }
+}
@@ -0,0 +1,614 @@
+//! Submits answers to the Advent of Code site, refusing ones the history already rules out.
+
+use crate::fetch;
+use crate::solution::Puzzle;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Where submissions are recorded unless `--history` says otherwise.
+pub const HISTORY_PATH: &str = "submissions.txt";
+
+/// What the server made of a submitted answer.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Outcome {
+    /// The answer was right.
+    Right,
+    /// The answer was wrong.
+    Wrong,
+    /// The answer was wrong and too high.
+    TooHigh,
+    /// The answer was wrong and too low.
+    TooLow,
+    /// Submitted too soon after the last answer.
+    RateLimited,
+    /// The part was already solved.
+    Solved,
+    /// The reply wasn't recognised.
+    Unknown,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 7] = [
+        Outcome::Right,
+        Outcome::Wrong,
+        Outcome::TooHigh,
+        Outcome::TooLow,
+        Outcome::RateLimited,
+        Outcome::Solved,
+        Outcome::Unknown,
+    ];
+
+    /// The outcome as written in the history.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Outcome::Right => "right",
+            Outcome::Wrong => "wrong",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::RateLimited => "rate-limited",
+            Outcome::Solved => "already-solved",
+            Outcome::Unknown => "unknown",
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::Wrong | Outcome::TooHigh | Outcome::TooLow)
+    }
+}
+
+/// The server's reply to a submission.
+#[derive(Debug, PartialEq)]
+pub struct Reply {
+    /// What the reply says of the answer.
+    pub outcome: Outcome,
+    /// How long the server wants us to wait before the next submission.
+    pub wait: Duration,
+    /// The reply's text, without its HTML.
+    pub message: String,
+}
+
+/// The text of the page's `<article>`, or of the whole page if it has none.
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads either `You have 1m 5s left to wait` or `Please wait 5 minutes before trying again`.
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some((before, _)) = text.split_once(" left to wait") {
+        let spec = before.rsplit("You have ").next()?;
+        return spec
+            .split_whitespace()
+            .try_fold(Duration::default(), |acc, part| {
+                let unit = match part.chars().last()? {
+                    'h' => 3600,
+                    'm' => 60,
+                    's' => 1,
+                    _ => return None,
+                };
+                let n = part[..part.len() - 1].parse::<u64>().ok()?;
+                acc.checked_add(Duration::from_secs(n.checked_mul(unit)?))
+            });
+    }
+
+    let (_, after) = text.split_once("wait ")?;
+    let (amount, _) = after.split_once(" before trying again")?;
+    match amount.split_once(' ')? {
+        ("one", _) => Some(Duration::from_secs(60)),
+        (n, unit) if unit.starts_with("minute") => {
+            let n = n.parse::<u64>().ok()?;
+            n.checked_mul(60).map(Duration::from_secs)
+        }
+        _ => None,
+    }
+}
+
+/// Reads the outcome and any wait out of the HTML page the server replies with.
+pub fn parse_reply(html: &str) -> Reply {
+    let message = article_text(html);
+    let outcome = if message.contains("That's the right answer") {
+        Outcome::Right
+    } else if message.contains("answer is too high") {
+        Outcome::TooHigh
+    } else if message.contains("answer is too low") {
+        Outcome::TooLow
+    } else if message.contains("That's not the right answer") {
+        Outcome::Wrong
+    } else if message.contains("You gave an answer too recently") {
+        Outcome::RateLimited
+    } else if message.contains("You don't seem to be solving the right level") {
+        Outcome::Solved
+    } else {
+        Outcome::Unknown
+    };
+    Reply {
+        outcome,
+        wait: parse_wait(&message).unwrap_or_default(),
+        message,
+    }
+}
+
+/// One answer submitted, as recorded in the history.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attempt {
+    /// The puzzle answered.
+    pub puzzle: Puzzle,
+    /// Which part, 1 or 2.
+    pub part: usize,
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    /// Seconds the server asked to wait before the next submission.
+    pub wait: u64,
+    /// What the server made of the answer.
+    pub outcome: Outcome,
+    /// The answer submitted.
+    pub answer: String,
+}
+
+/// Why an answer wasn't submitted.
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    /// The part was already solved with this answer.
+    Solved(String),
+    /// The same answer was already submitted, with this outcome.
+    Rejected(Outcome),
+    /// A numeric answer on the wrong side of one the server already said was too high or low.
+    Bound(Outcome, String),
+    /// The server asked to wait this much longer before the next submission.
+    Wait(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "already solved with {}", answer),
+            Refusal::Rejected(outcome) => write!(f, "already submitted as {}", outcome.name()),
+            Refusal::Bound(Outcome::TooLow, previous) => {
+                write!(f, "the answer must be above {}", previous)
+            }
+            Refusal::Bound(_, previous) => write!(f, "the answer must be below {}", previous),
+            Refusal::Wait(left) => {
+                write!(
+                    f,
+                    "submitted too recently, {}s left to wait",
+                    left.as_secs()
+                )
+            }
+        }
+    }
+}
+
+/// Every submission made so far, stored one per line as tab separated `year`, `day`, `part`,
+/// `time`, `wait`, `outcome` and `answer` columns.
+#[derive(Debug, Default, PartialEq)]
+pub struct History {
+    /// The attempts, oldest first.
+    pub attempts: Vec<Attempt>,
+}
+
+fn parse_attempt(line: &str) -> Result<Attempt, String> {
+    match line.split('\t').collect::<Vec<_>>().as_slice() {
+        &[year, day, part, time, wait, outcome, answer] => {
+            let number = |field: &str, name| {
+                field
+                    .parse::<u64>()
+                    .map_err(|err| format!("invalid {} {}: {}", name, field, err))
+            };
+            let outcome = Outcome::ALL
+                .iter()
+                .copied()
+                .find(|o| o.name() == outcome)
+                .ok_or_else(|| format!("invalid outcome {}", outcome))?;
+            Ok(Attempt {
+                puzzle: Puzzle::new(number(year, "year")? as usize, number(day, "day")? as usize),
+                part: number(part, "part")? as usize,
+                time: number(time, "time")?,
+                wait: number(wait, "wait")?,
+                outcome,
+                answer: answer.to_string(),
+            })
+        }
+        fields => Err(format!("expected 7 fields, found {}", fields.len())),
+    }
+}
+
+impl History {
+    /// Loads the history at `path`, treating a missing file as no submissions at all.
+    pub fn load(path: &Path) -> Result<History, SubmitError> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => History::parse(&contents)
+                .map_err(|(line, err)| SubmitError::Parse(path.to_path_buf(), line, err)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(History::default()),
+            Err(err) => Err(SubmitError::Io(path.to_path_buf(), err)),
+        }
+    }
+
+    /// Writes the history to `path`, replacing what was there.
+    pub fn save(&self, path: &Path) -> Result<(), SubmitError> {
+        std::fs::write(path, self.to_string())
+            .map_err(|err| SubmitError::Io(path.to_path_buf(), err))
+    }
+
+    fn parse(contents: &str) -> Result<History, (usize, String)> {
+        let attempts = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| parse_attempt(line).map_err(|err| (i + 1, err)))
+            .collect::<Result<_, _>>()?;
+        Ok(History { attempts })
+    }
+
+    /// Checks whether `answer` is worth submitting at `now`, given what the server has already
+    /// told us about this part and how long it asked us to wait.
+    pub fn check(
+        &self,
+        puzzle: Puzzle,
+        part: usize,
+        answer: &str,
+        now: u64,
+    ) -> Result<(), Refusal> {
+        let numeric = answer.parse::<i64>().ok();
+        for attempt in self
+            .attempts
+            .iter()
+            .filter(|a| a.puzzle == puzzle && a.part == part)
+        {
+            if attempt.outcome == Outcome::Right {
+                return Err(Refusal::Solved(attempt.answer.clone()));
+            }
+            if attempt.outcome.is_wrong() && attempt.answer == answer {
+                return Err(Refusal::Rejected(attempt.outcome));
+            }
+            let previous = attempt.answer.parse::<i64>().ok();
+            match (attempt.outcome, numeric, previous) {
+                (Outcome::TooHigh, Some(n), Some(p)) if n >= p => {
+                    return Err(Refusal::Bound(Outcome::TooHigh, attempt.answer.clone()))
+                }
+                (Outcome::TooLow, Some(n), Some(p)) if n <= p => {
+                    return Err(Refusal::Bound(Outcome::TooLow, attempt.answer.clone()))
+                }
+                _ => (),
+            }
+        }
+
+        // the server limits submissions across every puzzle, not per part
+        match self
+            .attempts
+            .iter()
+            .map(|a| a.time.saturating_add(a.wait))
+            .max()
+        {
+            Some(until) if until > now => Err(Refusal::Wait(Duration::from_secs(until - now))),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Display for History {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# year\tday\tpart\ttime\twait\toutcome\tanswer")?;
+        for a in &self.attempts {
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                a.puzzle.year,
+                a.puzzle.day,
+                a.part,
+                a.time,
+                a.wait,
+                a.outcome.name(),
+                a.answer
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Why an answer couldn't be submitted.
+#[derive(Debug)]
+pub enum SubmitError {
+    /// There's no session token to log in with.
+    NoSession,
+    /// The history rules the answer out.
+    Refused(Refusal),
+    /// The server answered with this HTTP status and body.
+    Status(u16, String),
+    /// The request didn't get an answer.
+    Transport(String),
+    /// Reading or writing the history failed.
+    Io(PathBuf, std::io::Error),
+    /// A line of the history, counting from 1, isn't a valid attempt.
+    Parse(PathBuf, usize, String),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::NoSession => write!(
+                f,
+                "no session token, pass --session or set {}",
+                fetch::SESSION_VAR
+            ),
+            SubmitError::Refused(refusal) => write!(f, "not submitting: {}", refusal),
+            SubmitError::Status(status, body) => {
+                write!(f, "server returned {}: {}", status, article_text(body))
+            }
+            SubmitError::Transport(err) => write!(f, "{}", err),
+            SubmitError::Io(path, err) => {
+                write!(f, "could not access history {}: {}", path.display(), err)
+            }
+            SubmitError::Parse(path, line, err) => {
+                write!(f, "{}:{}: {}", path.display(), line, err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+/// Where to submit answers and where to record them.
+pub struct Submitter {
+    /// The site to submit to.
+    pub url: String,
+    /// The session cookie to log in with.
+    pub session: Option<String>,
+    /// Where submissions are recorded.
+    pub history: PathBuf,
+}
+
+impl Default for Submitter {
+    fn default() -> Self {
+        Submitter {
+            url: std::env::var(fetch::URL_VAR).unwrap_or_else(|_| fetch::DEFAULT_URL.to_string()),
+            session: std::env::var(fetch::SESSION_VAR).ok(),
+            history: PathBuf::from(HISTORY_PATH),
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+impl Submitter {
+    /// The URL `puzzle`'s answers are posted to.
+    pub fn url(&self, puzzle: Puzzle) -> String {
+        format!(
+            "{}/{}/day/{}/answer",
+            self.url.trim_end_matches('/'),
+            puzzle.year,
+            puzzle.day
+        )
+    }
+
+    /// Submits `answer` unless the history shows it can't be right or the server asked us to
+    /// wait, and records the attempt whatever the reply.
+    pub fn submit(&self, puzzle: Puzzle, part: usize, answer: &str) -> Result<Reply, SubmitError> {
+        let mut history = History::load(&self.history)?;
+        history
+            .check(puzzle, part, answer, unix_now())
+            .map_err(SubmitError::Refused)?;
+
+        let session = self.session.as_ref().ok_or(SubmitError::NoSession)?;
+        let response = ureq::post(&self.url(puzzle))
+            .set("Cookie", &format!("session={}", session.trim()))
+            .set(
+                "User-Agent",
+                concat!(env!("CARGO_PKG_NAME"), " answer submitter"),
+            )
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|err| match err {
+                ureq::Error::Status(status, response) => {
+                    SubmitError::Status(status, response.into_string().unwrap_or_default())
+                }
+                ureq::Error::Transport(err) => SubmitError::Transport(err.to_string()),
+            })?;
+        let body = response
+            .into_string()
+            .map_err(|err| SubmitError::Transport(err.to_string()))?;
+
+        let reply = parse_reply(&body);
+        history.attempts.push(Attempt {
+            puzzle,
+            part,
+            time: unix_now(),
+            wait: reply.wait.as_secs(),
+            outcome: reply.outcome,
+            answer: answer.to_string(),
+        });
+        history.save(&self.history)?;
+        Ok(reply)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mock::serve_once;
+    use crate::solution::Puzzle;
+    use crate::submit::{parse_reply, Attempt, History, Outcome, Refusal, SubmitError, Submitter};
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_reply() {
+        let reply = parse_reply(
+            "<html><main><article><p>That's the right answer!  You are <em>one gold star</em> \
+             closer.</p></article></main></html>",
+        );
+        assert_eq!(reply.outcome, Outcome::Right);
+        assert_eq!(
+            reply.message,
+            "That's the right answer! You are one gold star closer."
+        );
+
+        let reply = parse_reply(
+            "<article><p>That's not the right answer; your answer is too low. Please wait one \
+             minute before trying again.</p></article>",
+        );
+        assert_eq!(
+            (reply.outcome, reply.wait),
+            (Outcome::TooLow, Duration::from_secs(60))
+        );
+
+        let reply = parse_reply(
+            "<article><p>That's not the right answer. Please wait 5 minutes before trying \
+             again.</p></article>",
+        );
+        assert_eq!(
+            (reply.outcome, reply.wait),
+            (Outcome::Wrong, Duration::from_secs(300))
+        );
+
+        let reply = parse_reply(
+            "<article><p>You gave an answer too recently; you have to wait after submitting an \
+             answer before trying again.  You have 1m 5s left to wait.</p></article>",
+        );
+        assert_eq!(
+            (reply.outcome, reply.wait),
+            (Outcome::RateLimited, Duration::from_secs(65))
+        );
+
+        for overflowing in [
+            "You have 99999999999999999999h left to wait.",
+            "You have 5124095576030432h left to wait.",
+            "You have 18446744073709551615s 1s left to wait.",
+            "Please wait 307445734561825861 minutes before trying again.",
+        ] {
+            let reply = parse_reply(&format!("<article><p>{}</p></article>", overflowing));
+            assert_eq!(reply.wait, Duration::default(), "{}", overflowing);
+        }
+
+        let reply = parse_reply("<article><p>You don't seem to be solving the right level.</p>");
+        assert_eq!(reply.outcome, Outcome::Solved);
+        assert_eq!(parse_reply("teapot").outcome, Outcome::Unknown);
+    }
+
+    fn attempt(day: usize, part: usize, outcome: Outcome, answer: &str) -> Attempt {
+        Attempt {
+            puzzle: Puzzle::new(2020, day),
+            part,
+            time: 1000,
+            wait: 60,
+            outcome,
+            answer: answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_history() {
+        let history = History {
+            attempts: vec![
+                attempt(1, 1, Outcome::TooHigh, "500"),
+                attempt(1, 1, Outcome::TooLow, "100"),
+                attempt(1, 1, Outcome::Wrong, "250"),
+                attempt(2, 1, Outcome::Right, "7"),
+                attempt(2, 2, Outcome::Wrong, "abc"),
+            ],
+        };
+        let parsed = History::parse(&history.to_string());
+        assert_eq!(parsed, Ok(history));
+        let history = parsed.expect("round trips");
+
+        assert_eq!(history.check(Puzzle::new(2020, 1), 1, "300", 2000), Ok(()));
+        assert_eq!(
+            history.check(Puzzle::new(2020, 1), 1, "250", 2000),
+            Err(Refusal::Rejected(Outcome::Wrong))
+        );
+        assert_eq!(
+            history.check(Puzzle::new(2020, 1), 1, "600", 2000),
+            Err(Refusal::Bound(Outcome::TooHigh, "500".to_string()))
+        );
+        assert_eq!(
+            history.check(Puzzle::new(2020, 1), 1, "50", 2000),
+            Err(Refusal::Bound(Outcome::TooLow, "100".to_string()))
+        );
+        assert_eq!(
+            history.check(Puzzle::new(2020, 2), 1, "8", 2000),
+            Err(Refusal::Solved("7".to_string()))
+        );
+        assert_eq!(
+            history.check(Puzzle::new(2020, 2), 2, "abc", 2000),
+            Err(Refusal::Rejected(Outcome::Wrong))
+        );
+        assert_eq!(history.check(Puzzle::new(2020, 2), 2, "abd", 2000), Ok(()));
+        assert_eq!(
+            history.check(Puzzle::new(2020, 3), 1, "1", 1030),
+            Err(Refusal::Wait(Duration::from_secs(30)))
+        );
+
+        let history = History {
+            attempts: vec![Attempt {
+                wait: u64::MAX,
+                ..attempt(1, 1, Outcome::Wrong, "5")
+            }],
+        };
+        assert_eq!(
+            history.check(Puzzle::new(2020, 1), 1, "6", 2000),
+            Err(Refusal::Wait(Duration::from_secs(u64::MAX - 2000)))
+        );
+
+        assert_eq!(
+            History::parse("2020\t1\t1\t0\t0\tmaybe\t5"),
+            Err((1, "invalid outcome maybe".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, server) = serve_once(
+            "200 OK",
+            "<article><p>That's not the right answer; your answer is too high.  Please wait \
+             one minute before trying again.</p></article>",
+        );
+        let history = std::env::temp_dir().join(format!("aoc-submit-{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&history);
+        let submitter = Submitter {
+            url,
+            session: Some("abc123".to_string()),
+            history: history.clone(),
+        };
+
+        let reply = submitter
+            .submit(Puzzle::new(2020, 1), 2, "1234")
+            .expect("submits");
+        assert_eq!(reply.outcome, Outcome::TooHigh);
+
+        let request = server.join().expect("server ran");
+        assert!(request.starts_with("POST /2020/day/1/answer HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=abc123\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=1234"));
+
+        let saved = History::load(&history).expect("history was saved");
+        assert_eq!(saved.attempts.len(), 1);
+        assert_eq!(
+            (saved.attempts[0].outcome, saved.attempts[0].wait),
+            (Outcome::TooHigh, 60)
+        );
+
+        // refused before anything is sent, as the server is gone
+        match submitter.submit(Puzzle::new(2020, 1), 2, "2000") {
+            Err(SubmitError::Refused(refusal @ Refusal::Bound(Outcome::TooHigh, _))) => {
+                assert_eq!(refusal.to_string(), "the answer must be below 1234")
+            }
+            other => panic!("expected a refusal, got {:?}", other),
+        }
+        std::fs::remove_file(&history).expect("can clean up");
+    }
+}
@@ -0,0 +1,143 @@
+//! Reruns a day whenever its input file changes, showing what changed since the last run.
+
+use crate::output::DayResult;
+use crate::solution::{Puzzle, Timings};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How often the input file is checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Modification time and length of a file, or `None` if it can't be read.
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = path.metadata().ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Polls a file for changes. There is no portable way to be told about them without pulling in
+/// a platform specific dependency, and a few checks a second is plenty for an input file.
+pub struct Watcher {
+    path: PathBuf,
+    last: Option<(SystemTime, u64)>,
+}
+
+impl Watcher {
+    /// Starts watching `path` from how it is now.
+    pub fn new(path: PathBuf) -> Watcher {
+        let last = stamp(&path);
+        Watcher { path, last }
+    }
+
+    /// The file being watched.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the file was modified, created or removed since the last check.
+    pub fn changed(&mut self) -> bool {
+        let now = stamp(&self.path);
+        now != std::mem::replace(&mut self.last, now)
+    }
+
+    /// Blocks until the file changes, checking every `interval`.
+    pub fn wait(&mut self, interval: Duration) {
+        while !self.changed() {
+            std::thread::sleep(interval);
+        }
+    }
+}
+
+/// What changed between two runs of the same puzzle. Answers are only given when they changed,
+/// as `(before, after)`, and timings are each run's fastest.
+#[derive(Debug, PartialEq)]
+pub struct Diff<'a> {
+    /// The puzzle run.
+    pub puzzle: Puzzle,
+    /// Part 1's answers, if they changed.
+    pub part1: Option<(&'a str, &'a str)>,
+    /// Part 2's answers, if they changed.
+    pub part2: Option<(&'a str, &'a str)>,
+    /// The fastest timings of the earlier run.
+    pub before: Timings,
+    /// The fastest timings of the later run.
+    pub after: Timings,
+}
+
+/// What changed from `before` to `after`.
+pub fn diff<'a>(before: &'a DayResult, after: &'a DayResult) -> Diff<'a> {
+    let answer = |a: &'a str, b: &'a str| match a == b {
+        true => None,
+        false => Some((a, b)),
+    };
+    Diff {
+        puzzle: after.puzzle,
+        part1: answer(&before.part1, &after.part1),
+        part2: answer(&before.part2, &after.part2),
+        before: before.min(),
+        after: after.min(),
+    }
+}
+
+/// Relative change from `before` to `after`, e.g. `-0.25` for 25% faster.
+pub fn change(before: Duration, after: Duration) -> f64 {
+    match before.as_secs_f64() {
+        secs if secs > 0.0 => after.as_secs_f64() / secs - 1.0,
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::DayResult;
+    use crate::solution::{Puzzle, Timings};
+    use crate::watch::{change, diff, Watcher};
+    use std::time::Duration;
+
+    fn result(part1: &str, part2: &str, micros: u64) -> DayResult {
+        DayResult {
+            puzzle: Puzzle::new(2020, 20),
+            title: "",
+            part1: part1.to_string(),
+            part2: part2.to_string(),
+            input: 0,
+            warmup: 0,
+            runs: vec![Timings {
+                parse: Duration::from_micros(micros),
+                ..Timings::default()
+            }],
+            memory: None,
+            elapsed: Duration::default(),
+        }
+    }
+
+    #[test]
+    fn test_watcher() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut watcher = Watcher::new(path.clone());
+        assert!(!watcher.changed());
+
+        std::fs::write(&path, "1\n2\n").expect("can write");
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        std::fs::write(&path, "1\n2\n3\n").expect("can write");
+        assert!(watcher.changed());
+
+        std::fs::remove_file(&path).expect("can clean up");
+        assert!(watcher.changed());
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = result("20899048083289", "273", 400);
+        let after = result("20899048083289", "274", 200);
+        let diff = diff(&before, &after);
+        assert_eq!(diff.part1, None);
+        assert_eq!(diff.part2, Some(("273", "274")));
+        assert_eq!(diff.after.parse, Duration::from_micros(200));
+
+        assert_eq!(change(diff.before.total(), diff.after.total()), -0.5);
+        assert_eq!(change(Duration::default(), Duration::from_secs(1)), 0.0);
+    }
+}
@@ -0,0 +1,12 @@
+//! Solutions grouped by year, one module per year.
+
+pub mod y2020;
+
+use crate::solution::Calendar;
+
+/// Every year's solutions.
+pub fn calendar() -> Calendar {
+    let mut calendar = Calendar::new();
+    calendar.add(y2020::registry());
+    calendar
+}
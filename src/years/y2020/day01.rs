@@ -0,0 +1,225 @@
+//! Day 1: [Report Repair](https://adventofcode.com/2020/day/1).
+
+use crate::crosscheck;
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/01.txt");
+const GOAL: usize = 2_020;
+
+/// Parses one expense entry per line.
+pub fn load_numbers(input: &str) -> Result<Vec<usize>> {
+    input.lines().map(|i| error::parse(input, i)).collect()
+}
+
+/// The product of the two entries that sum to 2020.
+pub fn part1(nums: &[usize]) -> Result<usize> {
+    let mut seen = [0usize; GOAL / 64 + 1];
+    for &num in nums.iter().filter(|&&num| num <= GOAL) {
+        let short = GOAL - num;
+        if seen[short / 64] & (1 << (short % 64)) != 0 {
+            return Ok(num * short);
+        }
+        seen[num / 64] |= 1 << (num % 64);
+    }
+    Err(Error::new("no two entries sum to 2020"))
+}
+
+/// The product of the three entries that sum to 2020. Each entry is checked against the pairs of
+/// entries before it, so none is ever used twice.
+pub fn part2(nums: &[usize]) -> Result<usize> {
+    let nums = nums
+        .iter()
+        .copied()
+        .filter(|&num| num <= GOAL)
+        .collect::<Vec<_>>();
+    let mut places = [None; GOAL + 1];
+    for (ind, &k) in nums.iter().enumerate() {
+        if let Some(&Some(ij)) = GOAL.checked_sub(k).and_then(|ij| places.get(ij)) {
+            return Ok(ij * k);
+        }
+        for &i in &nums[..ind] {
+            if i + k <= GOAL {
+                places[i + k] = Some(i * k);
+            }
+        }
+    }
+    Err(Error::new("no three entries sum to 2020"))
+}
+
+/// Part 1 the slow way, trying every pair of entries.
+pub fn reference_part1(nums: &[usize]) -> Result<usize> {
+    let mut products = Vec::new();
+    for (i, &a) in nums.iter().enumerate() {
+        for &b in &nums[i + 1..] {
+            if a.checked_add(b) == Some(GOAL) {
+                products.push(a * b);
+            }
+        }
+    }
+    crosscheck::only(products)
+}
+
+/// Part 2 the slow way, trying every triple of entries.
+pub fn reference_part2(nums: &[usize]) -> Result<usize> {
+    let mut products = Vec::new();
+    for (i, &a) in nums.iter().enumerate() {
+        for (j, &b) in nums.iter().enumerate().skip(i + 1) {
+            for &c in &nums[j + 1..] {
+                if a.checked_add(b).and_then(|ab| ab.checked_add(c)) == Some(GOAL) {
+                    products.push(a * b * c);
+                }
+            }
+        }
+    }
+    crosscheck::only(products)
+}
+
+/// How many pairs and triples of entries sum to 2020, counting each at most twice.
+fn sums(nums: &[usize]) -> (usize, usize) {
+    let small = nums.iter().filter(|&&n| n <= GOAL).collect::<Vec<_>>();
+    let mut pairs = 0;
+    let mut triples = 0;
+    for (i, &a) in small.iter().enumerate() {
+        for (j, &b) in small.iter().enumerate().skip(i + 1) {
+            pairs += (a + b == GOAL) as usize;
+            triples += small[j + 1..]
+                .iter()
+                .filter(|&&c| a + b + c == GOAL)
+                .count();
+        }
+        if pairs > 1 || triples > 1 {
+            break;
+        }
+    }
+    (pairs, triples)
+}
+
+/// `size` entries, with exactly one pair and one triple summing to 2020 among the first 200. The
+/// rest are above 2020, so they can never be part of an answer.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let small = size.clamp(5, 200);
+    let nums = loop {
+        let a = rng.range(1..=1009);
+        let x = rng.range(1..=672);
+        let y = rng.range(x + 1..=(GOAL - x) / 2 - 1);
+        let mut nums = vec![a, GOAL - a, x, y, GOAL - x - y];
+        let planted = nums.clone();
+        let completes = |n: usize| {
+            planted
+                .iter()
+                .enumerate()
+                .any(|(i, &p)| p + n == GOAL || planted[i + 1..].iter().any(|&q| p + q + n == GOAL))
+        };
+        while nums.len() < small {
+            let n = rng.range(GOAL / 2 + 1..=GOAL - 1);
+            if !completes(n) {
+                nums.push(n);
+            }
+        }
+        if sums(&nums) == (1, 1) {
+            break nums;
+        }
+    };
+    let mut nums = nums;
+    while nums.len() < size {
+        nums.push(rng.range(GOAL + 1..=999_999));
+    }
+    rng.shuffle(&mut nums);
+    nums.iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed<'a> = Vec<usize>;
+
+    fn day(&self) -> usize {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Report Repair"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>> {
+        load_numbers(input)
+    }
+
+    fn part1(&self, numbers: &Vec<usize>) -> Result<String> {
+        part1(numbers).map(|n| n.to_string())
+    }
+
+    fn part2(&self, numbers: &Vec<usize>) -> Result<String> {
+        part2(numbers).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        let numbers = load_numbers(input);
+        Some(
+            numbers
+                .and_then(|numbers| reference_part1(&numbers))
+                .map(|n| n.to_string()),
+        )
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        let numbers = load_numbers(input);
+        Some(
+            numbers
+                .and_then(|numbers| reference_part2(&numbers))
+                .map(|n| n.to_string()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_test() {
+        let numbers = load_numbers(INPUT).expect("valid input");
+        assert_eq!(part1(&numbers), Ok(319_531))
+    }
+
+    #[test]
+    fn part2_test() {
+        let numbers = load_numbers(INPUT).expect("valid input");
+        assert_eq!(part2(&numbers), Ok(244_300_320));
+        assert_eq!(part2(&[505, 1010, 1, 2, 2017]), Ok(4034));
+        assert_eq!(part2(&[2019, 0, 1]), Ok(0));
+        assert_eq!(part2(&[1010, 1010, 0]), Ok(0));
+    }
+
+    #[test]
+    fn errors_test() {
+        let err = load_numbers("1721\n979\n-366").expect_err("negative entry");
+        assert_eq!(err.position.map(|p| (p.line, p.column)), Some((3, 1)));
+        assert!(part1(&[1, 2, 3]).is_err());
+        assert!(part2(&[3000, 2, 1]).is_err());
+    }
+
+    #[test]
+    fn huge_entries_test() {
+        let nums = [usize::MAX, 2017, 1, 2, 2018];
+        assert_eq!(part1(&nums), Ok(4036));
+        assert_eq!(reference_part1(&nums), Ok(4036));
+        assert_eq!(part2(&nums), Ok(4034));
+        assert_eq!(reference_part2(&nums), Ok(4034));
+    }
+}
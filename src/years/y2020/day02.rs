@@ -0,0 +1,220 @@
+//! Day 2: [Password Philosophy](https://adventofcode.com/2020/day/2).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use core::str::Split;
+use std::convert::TryFrom;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/02.txt");
+
+struct Requirement {
+    char: char,
+    min: usize,
+    max: usize,
+}
+
+/// A password and the policy it was created under, e.g. `1-3 a: abcde`.
+pub struct Entry<'a> {
+    req: Requirement,
+    password: &'a [u8],
+}
+
+fn get_next_int(source: &mut Split<char>) -> Result<usize, String> {
+    match source.next() {
+        Some(v) => v,
+        None => return Err("not enough values found".to_string()),
+    }
+    .parse::<usize>()
+    .map_err(|err| err.to_string())
+}
+
+impl<'a> TryFrom<&'a str> for Entry<'a> {
+    type Error = String;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        // letters are compared and positions counted a byte at a time
+        if !s.is_ascii() {
+            return Err("entries must be ASCII".to_string());
+        }
+        let mut parts = s.split(' ');
+
+        let limits = parts.next().ok_or("pls be a thing")?;
+        let mut limits = limits.split('-');
+
+        let min = get_next_int(&mut limits)?;
+        let max = get_next_int(&mut limits)?;
+        if min == 0 || min > max {
+            return Err(format!("invalid limits {}-{}", min, max));
+        }
+
+        let char = parts
+            .next()
+            .ok_or("pls be a thing")?
+            .chars()
+            .next()
+            .ok_or_else(|| "char mustn't be 0 length".to_string())?;
+        let password = parts.next().ok_or("pls be a thing")?.as_ref();
+
+        Ok(Entry {
+            req: Requirement { char, min, max },
+            password,
+        })
+    }
+}
+
+impl Entry<'_> {
+    /// Whether the password has between `min` and `max` of the policy's letter.
+    pub fn valid(&self) -> bool {
+        let seen = bytecount::count(self.password, self.req.char as u8);
+        seen >= self.req.min && seen <= self.req.max
+    }
+
+    /// Whether exactly one of the (1-based) positions `min` and `max` holds the policy's letter.
+    pub fn alt_valid(&self) -> bool {
+        match self.password.get(self.req.min - 1) {
+            Some(&first) => match self.password.get(self.req.max - 1) {
+                Some(&second) => (first == self.req.char as u8) ^ (second == self.req.char as u8),
+                None => first == self.req.char as u8,
+            },
+            None => false,
+        }
+    }
+}
+
+/// Parses one [`Entry`] per line.
+pub fn load_entries(input: &str) -> Result<Vec<Entry<'_>>> {
+    input
+        .lines()
+        .map(|line| Entry::try_from(line).map_err(|err| Error::at(input, line, err)))
+        .collect()
+}
+
+/// How many passwords are [`Entry::valid`].
+pub fn part1(entries: &[Entry]) -> usize {
+    entries.iter().filter(|entry| entry.valid()).count()
+}
+
+/// How many passwords are [`Entry::alt_valid`].
+pub fn part2(entries: &[Entry]) -> usize {
+    entries.iter().filter(|entry| entry.alt_valid()).count()
+}
+
+/// Part 1 the slow way, counting the letter a character at a time.
+pub fn reference_part1(entries: &[Entry]) -> usize {
+    entries
+        .iter()
+        .filter(|entry| {
+            let password = String::from_utf8_lossy(entry.password);
+            let seen = password.chars().filter(|&c| c == entry.req.char).count();
+            (entry.req.min..=entry.req.max).contains(&seen)
+        })
+        .count()
+}
+
+/// Part 2 the slow way, looking the letter up a character at a time.
+pub fn reference_part2(entries: &[Entry]) -> usize {
+    entries
+        .iter()
+        .filter(|entry| {
+            let password = String::from_utf8_lossy(entry.password);
+            let at = |position: usize| password.chars().nth(position - 1) == Some(entry.req.char);
+            at(entry.req.min) != at(entry.req.max)
+        })
+        .count()
+}
+
+/// `size` policies and passwords, mostly drawn from a few letters so that policies often match.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let letters = &b"abcdefghijklmnopqrstuvwxyz"[..rng.range(3..=26)];
+            let char = *rng.pick(letters) as char;
+            let min = rng.range(1..=10);
+            let max = rng.range(min..=min + 10);
+            let password = (0..rng.range(1..=20))
+                .map(|_| *rng.pick(letters) as char)
+                .collect::<String>();
+            format!("{}-{} {}: {}", min, max, char, password)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed<'a> = Vec<Entry<'a>>;
+
+    fn day(&self) -> usize {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Password Philosophy"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<Entry<'a>>> {
+        load_entries(input)
+    }
+
+    fn part1(&self, entries: &Vec<Entry<'_>>) -> Result<String> {
+        Ok(part1(entries).to_string())
+    }
+
+    fn part2(&self, entries: &Vec<Entry<'_>>) -> Result<String> {
+        Ok(part2(entries).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(load_entries(input).map(|entries| reference_part1(&entries).to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(load_entries(input).map(|entries| reference_part2(&entries).to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parts_test() {
+        let entries = load_entries(INPUT).expect("valid input");
+        assert_eq!(part1(&entries), 483);
+        assert_eq!(part2(&entries), 482);
+    }
+
+    #[test]
+    fn part2_tests() {
+        let entry = Entry::try_from("1-3 a: abcde").expect("should be valid entry");
+        assert!(entry.alt_valid());
+
+        let entry = Entry::try_from("1-3 a: zbade").expect("should be valid entry");
+        assert!(entry.alt_valid());
+
+        let entry = Entry::try_from("1-3 b: cdefg").expect("should be valid entry");
+        assert!(!entry.alt_valid());
+
+        let entry = Entry::try_from("2-9 c: ccccccccc").expect("should be valid entry");
+        assert!(!entry.alt_valid());
+
+        assert!(Entry::try_from("0-3 a: abc").is_err());
+        assert!(Entry::try_from("1-2 a: éa").is_err());
+        let err = load_entries("1-3 a: abcde\n1-x b: cdefg")
+            .map(|_| ())
+            .expect_err("invalid limit");
+        assert_eq!(err.position.map(|p| p.line), Some(2));
+    }
+}
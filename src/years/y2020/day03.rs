@@ -0,0 +1,173 @@
+//! Day 3: [Toboggan Trajectory](https://adventofcode.com/2020/day/3).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/03.txt");
+
+/// The tree map, one bit per square, which repeats to the right.
+pub struct Trees {
+    width: usize,
+    rows: Vec<usize>,
+}
+
+/// Parses a map of `#` trees and `.` open squares.
+pub fn load_trees(input: &str) -> Result<Trees> {
+    let width = input.lines().next().map_or(0, str::len);
+    if width == 0 || width > usize::BITS as usize {
+        return Err(Error::new(format!(
+            "rows must be between 1 and {} wide",
+            usize::BITS
+        )));
+    }
+
+    let rows = input
+        .lines()
+        .map(|line| {
+            if line.len() != width {
+                return Err(Error::at(input, line, format!("row is not {} wide", width)));
+            }
+            line.char_indices().try_fold(0, |acc, (i, c)| match c {
+                '#' => Ok(acc | (1 << i)),
+                '.' => Ok(acc),
+                _ => Err(Error::at(input, &line[i..], format!("unexpected {:?}", c))),
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok(Trees { width, rows })
+}
+
+/// Part 2's slopes, as squares right and down per step.
+const SLOPES: [(usize, usize); 5] = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+
+/// How many trees are hit going `right` and `down` each step from the top left.
+///
+/// Panics if `down` is zero.
+pub fn part1(trees: &Trees, right: usize, down: usize) -> usize {
+    (0..trees.rows.len())
+        .step_by(down)
+        .zip((0..).map(|x| (x * right) % trees.width))
+        .filter(|&(y, x)| (trees.rows[y] & (1 << x)) != 0)
+        .count()
+}
+
+/// The product of the trees hit on each of the five slopes.
+pub fn part2(trees: &Trees) -> Result<usize> {
+    slope_product(
+        SLOPES
+            .iter()
+            .map(|&(right, down)| part1(trees, right, down)),
+    )
+}
+
+/// Multiplies the trees hit on each slope, failing if the product doesn't fit.
+fn slope_product(mut hits: impl Iterator<Item = usize>) -> Result<usize> {
+    hits.try_fold(1usize, |product, hit| product.checked_mul(hit))
+        .ok_or_else(|| Error::new("the trees hit on each slope multiply to more than 64 bits"))
+}
+
+/// Part 1 the slow way, stepping down the map until it runs out.
+pub fn reference_part1(trees: &Trees, right: usize, down: usize) -> usize {
+    let (mut x, mut y, mut hit) = (0, 0, 0);
+    while y < trees.rows.len() {
+        hit += (trees.rows[y] >> (x % trees.width)) & 1;
+        x += right;
+        y += down;
+    }
+    hit
+}
+
+/// Part 2 the slow way, using [`reference_part1`] for each slope.
+pub fn reference_part2(trees: &Trees) -> Result<usize> {
+    slope_product(
+        SLOPES
+            .iter()
+            .map(|&(right, down)| reference_part1(trees, right, down)),
+    )
+}
+
+/// A map 31 squares wide and `size` rows tall, about a fifth trees.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            (0..31)
+                .map(|_| if rng.chance(0.2) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed<'a> = Trees;
+
+    fn day(&self) -> usize {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Toboggan Trajectory"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Trees> {
+        load_trees(input)
+    }
+
+    fn part1(&self, trees: &Trees) -> Result<String> {
+        Ok(part1(trees, 3, 1).to_string())
+    }
+
+    fn part2(&self, trees: &Trees) -> Result<String> {
+        part2(trees).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(load_trees(input).map(|trees| reference_part1(&trees, 3, 1).to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        let product = load_trees(input).and_then(|trees| reference_part2(&trees));
+        Some(product.map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_test() {
+        let trees = load_trees(INPUT).expect("valid input");
+        assert_eq!(part1(&trees, 3, 1), 205);
+
+        let trees = load_trees("#\n.\n#").expect("valid map");
+        assert_eq!(part1(&trees, 1, 2), 2);
+    }
+
+    #[test]
+    fn part2_test() {
+        let trees = load_trees(INPUT).expect("valid input");
+        assert_eq!(part2(&trees), Ok(3_952_146_825));
+    }
+
+    #[test]
+    fn tall_map_test() {
+        let map = vec!["#"; 10_000].join("\n");
+        let trees = load_trees(&map).expect("valid map");
+        assert!(part2(&trees).is_err());
+        assert!(reference_part2(&trees).is_err());
+    }
+}
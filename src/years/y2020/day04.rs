@@ -0,0 +1,445 @@
+//! Day 4: [Passport Processing](https://adventofcode.com/2020/day/4).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/04.txt");
+
+/// The fields of a passport, `cid` aside, as they were written.
+#[derive(Default, Debug, Clone)]
+pub struct Record<'a> {
+    birth_year: Option<&'a str>,
+    issue_year: Option<&'a str>,
+    expiration_year: Option<&'a str>,
+    height: Option<&'a str>,
+    hair_colour: Option<&'a str>,
+    eye_colour: Option<&'a str>,
+    passport_id: Option<&'a str>,
+}
+
+/// Whether `number` is all digits and between `min` and `max`. Plain `parse` would also take a
+/// leading `+`.
+fn number_in_range(number: &str, min: usize, max: usize) -> bool {
+    if !number.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    match number.parse::<usize>() {
+        Ok(number) => number >= min && number <= max,
+        Err(_) => false,
+    }
+}
+
+fn year_in_range(year: &str, min_year: usize, max_year: usize) -> bool {
+    year.len() == 4 && number_in_range(year, min_year, max_year)
+}
+
+impl Record<'_> {
+    /// Whether every required field is present.
+    pub fn has_fields(&self) -> bool {
+        self.birth_year.is_some()
+            && self.issue_year.is_some()
+            && self.expiration_year.is_some()
+            && self.height.is_some()
+            && self.hair_colour.is_some()
+            && self.eye_colour.is_some()
+            && self.passport_id.is_some()
+    }
+
+    /// Whether every field that is present has a valid value.
+    pub fn valid_fields(&self) -> bool {
+        self.valid_birth_year()
+            && self.valid_issue_year()
+            && self.valid_expiration_year()
+            && self.valid_height()
+            && self.valid_hair_colour()
+            && self.valid_eye_colour()
+            && self.valid_passport_id()
+    }
+
+    fn valid_birth_year(&self) -> bool {
+        match &self.birth_year {
+            Some(year) => year_in_range(year, 1920, 2002),
+            None => false,
+        }
+    }
+
+    fn valid_issue_year(&self) -> bool {
+        match &self.issue_year {
+            Some(year) => year_in_range(year, 2010, 2020),
+            None => false,
+        }
+    }
+
+    fn valid_expiration_year(&self) -> bool {
+        match &self.expiration_year {
+            Some(year) => year_in_range(year, 2020, 2030),
+            None => false,
+        }
+    }
+
+    fn valid_height(&self) -> bool {
+        match &self.height {
+            Some(height) => match (height.strip_suffix("cm"), height.strip_suffix("in")) {
+                (Some(cm), _) => number_in_range(cm, 150, 193),
+                (_, Some(inches)) => number_in_range(inches, 59, 76),
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    fn valid_hair_colour(&self) -> bool {
+        let colour = match &self.hair_colour {
+            Some(colour) => colour,
+            None => return false,
+        };
+
+        match colour.strip_prefix('#') {
+            Some(hex) => {
+                hex.len() == 6
+                    && hex
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+            }
+            None => false,
+        }
+    }
+
+    fn valid_eye_colour(&self) -> bool {
+        match &self.eye_colour {
+            Some(colour) => matches!(
+                colour,
+                &"amb" | &"blu" | &"brn" | &"gry" | &"grn" | &"hzl" | &"oth"
+            ),
+            None => false,
+        }
+    }
+
+    fn valid_passport_id(&self) -> bool {
+        match &self.passport_id {
+            Some(id) => id.len() == 9 && id.chars().all(|c| c.is_ascii_digit()),
+            None => false,
+        }
+    }
+}
+
+/// Parses passports separated by blank lines, each a list of `key:value` fields.
+pub fn load_records(input: &str) -> Result<Vec<Record<'_>>> {
+    input
+        .split("\n\n")
+        .map(|record| {
+            let mut curr = Record::default();
+            for line in record.lines() {
+                for characteristic in line.split(' ') {
+                    let (key, status) = characteristic.split_once(':').ok_or_else(|| {
+                        Error::at(input, characteristic, "expected key:value field")
+                    })?;
+                    let status = Some(status);
+                    match key {
+                        "byr" => curr.birth_year = status,
+                        "iyr" => curr.issue_year = status,
+                        "eyr" => curr.expiration_year = status,
+                        "hgt" => curr.height = status,
+                        "hcl" => curr.hair_colour = status,
+                        "ecl" => curr.eye_colour = status,
+                        "pid" => curr.passport_id = status,
+                        "cid" => (),
+                        _ => return Err(Error::at(input, key, format!("unknown field {:?}", key))),
+                    }
+                }
+            }
+            Ok(curr)
+        })
+        .collect()
+}
+
+/// How many passports have every required field.
+pub fn part1(records: &[Record]) -> usize {
+    records.iter().filter(|record| record.has_fields()).count()
+}
+
+/// How many passports have every required field, all with valid values.
+pub fn part2(records: &[Record]) -> usize {
+    records
+        .iter()
+        .filter(|record| record.has_fields() && record.valid_fields())
+        .count()
+}
+
+/// Part 1 the slow way, the same as [`part1`] as there's no trick to it.
+pub fn reference_part1(records: &[Record]) -> usize {
+    records
+        .iter()
+        .filter(|record| fields(record).iter().all(Option::is_some))
+        .count()
+}
+
+/// Part 2 the slow way, checking each field against the rules as the puzzle words them.
+pub fn reference_part2(records: &[Record]) -> usize {
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let between = |s: &str, min: usize, max: usize| {
+        digits(s) && s.parse().is_ok_and(|n: usize| (min..=max).contains(&n))
+    };
+    let year = |s: &str, min, max| s.len() == 4 && between(s, min, max);
+    records
+        .iter()
+        .filter(|record| match fields(record) {
+            [Some(byr), Some(iyr), Some(eyr), Some(hgt), Some(hcl), Some(ecl), Some(pid)] => {
+                year(byr, 1920, 2002)
+                    && year(iyr, 2010, 2020)
+                    && year(eyr, 2020, 2030)
+                    && match (hgt.strip_suffix("cm"), hgt.strip_suffix("in")) {
+                        (Some(cm), _) => between(cm, 150, 193),
+                        (_, Some(inches)) => between(inches, 59, 76),
+                        _ => false,
+                    }
+                    && hcl.len() == 7
+                    && hcl.starts_with('#')
+                    && hcl[1..].bytes().all(|b| b"0123456789abcdef".contains(&b))
+                    && ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&ecl)
+                    && pid.len() == 9
+                    && digits(pid)
+            }
+            _ => false,
+        })
+        .count()
+}
+
+fn fields<'a>(record: &Record<'a>) -> [Option<&'a str>; 7] {
+    [
+        record.birth_year,
+        record.issue_year,
+        record.expiration_year,
+        record.height,
+        record.hair_colour,
+        record.eye_colour,
+        record.passport_id,
+    ]
+}
+
+/// A value for the field `key`, valid most of the time.
+fn field_value(rng: &mut Rng, key: &str) -> String {
+    let valid = rng.chance(0.8);
+    let year = |rng: &mut Rng, min: usize, max: usize| match valid {
+        true => rng.range(min..=max).to_string(),
+        false => rng.range(1900..=2040).to_string(),
+    };
+    let hex = |rng: &mut Rng, len| {
+        (0..len)
+            .map(|_| *rng.pick(b"0123456789abcdef") as char)
+            .collect::<String>()
+    };
+    match key {
+        "byr" => year(rng, 1920, 2002),
+        "iyr" => year(rng, 2010, 2020),
+        "eyr" => year(rng, 2020, 2030),
+        "hgt" => match (valid, rng.chance(0.5)) {
+            (true, true) => format!("{}cm", rng.range(150..=193)),
+            (true, false) => format!("{}in", rng.range(59..=76)),
+            (false, _) => format!("{}{}", rng.range(40..=200), rng.pick(&["cm", "in", ""])),
+        },
+        "hcl" => match valid {
+            true => format!("#{}", hex(rng, 6)),
+            false => {
+                let len = rng.range(3..=8);
+                format!("{}{}", rng.pick(&["#", ""]), hex(rng, len))
+            }
+        },
+        "ecl" => match valid {
+            true => rng
+                .pick(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"])
+                .to_string(),
+            false => rng.word(2..=4),
+        },
+        "pid" => {
+            let len = if valid { 9 } else { rng.range(5..=11) };
+            (0..len)
+                .map(|_| (b'0' + rng.below(10) as u8) as char)
+                .collect()
+        }
+        _ => rng.range(50..=350).to_string(),
+    }
+}
+
+/// `size` passports, some missing fields and some with invalid values.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let keys = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+    (0..size.max(1))
+        .map(|_| {
+            let complete = rng.chance(0.7);
+            let mut fields = Vec::new();
+            for key in keys.iter() {
+                if complete || rng.chance(0.7) {
+                    fields.push(format!("{}:{}", key, field_value(rng, key)));
+                }
+            }
+            if fields.is_empty() {
+                fields.push(format!("cid:{}", field_value(rng, "cid")));
+            }
+            rng.shuffle(&mut fields);
+            let mut record = String::new();
+            for (i, field) in fields.iter().enumerate() {
+                if i > 0 {
+                    record.push(if rng.chance(0.3) { '\n' } else { ' ' });
+                }
+                record.push_str(field);
+            }
+            record
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed<'a> = Vec<Record<'a>>;
+
+    fn day(&self) -> usize {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Passport Processing"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<Record<'a>>> {
+        load_records(input)
+    }
+
+    fn part1(&self, records: &Vec<Record<'_>>) -> Result<String> {
+        Ok(part1(records).to_string())
+    }
+
+    fn part2(&self, records: &Vec<Record<'_>>) -> Result<String> {
+        Ok(part2(records).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(load_records(input).map(|records| reference_part1(&records).to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(load_records(input).map(|records| reference_part2(&records).to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Each field with a pattern its valid values match.
+    const FIELDS: [(&str, &str); 8] = [
+        ("byr", "19[2-9][0-9]|200[0-2]"),
+        ("iyr", "201[0-9]|2020"),
+        ("eyr", "202[0-9]|2030"),
+        ("hgt", "1[5-8][0-9]cm|19[0-3]cm|59in|6[0-9]in|7[0-6]in"),
+        ("hcl", "#[0-9a-f]{6}"),
+        ("ecl", "amb|blu|brn|gry|grn|hzl|oth"),
+        ("pid", "[0-9]{9}"),
+        ("cid", "[0-9]{2,3}"),
+    ];
+
+    fn passport() -> impl Strategy<Value = String> {
+        let fields = FIELDS
+            .iter()
+            .map(|&(key, valid)| {
+                let value = prop_oneof![3 => valid, 1 => "[#+0-9a-z:]{0,10}"];
+                (prop::bool::weighted(0.8), value)
+                    .prop_map(move |(present, value)| (present, format!("{}:{}", key, value)))
+            })
+            .collect::<Vec<_>>();
+        let fields = fields
+            .prop_map(|fields| {
+                let present = fields.into_iter().filter(|&(present, _)| present);
+                present.map(|(_, field)| field).collect::<Vec<_>>()
+            })
+            .prop_shuffle();
+        (fields, any::<u64>()).prop_map(|(fields, breaks)| {
+            let mut passport = String::new();
+            for (i, field) in fields.iter().enumerate() {
+                if i > 0 {
+                    passport.push(if breaks >> i & 1 == 1 { '\n' } else { ' ' });
+                }
+                passport.push_str(field);
+            }
+            passport
+        })
+    }
+
+    #[test]
+    fn parts_test() {
+        let records = load_records(INPUT).expect("valid input");
+        assert_eq!(part1(&records), 228);
+        assert_eq!(part2(&records), 175);
+    }
+
+    #[test]
+    fn part2_tests() {
+        let record = Record {
+            passport_id: Some("087499704"),
+            height: Some("74in"),
+            eye_colour: Some("grn"),
+            issue_year: Some("2012"),
+            expiration_year: Some("2030"),
+            birth_year: Some("1980"),
+            hair_colour: Some("#623a2f"),
+        };
+        assert!(record.valid_fields());
+
+        for year in ["+1980", "01980", "198"].iter() {
+            let record = Record {
+                birth_year: Some(year),
+                ..record.clone()
+            };
+            assert!(!record.valid_fields(), "{}", year);
+        }
+
+        for height in ["cm", "in", "é", "1é", "190", "190é", "+74in"].iter() {
+            let record = Record {
+                height: Some(height),
+                ..record.clone()
+            };
+            assert!(!record.valid_fields(), "{}", height);
+            assert_eq!(reference_part2(&[record]), 0, "{}", height);
+        }
+    }
+
+    #[test]
+    fn errors_test() {
+        let err = load_records("byr:1980 iyr:2012\n\nhgt:74in xyz:1").expect_err("unknown field");
+        assert_eq!(err.to_string(), "line 3, column 10: unknown field \"xyz\"");
+        assert!(load_records("byr1980").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parts_match_reference(passports in prop::collection::vec(passport(), 1..20)) {
+            let input = passports.join("\n\n");
+            let records = load_records(&input).expect("valid input");
+            prop_assert_eq!(records.len(), passports.len());
+            prop_assert_eq!(part1(&records), reference_part1(&records));
+            prop_assert_eq!(part2(&records), reference_part2(&records));
+            prop_assert!(part2(&records) <= part1(&records));
+        }
+
+        #[test]
+        fn test_parse_never_panics(input in "([a-z:#0-9]{0,8}[ \n]{0,2})*") {
+            if let Ok(records) = load_records(&input) {
+                prop_assert!(part2(&records) <= part1(&records));
+            }
+        }
+    }
+}
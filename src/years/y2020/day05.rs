@@ -0,0 +1,264 @@
+//! Day 5: [Binary Boarding](https://adventofcode.com/2020/day/5).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/05.txt");
+
+/// Parses boarding passes such as `FBFBBFFRLR` into seat IDs.
+pub fn load_seats(input: &str) -> Result<Vec<usize>> {
+    let seats = input
+        .lines()
+        .map(|line| {
+            if line.len() != 10 {
+                return Err(Error::at(input, line, "seats must be 10 characters long"));
+            }
+            line.char_indices().try_fold(0, |acc, (i, v)| {
+                let bit = match v {
+                    'R' | 'B' => 1,
+                    'L' | 'F' => 0,
+                    _ => return Err(Error::at(input, &line[i..], format!("unexpected {:?}", v))),
+                };
+                Ok((acc << 1) + bit)
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    match seats.is_empty() {
+        true => Err(Error::new("no seats found")),
+        false => Ok(seats),
+    }
+}
+
+/// The highest seat ID.
+pub fn part1(seats: &[usize]) -> usize {
+    seats.iter().copied().max().unwrap_or(0)
+}
+
+/// The one seat ID missing from the middle of `seats`, which must not be empty.
+pub fn part2(seats: &[usize]) -> usize {
+    let mut p2 = 0;
+    let mut smallest = usize::MAX;
+    let mut largest = usize::MIN;
+
+    seats.iter().for_each(|&i| {
+        p2 ^= i;
+        smallest = usize::min(smallest, i);
+        largest = usize::max(largest, i);
+    });
+
+    // the IDs below any power of two from 4 up XOR to 0, so XORing the IDs around the seats
+    // gives the same as XORing the ones among them
+    let top = (largest + 1).next_power_of_two().max(4);
+    let dist = largest - smallest;
+    let poss_dist = top - largest;
+
+    if dist > poss_dist {
+        (1..smallest).for_each(|i| p2 ^= i);
+        ((largest + 1)..top).for_each(|i| p2 ^= i);
+    } else {
+        (smallest..=largest).for_each(|i| p2 ^= i);
+    }
+
+    p2
+}
+
+/// Seat IDs the slow way, halving the rows and columns left a letter at a time as the puzzle
+/// describes.
+fn reference_seats(input: &str) -> Result<Vec<usize>> {
+    input
+        .lines()
+        .map(|line| {
+            if line.len() != 10 || !line.is_ascii() {
+                return Err(Error::new("seats must be 10 letters long"));
+            }
+            let (row, column) = line.split_at(7);
+            let (mut rows, mut columns) = (0..128, 0..8);
+            for c in row.chars() {
+                match c {
+                    'F' => rows.end -= rows.len() / 2,
+                    'B' => rows.start += rows.len() / 2,
+                    _ => return Err(Error::new(format!("unexpected {:?} in row", c))),
+                }
+            }
+            for c in column.chars() {
+                match c {
+                    'L' => columns.end -= columns.len() / 2,
+                    'R' => columns.start += columns.len() / 2,
+                    _ => return Err(Error::new(format!("unexpected {:?} in column", c))),
+                }
+            }
+            Ok(rows.start * 8 + columns.start)
+        })
+        .collect()
+}
+
+/// Part 1 the slow way, sorting the seats and taking the last.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    let mut seats = reference_seats(input)?;
+    seats.sort_unstable();
+    seats
+        .last()
+        .copied()
+        .ok_or_else(|| Error::new("no seats found"))
+}
+
+/// Part 2 the slow way, sorting the seats and looking for the one gap between them.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    let mut seats = reference_seats(input)?;
+    seats.sort_unstable();
+    let gaps = seats
+        .windows(2)
+        .filter(|pair| pair[1] != pair[0] + 1)
+        .collect::<Vec<_>>();
+    match gaps.as_slice() {
+        [pair] if pair[1] == pair[0] + 2 => Ok(pair[0] + 1),
+        _ => Err(Error::new("seats are not a run with one missing")),
+    }
+}
+
+/// The boarding pass for a seat ID below 1024.
+pub fn boarding_pass(seat: usize) -> String {
+    (0..10)
+        .map(|i| {
+            let bit = seat >> (9 - i) & 1 == 1;
+            match (i < 7, bit) {
+                (true, true) => 'B',
+                (true, false) => 'F',
+                (false, true) => 'R',
+                (false, false) => 'L',
+            }
+        })
+        .collect()
+}
+
+/// Most seats in a generated run, leaving one seat to go missing.
+const MAX_SEATS: usize = 1023;
+
+/// A full run of seats from somewhere in the plane with one missing from the middle, in a random
+/// order. There are only 1024 seats, so `size` is capped at 1023.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.clamp(3, MAX_SEATS);
+    let first = rng.range(0..=1023 - count);
+    let missing = rng.range(first + 1..=first + count - 2);
+    let mut seats = (first..=first + count)
+        .filter(|&seat| seat != missing)
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut seats);
+    seats
+        .iter()
+        .map(|&seat| boarding_pass(seat))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed<'a> = Vec<usize>;
+
+    fn day(&self) -> usize {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Binary Boarding"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>> {
+        load_seats(input)
+    }
+
+    fn part1(&self, seats: &Vec<usize>) -> Result<String> {
+        Ok(part1(seats).to_string())
+    }
+
+    fn part2(&self, seats: &Vec<usize>) -> Result<String> {
+        Ok(part2(seats).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn max_size(&self) -> Option<usize> {
+        Some(MAX_SEATS)
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|seat| seat.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|seat| seat.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day05::{boarding_pass, load_seats, part1, part2, INPUT};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_actual() {
+        let seats = load_seats(INPUT).expect("valid input");
+        assert_eq!(part1(&seats), 885);
+        assert_eq!(part2(&seats), 623);
+    }
+
+    #[test]
+    fn to_id() {
+        let acc = |acc, v| {
+            (acc << 1)
+                + match v {
+                    'R' | 'B' => 1,
+                    _ => 0,
+                }
+        };
+
+        let line = "BFFFBBFRRR";
+        let id = line.chars().fold(0, acc);
+        assert_eq!(id, 567);
+
+        let line = "FFFBBBFRRR";
+        let id = line.chars().fold(0, acc);
+        assert_eq!(id, 119);
+
+        let line = "BBFFBBFRLL";
+        let id = line.chars().fold(0, acc);
+        assert_eq!(id, 820);
+    }
+
+    proptest! {
+        #[test]
+        fn test_passes_decode_to_distinct_seats(pass in "[FB]{7}[LR]{3}") {
+            let seats = load_seats(&pass).expect("valid pass");
+            prop_assert!(seats[0] < 1024);
+            prop_assert_eq!(boarding_pass(seats[0]), pass);
+        }
+
+        #[test]
+        fn test_every_seat_has_a_pass(seat in 0usize..1024) {
+            prop_assert_eq!(load_seats(&boarding_pass(seat)), Ok(vec![seat]));
+        }
+
+        #[test]
+        fn test_finds_missing_seat(
+            (first, last, missing) in (0usize..1022)
+                .prop_flat_map(|first| (Just(first), first + 2..1024))
+                .prop_flat_map(|(first, last)| (Just(first), Just(last), first + 1..last)),
+            order in any::<u64>(),
+        ) {
+            let mut seats = (first..=last).filter(|&s| s != missing).collect::<Vec<_>>();
+            let len = seats.len();
+            seats.rotate_left(order as usize % len);
+            prop_assert_eq!(part2(&seats), missing);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Day 6: [Custom Customs](https://adventofcode.com/2020/day/6).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::collections::BTreeSet;
+use std::ops::{BitAnd, BitOr};
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/06.txt");
+
+/// Parses groups separated by blank lines into the questions anyone and everyone in each
+/// answered yes to, as bitsets with bit 0 for `a`.
+pub fn load_groups(input: &str) -> Result<Vec<(usize, usize)>> {
+    input
+        .split("\n\n")
+        .map(|group| {
+            let mut answers = group.lines().map(|line| {
+                line.char_indices().try_fold(0usize, |acc, (i, c)| match c {
+                    'a'..='z' => Ok(acc.bitor(1 << (c as usize - 'a' as usize))),
+                    _ => Err(Error::at(input, &line[i..], format!("unexpected {:?}", c))),
+                })
+            });
+            let first = answers
+                .next()
+                .ok_or_else(|| Error::at(input, group, "empty group"))??;
+            answers.try_fold((first, first), |(a, b), v| {
+                v.map(|v| (a.bitor(v), b.bitand(v)))
+            })
+        })
+        .collect()
+}
+
+/// The sum over groups of questions anyone answered yes to.
+pub fn part1(groups: &[(usize, usize)]) -> usize {
+    groups
+        .iter()
+        .map(|(anyone, _)| anyone.count_ones() as usize)
+        .sum()
+}
+
+/// The sum over groups of questions everyone answered yes to.
+pub fn part2(groups: &[(usize, usize)]) -> usize {
+    groups
+        .iter()
+        .map(|(_, everyone)| everyone.count_ones() as usize)
+        .sum()
+}
+
+/// Each group's people's answers as sets of letters, parsed the slow way.
+fn reference_groups(input: &str) -> Result<Vec<Vec<BTreeSet<char>>>> {
+    input
+        .split("\n\n")
+        .map(|group| {
+            let people = group
+                .lines()
+                .map(|line| line.chars().collect::<BTreeSet<_>>())
+                .collect::<Vec<_>>();
+            let letters = people.iter().flatten().all(|c| c.is_ascii_lowercase());
+            match letters && !people.is_empty() {
+                true => Ok(people),
+                false => Err(Error::new("groups must be lines of lowercase letters")),
+            }
+        })
+        .collect()
+}
+
+/// Part 1 the slow way, taking the union of each group's answers.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    let groups = reference_groups(input)?;
+    Ok(groups
+        .iter()
+        .map(|people| people.iter().flatten().collect::<BTreeSet<_>>().len())
+        .sum())
+}
+
+/// Part 2 the slow way, counting the letters every person in a group has.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    let groups = reference_groups(input)?;
+    Ok(groups
+        .iter()
+        .map(|people| {
+            ('a'..='z')
+                .filter(|c| people.iter().all(|person| person.contains(c)))
+                .count()
+        })
+        .sum())
+}
+
+/// `size` groups of one to five people, each answering yes to a handful of questions.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let common = rng.word(0..=4);
+            (0..rng.range(1..=5))
+                .map(|_| {
+                    let mut answers = (common.clone() + &rng.word(0..=8)).into_bytes();
+                    if answers.is_empty() {
+                        answers.push(b'a' + rng.below(26) as u8);
+                    }
+                    answers.sort_unstable();
+                    answers.dedup();
+                    rng.shuffle(&mut answers);
+                    String::from_utf8(answers).expect("letters are ascii")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed<'a> = Vec<(usize, usize)>;
+
+    fn day(&self) -> usize {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Custom Customs"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<(usize, usize)>> {
+        load_groups(input)
+    }
+
+    fn part1(&self, groups: &Vec<(usize, usize)>) -> Result<String> {
+        Ok(part1(groups).to_string())
+    }
+
+    fn part2(&self, groups: &Vec<(usize, usize)>) -> Result<String> {
+        Ok(part2(groups).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_both() {
+        let groups = load_groups(INPUT).expect("valid input");
+        assert_eq!(part1(&groups), 6_297);
+        assert_eq!(part2(&groups), 3_158);
+    }
+}
@@ -0,0 +1,530 @@
+//! Day 7: [Handy Haversacks](https://adventofcode.com/2020/day/7).
+
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::collections::{HashMap, HashSet};
+use std::ptr::slice_from_raw_parts;
+use std::rc::{Rc, Weak};
+use std::str::Split;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/07.txt");
+
+/// Which bags must contain which others, linked both ways.
+pub struct BagTree<'a> {
+    nodes: HashMap<&'a str, Rc<Bag<'a>>>,
+}
+
+struct Bag<'a> {
+    colour: &'a str,
+    parents: RefCell<Vec<Weak<Bag<'a>>>>,
+    children: RefCell<Vec<ChildBagInfo<'a>>>,
+}
+
+struct ChildBagInfo<'a> {
+    count: usize,
+    child: Weak<Bag<'a>>,
+}
+
+unsafe fn combine_name_parts<'a>(a: &'a str, b: &'a str) -> &'a str {
+    let s = slice_from_raw_parts(a.as_ptr(), a.len() + b.len() + 1);
+    std::str::from_utf8_unchecked(&*s)
+}
+
+unsafe fn get_rest_of_string<'a>(full: &'a str, start: &'a str) -> &'a str {
+    let full_p = full.as_ptr();
+    let base_p = start.as_ptr();
+    let seen = base_p as usize - full_p as usize;
+    let s = slice_from_raw_parts(base_p, full.len() - seen - 1);
+    std::str::from_utf8_unchecked(&*s)
+}
+
+fn next_word<'a>(input: &str, rule: &'a str, words: &mut Split<'a, char>) -> Result<&'a str> {
+    words
+        .next()
+        .ok_or_else(|| Error::at(input, rule, "rule ended unexpectedly"))
+}
+
+impl BagTree<'_> {
+    /// Parses one rule per line, e.g. `light red bags contain 1 bright white bag.`
+    pub fn new(input: &str) -> Result<BagTree<'_>> {
+        let colour_and_children = input
+            .lines()
+            .map(|rule| {
+                if !rule.ends_with('.') {
+                    return Err(Error::at(input, rule, "rule should end with a full stop"));
+                }
+                let mut parts = rule.split(' ');
+                let quality = next_word(input, rule, &mut parts)?;
+                let colour = next_word(input, rule, &mut parts)?;
+                let colour = unsafe { combine_name_parts(quality, colour) };
+                parts.next();
+                parts.next();
+                let start = next_word(input, rule, &mut parts)?;
+                let res = unsafe { get_rest_of_string(rule, start) };
+                Ok((colour, res))
+            })
+            .collect::<Result<Vec<(&str, &str)>>>()?;
+
+        let mut nodes = HashMap::with_capacity(colour_and_children.len());
+        colour_and_children.iter().for_each(|&(colour, _)| {
+            nodes.insert(
+                colour,
+                Rc::new(Bag {
+                    colour,
+                    parents: RefCell::new(Vec::new()),
+                    children: RefCell::new(Vec::new()),
+                }),
+            );
+        });
+
+        for (colour, children) in colour_and_children {
+            if children == "no other bags" {
+                continue;
+            }
+
+            let parent = &nodes[colour];
+
+            for line in children.split(", ") {
+                let mut words = line.split(' ');
+                let count = error::parse::<usize>(input, next_word(input, line, &mut words)?)?;
+                let quality = next_word(input, line, &mut words)?;
+                let colour = next_word(input, line, &mut words)?;
+                let colour = unsafe { combine_name_parts(quality, colour) };
+                let child = nodes.get(colour).ok_or_else(|| {
+                    Error::at(input, colour, format!("no rule for {} bags", colour))
+                })?;
+
+                child.parents.borrow_mut().push(Rc::downgrade(parent));
+                parent.children.borrow_mut().push(ChildBagInfo {
+                    count,
+                    child: Rc::downgrade(child),
+                });
+            }
+        }
+
+        // part 2 follows children all the way down, so a bag inside itself would never finish
+        let mut finished = HashMap::with_capacity(nodes.len());
+        for bag in nodes.values() {
+            if let Some(colour) = bag.cycle(&mut finished)? {
+                return Err(Error::at(
+                    input,
+                    colour,
+                    format!("{} bags end up containing themselves", colour),
+                ));
+            }
+        }
+
+        Ok(BagTree { nodes })
+    }
+}
+
+fn upgrade<'a>(bag: &Weak<Bag<'a>>) -> Result<Rc<Bag<'a>>> {
+    bag.upgrade()
+        .ok_or_else(|| Error::new("bag rules were dropped while in use"))
+}
+
+impl<'c> Bag<'c> {
+    /// The colour of a bag that ends up inside itself, searching from this one. `finished` marks
+    /// colours already searched as `true` and those still being searched as `false`.
+    fn cycle(&self, finished: &mut HashMap<&'c str, bool>) -> Result<Option<&'c str>> {
+        match finished.insert(self.colour, false) {
+            Some(true) => {
+                finished.insert(self.colour, true);
+                return Ok(None);
+            }
+            Some(false) => return Ok(Some(self.colour)),
+            None => (),
+        }
+        for c in self.children.borrow().iter() {
+            if let Some(colour) = upgrade(&c.child)?.cycle(finished)? {
+                return Ok(Some(colour));
+            }
+        }
+        finished.insert(self.colour, true);
+        Ok(None)
+    }
+
+    fn parents(&self) -> Result<usize> {
+        self.parents_helper(&mut HashSet::new())
+    }
+
+    fn parents_helper<'a, 'b>(&'a self, seen: &'b mut HashSet<&'c str>) -> Result<usize> {
+        let mut count = 0;
+        for parent in self.parents.borrow().iter() {
+            let parent = upgrade(parent)?;
+            if seen.insert(parent.colour) {
+                count += 1 + parent.parents_helper(seen)?;
+            }
+        }
+        Ok(count)
+    }
+
+    fn children(&self) -> Result<usize> {
+        self.children.borrow().iter().try_fold(0usize, |total, c| {
+            let inside = upgrade(&c.child)?.children()?;
+            inside
+                .checked_add(1)
+                .and_then(|bags| bags.checked_mul(c.count))
+                .and_then(|bags| bags.checked_add(total))
+                .ok_or_else(too_many_bags)
+        })
+    }
+}
+
+fn too_many_bags() -> Error {
+    Error::new("shiny gold bags hold too many bags to count")
+}
+
+fn shiny_gold<'a, 'b>(tree: &'b BagTree<'a>) -> Result<&'b Bag<'a>> {
+    tree.nodes
+        .get("shiny gold")
+        .map(|bag| bag.as_ref())
+        .ok_or_else(|| Error::new("no rule for shiny gold bags"))
+}
+
+/// How many colours of bag can eventually contain a shiny gold bag.
+pub fn part1(tree: &BagTree) -> Result<usize> {
+    shiny_gold(tree)?.parents()
+}
+
+/// How many bags a shiny gold bag must contain.
+pub fn part2(tree: &BagTree) -> Result<usize> {
+    shiny_gold(tree)?.children()
+}
+
+/// The bags each colour must contain, parsed the slow way.
+fn reference_rules(input: &str) -> Result<HashMap<&str, Vec<(usize, &str)>>> {
+    let two_words = |colour: &str| colour.split(' ').count() == 2;
+    let mut rules = HashMap::new();
+    for rule in input.lines() {
+        let (colour, contents) = rule
+            .strip_suffix('.')
+            .and_then(|rule| rule.split_once(" bags contain "))
+            .filter(|&(colour, _)| two_words(colour))
+            .ok_or_else(|| Error::new(format!("unexpected rule {:?}", rule)))?;
+        let contents = match contents {
+            "no other bags" => Vec::new(),
+            contents => contents
+                .split(", ")
+                .map(|content| {
+                    let (count, bag) = content.split_once(' ')?;
+                    let child = bag
+                        .strip_suffix(" bags")
+                        .or_else(|| bag.strip_suffix(" bag"))
+                        .filter(|&child| two_words(child))?;
+                    Some((count.parse().ok()?, child))
+                })
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| Error::new(format!("unexpected contents {:?}", contents)))?,
+        };
+        if rules.insert(colour, contents).is_some() {
+            return Err(Error::new(format!(
+                "more than one rule for {} bags",
+                colour
+            )));
+        }
+    }
+    let colours = rules.values().flatten().map(|&(_, child)| child);
+    if let Some(child) = colours
+        .chain(Some("shiny gold"))
+        .find(|c| !rules.contains_key(c))
+    {
+        return Err(Error::new(format!("no rule for {} bags", child)));
+    }
+    Ok(rules)
+}
+
+/// Part 1 the slow way, searching down from every colour in turn for a shiny gold bag.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    let rules = reference_rules(input)?;
+    let holds_gold = |colour: &str| {
+        let mut seen = HashSet::new();
+        let mut stack = vec![colour];
+        while let Some(colour) = stack.pop() {
+            for &(_, child) in &rules[colour] {
+                if child == "shiny gold" {
+                    return true;
+                }
+                if seen.insert(child) {
+                    stack.push(child);
+                }
+            }
+        }
+        false
+    };
+    Ok(rules.keys().filter(|colour| holds_gold(colour)).count())
+}
+
+/// Part 2 the slow way, unpacking every bag one at a time. Fails if a shiny gold bag would hold
+/// itself, however deep down.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    fn inside<'a>(
+        rules: &HashMap<&'a str, Vec<(usize, &'a str)>>,
+        colour: &'a str,
+        path: &mut Vec<&'a str>,
+    ) -> Result<usize> {
+        if path.contains(&colour) {
+            return Err(Error::new(format!("{} bags contain themselves", colour)));
+        }
+        path.push(colour);
+        let mut total = 0;
+        for &(count, child) in &rules[colour] {
+            total = inside(rules, child, path)?
+                .checked_add(1)
+                .and_then(|bags| bags.checked_mul(count))
+                .and_then(|bags| bags.checked_add(total))
+                .ok_or_else(too_many_bags)?;
+        }
+        path.pop();
+        Ok(total)
+    }
+    inside(&reference_rules(input)?, "shiny gold", &mut Vec::new())
+}
+
+const QUALITIES: [&str; 16] = [
+    "light", "dark", "bright", "muted", "shiny", "faded", "dotted", "pale", "dull", "vibrant",
+    "posh", "mirrored", "wavy", "plaid", "striped", "drab",
+];
+
+const COLOURS: [&str; 16] = [
+    "red", "orange", "white", "yellow", "gold", "olive", "plum", "blue", "black", "green", "teal",
+    "tan", "maroon", "violet", "lime", "coral",
+];
+
+/// How many levels of bags there are above shiny gold, for part 1 to search up through.
+const ABOVE: usize = 3;
+
+/// Rules for `size` colours of bag, including shiny gold, where bags only ever contain bags from
+/// the level below so the rules never form a cycle. Part 2 walks every path down from shiny
+/// gold, which takes exponentially longer the more levels there are below it, so the levels
+/// below grow with the number of bits in `size`.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let levels = ABOVE + 1 + (usize::BITS - size.leading_zeros()) as usize;
+    let mut names = BTreeSet::new();
+    names.insert("shiny gold".to_string());
+    while names.len() < size.max(2) {
+        let quality = match names.len() < QUALITIES.len() * COLOURS.len() / 2 {
+            true => rng.pick(&QUALITIES).to_string(),
+            false => rng.word(4..=8),
+        };
+        names.insert(format!("{} {}", quality, rng.pick(&COLOURS)));
+    }
+    let mut bags = names
+        .into_iter()
+        .map(|name| {
+            let level = match name == "shiny gold" {
+                true => ABOVE,
+                false => rng.below(levels),
+            };
+            (level, name)
+        })
+        .collect::<Vec<_>>();
+    bags.sort_unstable();
+
+    let mut rules = bags
+        .iter()
+        .enumerate()
+        .map(|(i, (level, name))| {
+            let below = bags[i..].partition_point(|(l, _)| l == level) + i;
+            let after = bags[below..].partition_point(|(l, _)| *l == level + 1) + below;
+            let mut children = BTreeSet::new();
+            if below < after {
+                for _ in 0..rng.range(1..=4) {
+                    children.insert(rng.range(below..=after - 1));
+                }
+            }
+            let contents = match children.is_empty() {
+                true => "no other bags".to_string(),
+                false => children
+                    .iter()
+                    .map(|&child| match rng.range(1..=5) {
+                        1 => format!("1 {} bag", bags[child].1),
+                        count => format!("{} {} bags", count, bags[child].1),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            format!("{} bags contain {}.", name, contents)
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut rules);
+    rules.join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed<'a> = BagTree<'a>;
+
+    fn day(&self) -> usize {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Handy Haversacks"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<BagTree<'a>> {
+        BagTree::new(input)
+    }
+
+    fn part1(&self, tree: &BagTree<'_>) -> Result<String> {
+        part1(tree).map(|n| n.to_string())
+    }
+
+    fn part2(&self, tree: &BagTree<'_>) -> Result<String> {
+        part2(tree).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeMap;
+
+    /// A few colours of bag, one of them shiny gold, with what each contains. Bags only contain
+    /// colours that come after their own, so there are no cycles, but the rules come in any order.
+    fn acyclic_rules() -> impl Strategy<Value = Vec<(String, String)>> {
+        let contents = prop::collection::vec((any::<prop::sample::Index>(), 1usize..=5), 0..4);
+        (
+            prop::collection::vec(contents, 2..8),
+            any::<prop::sample::Index>(),
+        )
+            .prop_map(|(bags, gold)| {
+                let gold = gold.index(bags.len());
+                let name = |i: usize| match i == gold {
+                    true => "shiny gold".to_string(),
+                    false => format!("{} {}", QUALITIES[i], COLOURS[i + 8]),
+                };
+                bags.iter()
+                    .enumerate()
+                    .map(|(i, contents)| {
+                        let after = bags.len() - i - 1;
+                        let children = contents
+                            .iter()
+                            .filter(|_| after > 0)
+                            .map(|(child, count)| (i + 1 + child.index(after), *count))
+                            .collect::<BTreeMap<_, _>>();
+                        let contents = match children.is_empty() {
+                            true => "no other bags".to_string(),
+                            false => children
+                                .iter()
+                                .map(|(&child, &count)| match count {
+                                    1 => format!("1 {} bag", name(child)),
+                                    count => format!("{} {} bags", count, name(child)),
+                                })
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        };
+                        (name(i), contents)
+                    })
+                    .collect()
+            })
+            .prop_shuffle()
+    }
+
+    fn rules_input(rules: &[(String, String)]) -> String {
+        rules
+            .iter()
+            .map(|(colour, contents)| format!("{} bags contain {}.", colour, contents))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_part1() {
+        let tree = BagTree::new(INPUT).expect("valid input");
+        assert_eq!(part1(&tree), Ok(332));
+    }
+
+    #[test]
+    fn test_part2() {
+        let tree = BagTree::new(INPUT).expect("valid input");
+        assert_eq!(part2(&tree), Ok(10_875));
+    }
+
+    #[test]
+    fn test_errors() {
+        let rules =
+            "shiny gold bags contain 2 dark red bags.\nfaded blue bags contain no other bags.";
+        let err = BagTree::new(rules).err().expect("missing rule");
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 27: no rule for dark red bags"
+        );
+
+        let tree = BagTree::new("faded blue bags contain no other bags.").expect("valid rules");
+        assert!(part1(&tree).is_err());
+    }
+
+    #[test]
+    fn test_cycles() {
+        let rules = "shiny gold bags contain 1 dark red bag.
+dark red bags contain 2 shiny gold bags.";
+        let err = BagTree::new(rules).err().expect("cyclic rules");
+        assert!(err
+            .to_string()
+            .ends_with("bags end up containing themselves"));
+
+        let rules = "shiny gold bags contain no other bags.
+dark red bags contain 1 dark red bag.";
+        let err = BagTree::new(rules).err().expect("cyclic rules");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: dark red bags end up containing themselves"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_parts_match_reference(rules in acyclic_rules()) {
+            let input = rules_input(&rules);
+            let tree = BagTree::new(&input).expect("valid input");
+            prop_assert_eq!(part1(&tree), reference_part1(&input));
+            prop_assert_eq!(part2(&tree), reference_part2(&input));
+        }
+
+        #[test]
+        fn test_bag_inside_itself_is_rejected(
+            mut rules in acyclic_rules(),
+            at in any::<prop::sample::Index>(),
+        ) {
+            let at = at.index(rules.len());
+            let colour = rules[at].0.clone();
+            rules[at].1 = format!("1 {} bag", colour);
+            prop_assert!(BagTree::new(&rules_input(&rules)).is_err());
+        }
+
+        #[test]
+        fn test_parse_never_panics(
+            input in "((shiny|dark) (gold|red)( bags?| contain| no| other| [0-9]|,|\\.)*\n?)*",
+        ) {
+            if let Ok(tree) = BagTree::new(&input) {
+                let _ = part1(&tree);
+                let _ = part2(&tree);
+            }
+        }
+    }
+}
@@ -0,0 +1,384 @@
+//! Day 8: [Handheld Halting](https://adventofcode.com/2020/day/8).
+
+use crate::crosscheck;
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/08.txt");
+
+#[derive(Clone)]
+enum Instruction {
+    Acc(i64),
+    Jmp(i64),
+    Nop(i64),
+}
+
+/// Why [`Machine::run_to_cycle`] stopped.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Exit {
+    /// An instruction was about to run a second time.
+    Looped,
+    /// The program jumped to just past its last instruction.
+    Terminated,
+    /// The program jumped somewhere other than an instruction or its end.
+    OutOfBounds,
+    /// The accumulator went past what fits in 64 bits.
+    Overflowed,
+}
+
+/// The handheld's boot code and its state, parsed from one instruction per line.
+#[derive(Clone)]
+pub struct Machine {
+    i: i64,
+    instructions: Vec<Instruction>,
+    acc: i64,
+    swapped: Option<(Instruction, usize)>,
+}
+
+impl FromStr for Instruction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (op, num) = s
+            .split_once(' ')
+            .ok_or_else(|| "expected an operation and argument".to_string())?;
+        let i = match num.strip_prefix('+') {
+            Some(num) => num.parse::<i64>(),
+            None => num.parse::<i64>(),
+        }
+        .map_err(|err| format!("invalid argument {:?}: {}", num, err))?;
+        match op {
+            "nop" => Ok(Instruction::Nop(i)),
+            "acc" => Ok(Instruction::Acc(i)),
+            "jmp" => Ok(Instruction::Jmp(i)),
+            _ => Err(format!("invalid instruction {:?}", op)),
+        }
+    }
+}
+
+impl FromStr for Machine {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut instructions = Vec::new();
+        for instruction in s.lines() {
+            instructions.push(
+                instruction
+                    .parse()
+                    .map_err(|err: String| Error::at(s, instruction, err))?,
+            );
+        }
+        if instructions.is_empty() {
+            return Err(Error::new("program has no instructions"));
+        }
+        Ok(Machine {
+            i: 0,
+            instructions,
+            acc: 0,
+            swapped: None,
+        })
+    }
+}
+
+impl Machine {
+    /// The accumulator.
+    pub fn acc(&self) -> i64 {
+        self.acc
+    }
+
+    /// How many instructions the program has.
+    pub fn ins_count(&self) -> usize {
+        self.instructions.len()
+    }
+
+    /// Clears the accumulator and instruction pointer, undoing any [`Machine::swap_ins`].
+    pub fn reset(&mut self) {
+        self.acc = 0;
+        self.i = 0;
+        let mut replace: Option<(Instruction, usize)> = None;
+        std::mem::swap(&mut replace, &mut self.swapped);
+        if let Some((ins, i)) = replace {
+            self.instructions[i] = ins;
+        }
+    }
+
+    /// Swaps the `jmp` or `nop` at `ind` for the other, returning false if there isn't one.
+    pub fn swap_ins(&mut self, ind: usize) -> bool {
+        let mut new_ins = match self.instructions.get(ind) {
+            Some(&Instruction::Jmp(a)) => Instruction::Nop(a),
+            Some(&Instruction::Nop(a)) => Instruction::Jmp(a),
+            _ => return false,
+        };
+        std::mem::swap(&mut new_ins, &mut self.instructions[ind]);
+        self.swapped = Some((new_ins, ind));
+        true
+    }
+
+    /// Runs from the current instruction until the program loops or leaves its instructions.
+    pub fn run_to_cycle(&mut self) -> Exit {
+        let mut seen = vec![false; self.instructions.len()];
+        loop {
+            match usize::try_from(self.i) {
+                Ok(i) if i == seen.len() => return Exit::Terminated,
+                Ok(i) if i < seen.len() => {
+                    if std::mem::replace(&mut seen[i], true) {
+                        return Exit::Looped;
+                    }
+                }
+                _ => return Exit::OutOfBounds,
+            }
+            if let Some(exit) = self.iterate() {
+                return exit;
+            }
+        }
+    }
+
+    /// Runs the current instruction, returning why the program has to stop if it can't.
+    fn iterate(&mut self) -> Option<Exit> {
+        let jump = match self.instructions[self.i as usize] {
+            Instruction::Acc(i) => {
+                self.acc = match self.acc.checked_add(i) {
+                    Some(acc) => acc,
+                    None => return Some(Exit::Overflowed),
+                };
+                1
+            }
+            Instruction::Jmp(i) => i,
+            Instruction::Nop(_) => 1,
+        };
+        // no instruction is that far away, so it's out of bounds either way
+        self.i = self.i.checked_add(jump).unwrap_or(-1);
+        None
+    }
+}
+
+/// The accumulator just before any instruction runs twice.
+pub fn part1(machine: &mut Machine) -> Result<i64> {
+    match machine.run_to_cycle() {
+        Exit::Looped => Ok(machine.acc()),
+        exit => Err(Error::new(format!("program never looped: {:?}", exit))),
+    }
+}
+
+/// The accumulator once the program terminates with one `jmp` or `nop` swapped.
+pub fn part2(machine: &mut Machine) -> Result<i64> {
+    (0..machine.ins_count())
+        .find_map(|r| {
+            machine.reset();
+            match machine.swap_ins(r) && machine.run_to_cycle() == Exit::Terminated {
+                true => Some(machine.acc()),
+                false => None,
+            }
+        })
+        .ok_or_else(|| Error::new("no single swap makes the program terminate"))
+}
+
+/// A program parsed the slow way, as operations and arguments.
+fn reference_program(input: &str) -> Result<Vec<(&str, i64)>> {
+    input
+        .lines()
+        .map(|line| match line.split_once(' ') {
+            Some((op @ ("acc" | "jmp" | "nop"), arg)) => arg
+                .parse()
+                .map(|arg| (op, arg))
+                .map_err(|_| Error::new(format!("invalid argument {:?}", arg))),
+            _ => Err(Error::new(format!("invalid instruction {:?}", line))),
+        })
+        .collect()
+}
+
+/// Runs a program the slow way, returning the accumulator and whether it terminated rather than
+/// looped. Fails if it jumps anywhere else.
+fn reference_run(program: &[(&str, i64)]) -> Result<(i64, bool)> {
+    let (mut acc, mut i) = (0, 0);
+    let mut seen = HashSet::new();
+    loop {
+        if i == program.len() as i64 {
+            return Ok((acc, true));
+        }
+        if !seen.insert(i) {
+            return Ok((acc, false));
+        }
+        match usize::try_from(i).ok().and_then(|i| program.get(i)) {
+            Some(&("acc", arg)) => {
+                acc = i64::checked_add(acc, arg)
+                    .ok_or_else(|| Error::new("the accumulator overflowed"))?;
+                i += 1;
+            }
+            Some(&("jmp", arg)) => i = i.checked_add(arg).unwrap_or(-1),
+            Some(_) => i += 1,
+            None => return Err(Error::new(format!("jumped out of bounds to {}", i))),
+        }
+    }
+}
+
+/// Part 1 the slow way, keeping every instruction run so far in a set.
+pub fn reference_part1(input: &str) -> Result<i64> {
+    match reference_run(&reference_program(input)?)? {
+        (acc, false) => Ok(acc),
+        (_, true) => Err(Error::new("program never looped")),
+    }
+}
+
+/// Part 2 the slow way, trying every swap and failing unless the ones that terminate agree.
+pub fn reference_part2(input: &str) -> Result<i64> {
+    let program = reference_program(input)?;
+    let mut accs = Vec::new();
+    for i in 0..program.len() {
+        let mut swapped = program.clone();
+        swapped[i].0 = match swapped[i].0 {
+            "jmp" => "nop",
+            "nop" => "jmp",
+            _ => continue,
+        };
+        if let Ok((acc, true)) = reference_run(&swapped) {
+            accs.push(acc);
+        }
+    }
+    crosscheck::only(accs)
+}
+
+fn instruction(op: &str, arg: i64) -> String {
+    format!("{} {:+}", op, arg)
+}
+
+/// A program of about `size` instructions that loops back to the start, and terminates only if
+/// the `jmp` that loops is swapped for a `nop`.
+///
+/// The loop also jumps over dead code that jumps back into it, and its `nop`s only point
+/// somewhere within the loop or its dead code, so swapping any other instruction still loops.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(4);
+    let tail = size / 4 + 1;
+    let mut program = Vec::new();
+    // indices of the loop's nops, whose arguments are filled in once its length is known
+    let mut nops = Vec::new();
+    while program.len() + 1 < size - tail {
+        let i = program.len();
+        match rng.below(6) {
+            0 => nops.push(i),
+            1 if i + 4 < size - tail => {
+                let dead = rng.range(1..=3);
+                program.push(instruction("jmp", dead as i64 + 2));
+                for _ in 0..dead {
+                    program.push(instruction("acc", rng.range(0..=100) as i64 - 50));
+                }
+                let last = program.len() as i64;
+                program.push(instruction("jmp", rng.range(0..=i) as i64 - last));
+                continue;
+            }
+            _ => {}
+        }
+        program.push(instruction("acc", rng.range(0..=100) as i64 - 50));
+    }
+    let end = program.len();
+    for &i in &nops {
+        program[i] = instruction("nop", rng.range(0..=end) as i64 - i as i64);
+    }
+    program.push(instruction("jmp", -(end as i64)));
+    while program.len() < size {
+        let i = program.len() as i64;
+        program.push(match rng.below(4) {
+            0 => instruction("nop", rng.range(0..=size) as i64 - i),
+            1 => instruction("jmp", rng.range(1..=(size as i64 - i) as usize) as i64),
+            _ => instruction("acc", rng.range(0..=100) as i64 - 50),
+        });
+    }
+    program.join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed<'a> = Machine;
+
+    fn day(&self) -> usize {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Handheld Halting"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Machine> {
+        input.parse()
+    }
+
+    fn part1(&self, machine: &Machine) -> Result<String> {
+        part1(&mut machine.clone()).map(|acc| acc.to_string())
+    }
+
+    fn part2(&self, machine: &Machine) -> Result<String> {
+        part2(&mut machine.clone()).map(|acc| acc.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|acc| acc.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|acc| acc.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::years::y2020::day08::{part1, part2, Exit, Machine, INPUT};
+
+    #[test]
+    fn test_parts() {
+        let mut machine = INPUT.parse::<Machine>().expect("please be a machine");
+        assert_eq!(part1(&mut machine), Ok(1_671));
+        machine.reset();
+        assert_eq!(part2(&mut machine), Ok(892));
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = "nop +0\nacc 1x"
+            .parse::<Machine>()
+            .err()
+            .expect("bad argument");
+        assert_eq!(err.position.map(|p| p.line), Some(2));
+        assert!("mul +3".parse::<Machine>().is_err());
+
+        let mut machine = "jmp -1".parse::<Machine>().expect("valid program");
+        assert!(part1(&mut machine).is_err());
+        let mut machine = "nop +0\njmp +3\nacc +1"
+            .parse::<Machine>()
+            .expect("valid program");
+        assert_eq!(machine.run_to_cycle(), Exit::OutOfBounds);
+    }
+
+    #[test]
+    fn test_loop_to_start() {
+        let mut machine = "acc +7\njmp -1".parse::<Machine>().expect("valid program");
+        assert_eq!(part1(&mut machine), Ok(7));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let program = format!("acc +{}\nacc +1\njmp -2", i64::MAX);
+        let mut machine = program.parse::<Machine>().expect("valid program");
+        assert_eq!(machine.run_to_cycle(), Exit::Overflowed);
+        assert!(part1(&mut machine).is_err());
+
+        let program = format!("nop +0\njmp +{}", i64::MAX);
+        let mut machine = program.parse::<Machine>().expect("valid program");
+        assert_eq!(machine.run_to_cycle(), Exit::OutOfBounds);
+    }
+}
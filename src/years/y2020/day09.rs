@@ -0,0 +1,244 @@
+//! Day 9: [Encoding Error](https://adventofcode.com/2020/day/9).
+
+use crate::crosscheck;
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/09.txt");
+/// How many numbers come before the first that must be a sum of two of them.
+pub const PREAMBLE: usize = 25;
+
+/// Parses one number per line.
+pub fn load_numbers(input: &str) -> Result<Vec<usize>> {
+    input.lines().map(|n| error::parse(input, n)).collect()
+}
+
+/// The first number that isn't the sum of two different values among the `check` numbers before
+/// it.
+pub fn part1(nums: &[usize], check: usize) -> Result<usize> {
+    for window_and_goal in nums.windows(check + 1) {
+        let goal = window_and_goal[check];
+        let window = &window_and_goal[..check];
+
+        if !pair_sum(window, goal) {
+            return Ok(goal);
+        }
+    }
+
+    Err(Error::new("every number is the sum of two before it"))
+}
+
+/// The sum of the smallest and largest numbers in a contiguous range of at least two summing to
+/// `goal`.
+pub fn part2(nums: &[usize], goal: usize) -> Result<usize> {
+    for i in 0..nums.len() {
+        let mut sum = 0usize;
+        for j in i..nums.len() {
+            sum = match sum.checked_add(nums[j]) {
+                Some(sum) if sum <= goal => sum,
+                _ => break,
+            };
+            if sum == goal && j > i {
+                let (smallest, largest) = nums[i..=j]
+                    .iter()
+                    .fold((usize::MAX, usize::MIN), |(low, high), &v| {
+                        (low.min(v), high.max(v))
+                    });
+                return Ok(smallest + largest);
+            }
+        }
+    }
+
+    Err(Error::new(format!("no contiguous range sums to {}", goal)))
+}
+
+/// Part 1 the slow way, trying every pair of different values before each number.
+pub fn reference_part1(nums: &[usize], check: usize) -> Result<usize> {
+    (check..nums.len())
+        .map(|i| nums[i])
+        .zip(nums.windows(check))
+        .find(|&(goal, window)| {
+            !window.iter().any(|&a| {
+                window
+                    .iter()
+                    .any(|&b| a != b && a.checked_add(b) == Some(goal))
+            })
+        })
+        .map(|(goal, _)| goal)
+        .ok_or_else(|| Error::new("every number is the sum of two before it"))
+}
+
+/// Part 2 the slow way, summing every range of at least two numbers.
+pub fn reference_part2(nums: &[usize], goal: usize) -> Result<usize> {
+    let mut answers = Vec::new();
+    for start in 0..nums.len() {
+        for end in start + 2..=nums.len() {
+            let range = &nums[start..end];
+            if range.iter().try_fold(0usize, |sum, &n| sum.checked_add(n)) == Some(goal) {
+                let smallest = range.iter().min().expect("not empty");
+                let largest = range.iter().max().expect("not empty");
+                answers.push(smallest + largest);
+            }
+        }
+    }
+    crosscheck::only(answers)
+}
+
+/// Whether `goal` is the sum of two different values in `window`.
+fn pair_sum(window: &[usize], goal: usize) -> bool {
+    window.iter().enumerate().any(|(i, &a)| {
+        window[i + 1..]
+            .iter()
+            .any(|&b| a != b && a.checked_add(b) == Some(goal))
+    })
+}
+
+/// Every number must be the sum of two of the 25 before it, so even picking the smallest pairs
+/// they double every 25 numbers or so. This is about as many as fit in 64 bits.
+const MAX_NUMBERS: usize = 1_200;
+
+/// `size` numbers, up to 1200, where one number somewhere past the preamble is not the sum of two
+/// before it but is the sum of a contiguous range of earlier numbers.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(PREAMBLE + 2, MAX_NUMBERS);
+    let mut nums = (1..=PREAMBLE * 2).collect::<Vec<_>>();
+    rng.shuffle(&mut nums);
+    nums.truncate(PREAMBLE);
+    let invalid = rng.range(PREAMBLE + 1..=PREAMBLE + (size - PREAMBLE) / 2);
+    while nums.len() < size {
+        let window = &nums[nums.len() - PREAMBLE..];
+        if nums.len() == invalid {
+            let end = rng.range(1..=invalid - 1);
+            let start = end - rng.range(1..=end.min(16));
+            let goal = nums[start..=end].iter().sum();
+            if !pair_sum(window, goal) {
+                nums.push(goal);
+            }
+            continue;
+        }
+        let mut smallest = window.to_vec();
+        smallest.sort_unstable();
+        smallest.dedup();
+        smallest.truncate(6);
+        rng.shuffle(&mut smallest);
+        nums.push(smallest[0] + smallest[1]);
+    }
+    nums.iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed<'a> = Vec<usize>;
+
+    fn day(&self) -> usize {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Encoding Error"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>> {
+        load_numbers(input)
+    }
+
+    fn part1(&self, numbers: &Vec<usize>) -> Result<String> {
+        part1(numbers, PREAMBLE).map(|n| n.to_string())
+    }
+
+    fn part2(&self, numbers: &Vec<usize>) -> Result<String> {
+        let invalid = part1(numbers, PREAMBLE)?;
+        part2(numbers, invalid).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn max_size(&self) -> Option<usize> {
+        Some(MAX_NUMBERS)
+    }
+
+    fn unscaled(&self) -> Option<&'static str> {
+        Some("the 25 numbers each number is checked against")
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        let invalid = load_numbers(input).and_then(|numbers| reference_part1(&numbers, PREAMBLE));
+        Some(invalid.map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        let numbers = match load_numbers(input) {
+            Ok(numbers) => numbers,
+            Err(err) => return Some(Err(err)),
+        };
+        let weakness = reference_part1(&numbers, PREAMBLE)
+            .and_then(|invalid| reference_part2(&numbers, invalid));
+        Some(weakness.map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::years::y2020::day09::{load_numbers, part1, part2, INPUT};
+
+    #[test]
+    fn actual_answers() {
+        let nums = load_numbers(INPUT).expect("valid input");
+        assert_eq!(part1(&nums, 25), Ok(15_690_279));
+        assert_eq!(part2(&nums, 15_690_279), Ok(2_174_232));
+    }
+
+    #[test]
+    fn part2_test() {
+        let nums = "35
+20
+15
+25
+47
+40
+62
+55
+65
+95
+102
+117
+150
+182
+127
+219
+299
+277
+309
+576";
+        let nums = load_numbers(nums).expect("valid input");
+        let p1 = part1(&nums, 5).expect("has an invalid number");
+        assert_eq!(p1, 127);
+
+        let p2 = part2(&nums, p1);
+        assert_eq!(p2, Ok(62));
+        assert!(part2(&nums, 1).is_err());
+
+        assert_eq!(part1(&[5, 5, 10], 2), Ok(10));
+        assert_eq!(part2(&[5, 3, 2], 5), Ok(5));
+    }
+
+    #[test]
+    fn huge_numbers_test() {
+        let huge = usize::MAX - 1;
+        assert_eq!(part1(&[huge, 3, 2], 2), Ok(2));
+        assert!(part2(&[huge, 3, 2], 2).is_err());
+    }
+}
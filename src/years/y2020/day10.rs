@@ -0,0 +1,264 @@
+//! Day 10: [Adapter Array](https://adventofcode.com/2020/day/10).
+
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::collections::HashMap;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/10.txt");
+
+/// Parses one adapter rating per line, sorted in ascending order.
+pub fn load_input(input: &str) -> Result<Vec<usize>> {
+    let mut res = input
+        .lines()
+        .map(|line| error::parse(input, line))
+        .collect::<Result<Vec<_>>>()?;
+    res.sort_unstable();
+    Ok(res)
+}
+
+fn unsupported_gap(last: usize, num: usize) -> Error {
+    Error::new(format!(
+        "adapters {} and {} must differ by 1 or 3 jolts",
+        last, num
+    ))
+}
+
+/// The number of 1-jolt differences multiplied by the number of 3-jolt differences in the
+/// chain of sorted adapters, counting the device's built-in one.
+pub fn part1(nums: &[usize]) -> Result<usize> {
+    let mut ones = 0;
+    let mut threes = 1;
+
+    let mut last = 0;
+    for num in nums {
+        match num - last {
+            1 => ones += 1,
+            3 => threes += 1,
+            _ => return Err(unsupported_gap(last, *num)),
+        }
+        last = *num;
+    }
+
+    Ok(ones * threes)
+}
+
+/// How many ways a run of `consec` 1-jolt steps can be connected with some adapters left out,
+/// which is 1, 1, 2, 4, 7, 13 and so on, each the sum of the three before.
+fn arrangements(consec: usize) -> usize {
+    let (mut a, mut b, mut c) = (0, 0, 1);
+    for _ in 0..consec {
+        let next = a + b + c;
+        a = b;
+        b = c;
+        c = next;
+    }
+    c
+}
+
+/// How many ways the sorted adapters can connect the outlet to the device.
+pub fn part2(nums: &[usize]) -> Result<usize> {
+    let mut p2 = 1;
+
+    let mut last = 0;
+    let mut consec = 0;
+    for num in nums {
+        match num - last {
+            1 => consec += 1,
+            3 => {
+                p2 *= arrangements(consec);
+                consec = 0;
+            }
+            _ => return Err(unsupported_gap(last, *num)),
+        }
+        last = *num;
+    }
+
+    Ok(p2 * arrangements(consec))
+}
+
+/// The adapters sorted into a chain the slow way, failing if some can't be chained.
+fn reference_chain(input: &str) -> Result<Vec<usize>> {
+    let mut chain = input
+        .lines()
+        .map(|line| line.parse().map_err(|_| Error::new("invalid rating")))
+        .collect::<Result<Vec<usize>>>()?;
+    chain.push(0);
+    chain.sort_unstable();
+    match chain
+        .windows(2)
+        .all(|pair| (1..=3).contains(&(pair[1] - pair[0])))
+    {
+        true => Ok(chain),
+        false => Err(Error::new("adapters must be 1 to 3 jolts apart")),
+    }
+}
+
+/// Part 1 the slow way, tallying every step along the chain.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    let chain = reference_chain(input)?;
+    let mut steps = HashMap::new();
+    for pair in chain.windows(2) {
+        *steps.entry(pair[1] - pair[0]).or_insert(0) += 1;
+    }
+    let count = |step| steps.get(&step).copied().unwrap_or(0);
+    Ok(count(1) * (count(3) + 1))
+}
+
+/// Part 2 the slow way, counting the ways to reach each adapter from those up to 3 jolts below.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    let chain = reference_chain(input)?;
+    let mut ways = vec![1];
+    for (i, &rating) in chain.iter().enumerate().skip(1) {
+        let below = (0..i).filter(|&j| rating - chain[j] <= 3);
+        ways.push(below.map(|j| ways[j]).sum());
+    }
+    Ok(ways[ways.len() - 1])
+}
+
+/// About `size` adapters whose sorted ratings differ by 1 or 3, in runs of at most four 1-jolt
+/// steps. Long inputs switch to runs of one step once the number of arrangements nears `u64`'s
+/// limit, so part 2 can't overflow.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut adapters = Vec::new();
+    let mut rating = 0;
+    let mut ways = 1u64;
+    while adapters.len() < size.max(1) {
+        let longest = match ways < 1 << 50 {
+            true => 4,
+            false => 1,
+        };
+        let run = rng.range(1..=longest);
+        ways *= arrangements(run) as u64;
+        for _ in 0..run {
+            rating += 1;
+            adapters.push(rating);
+        }
+        rating += 2;
+    }
+    rng.shuffle(&mut adapters);
+    adapters
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed<'a> = Vec<usize>;
+
+    fn day(&self) -> usize {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Adapter Array"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>> {
+        load_input(input)
+    }
+
+    fn part1(&self, nums: &Vec<usize>) -> Result<String> {
+        part1(nums).map(|n| n.to_string())
+    }
+
+    fn part2(&self, nums: &Vec<usize>) -> Result<String> {
+        part2(nums).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day10::{load_input, part1, part2, INPUT};
+
+    #[test]
+    fn test_actual() {
+        let nums = load_input(INPUT).expect("valid input");
+        assert_eq!(part1(&nums), Ok(2_484));
+        assert_eq!(part2(&nums), Ok(15_790_581_481_472));
+    }
+
+    #[test]
+    fn test_part2() {
+        let mut nums = load_input(
+            "16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4",
+        )
+        .expect("valid input");
+        nums.sort_unstable();
+        assert_eq!((part1(&nums), part2(&nums)), (Ok(35), Ok(8)));
+
+        let mut nums = load_input(
+            "28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3",
+        )
+        .expect("valid input");
+        nums.sort_unstable();
+        assert_eq!((part1(&nums), part2(&nums)), (Ok(220), Ok(19_208)));
+
+        let nums = load_input("1\n3\n5").expect("valid input");
+        assert!(part1(&nums).is_err());
+
+        let nums = load_input("1\n2\n3\n4\n5\n6").expect("valid input");
+        assert_eq!(part2(&nums), Ok(24));
+    }
+}
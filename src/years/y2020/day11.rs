@@ -0,0 +1,443 @@
+//! Day 11: [Seating System](https://adventofcode.com/2020/day/11).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use arrayvec::ArrayVec;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/11.txt");
+
+const ORDINALS: [(i16, i16); 8] = [
+    (0, 1),
+    (0, -1),
+    (1, 0),
+    (-1, 0),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Tile {
+    Floor,
+    Occupied,
+    Empty,
+}
+
+type LineOfSightOptions = ArrayVec<[(usize, usize); 8]>;
+
+/// The seating area, with whatever the current rules need to find each seat's neighbours.
+#[derive(Clone, Debug)]
+pub struct World {
+    floor: Vec<Vec<Tile>>,
+    to_toggle: Vec<(usize, usize)>,
+    line_of_sight: Vec<Vec<LineOfSightOptions>>,
+}
+
+impl World {
+    fn iterate_surrounding(&mut self) -> bool {
+        let mut change = false;
+
+        for (i, row) in self.floor.iter().enumerate() {
+            for (j, tile) in row.iter().enumerate() {
+                match tile {
+                    Tile::Floor => (),
+                    Tile::Occupied => {
+                        if self.surrounding(i, j) >= 4 {
+                            self.to_toggle.push((i, j));
+                            change = true;
+                        }
+                    }
+                    Tile::Empty => {
+                        if self.surrounding(i, j) == 0 {
+                            self.to_toggle.push((i, j));
+                            change = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        while let Some((i, j)) = self.to_toggle.pop() {
+            self.floor[i][j] = match self.floor[i][j] {
+                Tile::Floor => unreachable!("only seats are toggled"),
+                Tile::Occupied => Tile::Empty,
+                Tile::Empty => Tile::Occupied,
+            }
+        }
+
+        change
+    }
+
+    fn iterate_line_of_sight(&mut self) -> bool {
+        let mut change = false;
+
+        for (i, row) in self.floor.iter().enumerate() {
+            for (j, tile) in row.iter().enumerate() {
+                match tile {
+                    Tile::Floor => (),
+                    Tile::Occupied => {
+                        if self.line_of_sight[i][j].len() >= 5
+                            && self.line_of_sight[i][j]
+                                .iter()
+                                .filter(|(x, y)| self.floor[*x][*y] == Tile::Occupied)
+                                .count()
+                                >= 5
+                        {
+                            self.to_toggle.push((i, j));
+                            change = true;
+                        }
+                    }
+                    Tile::Empty => {
+                        if self.line_of_sight[i][j]
+                            .iter()
+                            .all(|(x, y)| self.floor[*x][*y] != Tile::Occupied)
+                        {
+                            self.to_toggle.push((i, j));
+                            change = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        while let Some((i, j)) = self.to_toggle.pop() {
+            self.floor[i][j] = match self.floor[i][j] {
+                Tile::Floor => unreachable!("only seats are toggled"),
+                Tile::Occupied => Tile::Empty,
+                Tile::Empty => Tile::Occupied,
+            }
+        }
+
+        change
+    }
+
+    fn occupied(&self) -> usize {
+        self.floor
+            .iter()
+            .flat_map(|row| row.iter())
+            .filter(|&t| *t == Tile::Occupied)
+            .count()
+    }
+
+    fn surrounding(&self, i: usize, j: usize) -> usize {
+        let mut occupied = 0;
+
+        if i > 0 {
+            if j > 0 && self.floor[i - 1][j - 1] == Tile::Occupied {
+                occupied += 1;
+            }
+
+            if self.floor[i - 1][j] == Tile::Occupied {
+                occupied += 1;
+            }
+
+            if j < self.floor[0].len() - 1 && self.floor[i - 1][j + 1] == Tile::Occupied {
+                occupied += 1;
+            }
+        }
+
+        if i < self.floor.len() - 1 {
+            if j > 0 && self.floor[i + 1][j - 1] == Tile::Occupied {
+                occupied += 1;
+            }
+
+            if self.floor[i + 1][j] == Tile::Occupied {
+                occupied += 1;
+            }
+
+            if j < self.floor[0].len() - 1 && self.floor[i + 1][j + 1] == Tile::Occupied {
+                occupied += 1;
+            }
+        }
+
+        if j > 0 && self.floor[i][j - 1] == Tile::Occupied {
+            occupied += 1;
+        }
+
+        if j < self.floor[0].len() - 1 && self.floor[i][j + 1] == Tile::Occupied {
+            occupied += 1;
+        }
+
+        occupied
+    }
+
+    fn gen_line_of_sight_options(&mut self) {
+        let size = self.floor.len() * self.floor[0].len();
+        self.line_of_sight = Vec::with_capacity(size);
+
+        for i in 0..self.floor.len() {
+            let mut row = Vec::new();
+            for j in 0..self.floor[0].len() {
+                let mut ind = ArrayVec::new();
+
+                for &(dx, dy) in ORDINALS.iter() {
+                    let mut nx = i as i16 + dx;
+                    let mut ny = j as i16 + dy;
+                    while nx >= 0
+                        && ny >= 0
+                        && nx < self.floor.len() as i16
+                        && ny < self.floor[0].len() as i16
+                    {
+                        match self.floor[nx as usize][ny as usize] {
+                            Tile::Occupied | Tile::Empty => {
+                                ind.push((nx as usize, ny as usize));
+                                break;
+                            }
+                            _ => (),
+                        }
+                        nx += dx;
+                        ny += dy;
+                    }
+                }
+                row.push(ind);
+            }
+            self.line_of_sight.push(row);
+        }
+    }
+}
+
+/// Parses a grid of `L` empty seats, `#` occupied seats and `.` floor.
+pub fn load_world(input: &str) -> Result<World> {
+    let width = input.lines().next().map_or(0, str::len);
+    let height = input.lines().count();
+    if width == 0 || width > i16::MAX as usize || height > i16::MAX as usize {
+        return Err(Error::new(format!(
+            "seating area must be between 1 and {} seats across",
+            i16::MAX
+        )));
+    }
+
+    let contents = input
+        .lines()
+        .map(|line| {
+            if line.len() != width {
+                return Err(Error::at(input, line, format!("row is not {} wide", width)));
+            }
+            line.char_indices()
+                .map(|(i, c)| match c {
+                    'L' => Ok(Tile::Empty),
+                    '.' => Ok(Tile::Floor),
+                    '#' => Ok(Tile::Occupied),
+                    _ => Err(Error::at(input, &line[i..], format!("unexpected {:?}", c))),
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(World {
+        floor: contents,
+        to_toggle: Vec::new(),
+        line_of_sight: Vec::new(),
+    })
+}
+
+/// How many seats end up occupied when people look at the eight seats around them.
+pub fn part1(mut world: World) -> usize {
+    while world.iterate_surrounding() {}
+    world.occupied()
+}
+
+/// How many seats end up occupied when people look at the first seat in each direction.
+pub fn part2(mut world: World) -> usize {
+    while world.iterate_line_of_sight() {}
+    world.occupied()
+}
+
+/// Seats that flip back and forth forever under `step`, or none if the seating area settles.
+/// Both sets of rules are symmetric thresholds, so a seating area that never settles ends up
+/// repeating every two rounds.
+fn oscillating(mut world: World, step: fn(&mut World) -> bool) -> Vec<(usize, usize)> {
+    let mut two_back = Vec::new();
+    loop {
+        let before = world.floor.clone();
+        if !step(&mut world) {
+            return Vec::new();
+        }
+        if world.floor == two_back {
+            return (0..before.len())
+                .flat_map(|i| (0..before[i].len()).map(move |j| (i, j)))
+                .filter(|&(i, j)| world.floor[i][j] != before[i][j])
+                .collect();
+        }
+        two_back = before;
+    }
+}
+
+/// Runs the seating area the slow way until it settles, returning how many seats end up
+/// occupied. People look past floor unless `adjacent_only`, and leave once they see `crowded`
+/// occupied seats. Fails if the seating area never settles.
+fn reference_settle(input: &str, adjacent_only: bool, crowded: usize) -> Result<usize> {
+    let mut grid = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let width = grid.first().map_or(0, Vec::len);
+    let valid = |row: &Vec<char>| row.len() == width && row.iter().all(|c| "L.#".contains(*c));
+    if width == 0 || !grid.iter().all(valid) {
+        return Err(Error::new("seating areas are rectangles of L, . and #"));
+    }
+
+    let mut seen = HashSet::new();
+    loop {
+        if !seen.insert(grid.clone()) {
+            return Err(Error::new("seating area never settles"));
+        }
+        let at = |i: i64, j: i64| {
+            let row = usize::try_from(i).ok().and_then(|i| grid.get(i))?;
+            usize::try_from(j).ok().and_then(|j| row.get(j)).copied()
+        };
+        let sees = |i: usize, j: usize| {
+            ORDINALS
+                .iter()
+                .filter(|&&(di, dj)| {
+                    let (mut x, mut y) = (i as i64, j as i64);
+                    loop {
+                        x += di as i64;
+                        y += dj as i64;
+                        match at(x, y) {
+                            Some('.') if !adjacent_only => continue,
+                            seat => return seat == Some('#'),
+                        }
+                    }
+                })
+                .count()
+        };
+        let next = (0..grid.len())
+            .map(|i| {
+                (0..width)
+                    .map(|j| match (grid[i][j], sees(i, j)) {
+                        ('L', 0) => '#',
+                        ('#', occupied) if occupied >= crowded => 'L',
+                        (seat, _) => seat,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        if next == grid {
+            return Ok(grid.iter().flatten().filter(|&&c| c == '#').count());
+        }
+        grid = next;
+    }
+}
+
+/// Part 1 the slow way, copying the whole seating area every round.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    reference_settle(input, true, 4)
+}
+
+/// Part 2 the slow way, walking out in each direction every round.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    reference_settle(input, false, 5)
+}
+
+/// A seating area 90 seats across and `size` rows deep, about three quarters of it empty seats.
+/// Random seating areas don't always settle, so seats that never do are replaced with floor.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut floor = (0..size.clamp(1, i16::MAX as usize))
+        .map(|_| {
+            (0..90)
+                .map(|_| if rng.chance(0.75) { 'L' } else { '.' })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    loop {
+        let input = floor
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let world = load_world(&input).expect("generated seating areas are valid");
+        let mut seats = oscillating(world.clone(), World::iterate_surrounding);
+        if seats.is_empty() {
+            let mut world = world;
+            world.gen_line_of_sight_options();
+            seats = oscillating(world, World::iterate_line_of_sight);
+        }
+        if seats.is_empty() {
+            return input;
+        }
+        for (i, j) in seats {
+            floor[i][j] = '.';
+        }
+    }
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed<'a> = World;
+
+    fn day(&self) -> usize {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Seating System"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<World> {
+        load_world(input)
+    }
+
+    fn part1(&self, world: &World) -> Result<String> {
+        Ok(part1(world.clone()).to_string())
+    }
+
+    fn part2(&self, world: &World) -> Result<String> {
+        let mut world = world.clone();
+        world.gen_line_of_sight_options();
+        Ok(part2(world).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::years::y2020::day11::{load_world, part1, part2, INPUT};
+
+    #[test]
+    fn test_actual() {
+        let mut world = load_world(INPUT).expect("valid input");
+        assert_eq!(part1(world.clone()), 2_204);
+        world.gen_line_of_sight_options();
+        assert_eq!(part2(world), 1_986);
+    }
+
+    #[test]
+    fn test_example() {
+        let s = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+        let mut world = load_world(s).expect("valid input");
+        assert_eq!(part1(world.clone()), 37);
+        world.gen_line_of_sight_options();
+        assert_eq!(part2(world), 26);
+    }
+}
@@ -0,0 +1,361 @@
+//! Day 12: [Rain Risk](https://adventofcode.com/2020/day/12).
+
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::convert::TryFrom;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/12.txt");
+
+/// A navigation instruction. Turns are in quarter turns rather than degrees.
+pub enum Instruction {
+    /// `N` followed by a distance.
+    North(i64),
+    /// `S` followed by a distance.
+    South(i64),
+    /// `E` followed by a distance.
+    East(i64),
+    /// `W` followed by a distance.
+    West(i64),
+    /// `F` followed by a distance.
+    Forward(i64),
+    /// `L` followed by a multiple of 90 degrees, which may be negative.
+    Left(i64),
+    /// `R` followed by a multiple of 90 degrees, which may be negative.
+    Right(i64),
+}
+
+enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    fn left(&self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+            Direction::West => Direction::South,
+        }
+    }
+
+    fn right(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::South => Direction::West,
+            Direction::East => Direction::South,
+            Direction::West => Direction::North,
+        }
+    }
+}
+
+/// Parses one instruction per line, e.g. `F10` or `R90`.
+pub fn load_instructions(input: &str) -> Result<Vec<Instruction>> {
+    input
+        .lines()
+        .map(|line| {
+            let action = line
+                .chars()
+                .next()
+                .ok_or_else(|| Error::at(input, line, "empty instruction"))?;
+            let dist = error::parse(input, &line[action.len_utf8()..])?;
+            Ok(match action {
+                'N' => Instruction::North(dist),
+                'S' => Instruction::South(dist),
+                'E' => Instruction::East(dist),
+                'W' => Instruction::West(dist),
+                'F' => Instruction::Forward(dist),
+                'L' | 'R' if dist % 90 != 0 => {
+                    return Err(Error::at(
+                        input,
+                        line,
+                        "turns must be multiples of 90 degrees",
+                    ))
+                }
+                'L' => Instruction::Left((dist / 90).rem_euclid(4)),
+                'R' => Instruction::Right((dist / 90).rem_euclid(4)),
+                _ => {
+                    return Err(Error::at(
+                        input,
+                        line,
+                        format!("unknown action {:?}", action),
+                    ))
+                }
+            })
+        })
+        .collect()
+}
+
+fn too_far() -> Error {
+    Error::new("the ship went too far to keep track of")
+}
+
+/// `a + b * c`, failing if it overflows.
+fn move_by(a: i64, b: i64, c: i64) -> Result<i64> {
+    b.checked_mul(c)
+        .and_then(|bc| a.checked_add(bc))
+        .ok_or_else(too_far)
+}
+
+/// The Manhattan distance from the start to `(x, y)`.
+fn distance(x: i64, y: i64) -> Result<usize> {
+    x.unsigned_abs()
+        .checked_add(y.unsigned_abs())
+        .and_then(|d| usize::try_from(d).ok())
+        .ok_or_else(too_far)
+}
+
+/// The Manhattan distance the ship ends up from the start when the instructions move it. Fails
+/// if the ship goes too far to fit its position in 64 bits.
+pub fn part1(instructions: &[Instruction]) -> Result<usize> {
+    let mut direction = Direction::East;
+    let mut x = 0i64;
+    let mut y = 0i64;
+
+    for instruction in instructions {
+        match *instruction {
+            Instruction::North(i) => y = move_by(y, i, 1)?,
+            Instruction::South(i) => y = move_by(y, i, -1)?,
+            Instruction::East(i) => x = move_by(x, i, 1)?,
+            Instruction::West(i) => x = move_by(x, i, -1)?,
+            Instruction::Forward(i) => match direction {
+                Direction::North => y = move_by(y, i, 1)?,
+                Direction::South => y = move_by(y, i, -1)?,
+                Direction::East => x = move_by(x, i, 1)?,
+                Direction::West => x = move_by(x, i, -1)?,
+            },
+            Instruction::Left(l) => {
+                for _ in 0..l {
+                    direction = direction.left();
+                }
+            }
+            Instruction::Right(r) => {
+                for _ in 0..r {
+                    direction = direction.right();
+                }
+            }
+        }
+    }
+
+    distance(x, y)
+}
+
+/// The Manhattan distance the ship ends up from the start when the instructions move its
+/// waypoint. Fails if the ship or waypoint go too far to fit their positions in 64 bits.
+pub fn part2(instructions: &[Instruction]) -> Result<usize> {
+    let mut x = 0;
+    let mut y = 0;
+
+    let mut wx = 10i64;
+    let mut wy = 1i64;
+
+    for instruction in instructions {
+        match *instruction {
+            Instruction::North(i) => wy = move_by(wy, i, 1)?,
+            Instruction::South(i) => wy = move_by(wy, i, -1)?,
+            Instruction::East(i) => wx = move_by(wx, i, 1)?,
+            Instruction::West(i) => wx = move_by(wx, i, -1)?,
+            Instruction::Forward(i) => {
+                x = move_by(x, i, wx)?;
+                y = move_by(y, i, wy)?;
+            }
+            Instruction::Left(l) => {
+                for _ in 0..l {
+                    std::mem::swap(&mut wx, &mut wy);
+                    wx = wx.checked_neg().ok_or_else(too_far)?;
+                }
+            }
+            Instruction::Right(r) => {
+                for _ in 0..r {
+                    std::mem::swap(&mut wx, &mut wy);
+                    wy = wy.checked_neg().ok_or_else(too_far)?;
+                }
+            }
+        }
+    }
+    distance(x, y)
+}
+
+/// Where the ship ends up the slow way, as its Manhattan distance from the start. The actions
+/// move the waypoint if `waypoint`, or else the ship, which then faces east as if steering by a
+/// waypoint one square away.
+fn reference_navigate(input: &str, waypoint: bool) -> Result<usize> {
+    let (mut x, mut y) = (0i64, 0i64);
+    let (mut wx, mut wy) = if waypoint { (10, 1) } else { (1, 0) };
+    for line in input.lines() {
+        let action = line.chars().next().unwrap_or(' ');
+        let value = line[action.len_utf8().min(line.len())..]
+            .parse::<i64>()
+            .map_err(|_| Error::new(format!("invalid instruction {:?}", line)))?;
+        let (dx, dy) = match action {
+            'N' => (0, 1),
+            'S' => (0, -1),
+            'E' => (1, 0),
+            'W' => (-1, 0),
+            _ => (0, 0),
+        };
+        match action {
+            'N' | 'S' | 'E' | 'W' if waypoint => {
+                wx = move_by(wx, value, dx)?;
+                wy = move_by(wy, value, dy)?;
+            }
+            'N' | 'S' | 'E' | 'W' => {
+                x = move_by(x, value, dx)?;
+                y = move_by(y, value, dy)?;
+            }
+            'F' => {
+                x = move_by(x, value, wx)?;
+                y = move_by(y, value, wy)?;
+            }
+            'L' | 'R' if value % 90 == 0 => {
+                let left = if action == 'L' { value } else { -value };
+                for _ in 0..(left / 90).rem_euclid(4) {
+                    let turned = (wy.checked_neg().ok_or_else(too_far)?, wx);
+                    wx = turned.0;
+                    wy = turned.1;
+                }
+            }
+            _ => return Err(Error::new(format!("invalid instruction {:?}", line))),
+        }
+    }
+    distance(x, y)
+}
+
+/// Part 1 the slow way, treating the ship's heading as a waypoint one square away.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    reference_navigate(input, false)
+}
+
+/// Part 2 the slow way, turning the waypoint a quarter turn left at a time.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    reference_navigate(input, true)
+}
+
+/// `size` navigation instructions, with turns of 90, 180 or 270 degrees.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| match *rng.pick(b"NSEWFFLR") {
+            action @ (b'L' | b'R') => format!("{}{}", action as char, rng.range(1..=3) * 90),
+            action => format!("{}{}", action as char, rng.range(1..=100)),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed<'a> = Vec<Instruction>;
+
+    fn day(&self) -> usize {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "Rain Risk"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Instruction>> {
+        load_instructions(input)
+    }
+
+    fn part1(&self, instructions: &Vec<Instruction>) -> Result<String> {
+        part1(instructions).map(|n| n.to_string())
+    }
+
+    fn part2(&self, instructions: &Vec<Instruction>) -> Result<String> {
+        part2(instructions).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day12::{load_instructions, part1, part2, reference_part1};
+    use proptest::prelude::*;
+
+    fn instruction() -> impl Strategy<Value = String> {
+        prop_oneof![
+            ("[NSEWF]", 0..1000).prop_map(|(action, dist)| format!("{}{}", action, dist)),
+            ("[LR]", 0..4).prop_map(|(action, turns)| format!("{}{}", action, turns * 90)),
+        ]
+    }
+
+    fn full_turn() -> impl Strategy<Value = Vec<&'static str>> {
+        prop_oneof![
+            Just(vec!["L360"]),
+            Just(vec!["R360"]),
+            Just(vec!["R90", "R90", "R90", "R90"]),
+            Just(vec!["L180", "L90", "L90"]),
+            Just(vec!["L270", "R270", "R180", "L180"]),
+        ]
+    }
+
+    #[test]
+    fn test_parts() {
+        let s = "F10
+N3
+F7
+R90
+F11";
+        let ins = load_instructions(s).expect("valid input");
+        assert_eq!(part1(&ins), Ok(25));
+        assert_eq!(part2(&ins), Ok(286));
+
+        let ins = load_instructions("L-90\nF5\nR450\nF3").expect("valid input");
+        assert_eq!((part1(&ins), part2(&ins)), (Ok(8), Ok(78)));
+        assert!(load_instructions("R45").is_err());
+    }
+
+    #[test]
+    fn test_too_far() {
+        let ins = load_instructions("F2000000000000000000\nF9000000000000000000").expect("valid");
+        assert!(part1(&ins).is_err() && part2(&ins).is_err());
+        assert!(reference_part1("F9000000000000000000\nF9000000000000000000").is_err());
+
+        let ins = load_instructions("W9223372036854775807\nS9223372036854775807").expect("valid");
+        assert_eq!(part1(&ins), Ok(18_446_744_073_709_551_614));
+        let ins = load_instructions("F100000000000000000").expect("valid");
+        assert_eq!(part2(&ins), Ok(1_100_000_000_000_000_000));
+    }
+
+    proptest! {
+        #[test]
+        fn test_full_turns_change_nothing(
+            instructions in prop::collection::vec(instruction(), 0..50),
+            turn in full_turn(),
+            at in any::<prop::sample::Index>(),
+        ) {
+            let before = instructions.join("\n");
+            let mut turned = instructions.clone();
+            let at = at.index(instructions.len() + 1);
+            turned.splice(at..at, turn.iter().map(|s| s.to_string()));
+            let turned = turned.join("\n");
+
+            let before = load_instructions(&before).expect("valid input");
+            let turned = load_instructions(&turned).expect("valid input");
+            prop_assert_eq!(part1(&turned), part1(&before));
+            prop_assert_eq!(part2(&turned), part2(&before));
+        }
+    }
+}
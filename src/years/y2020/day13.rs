@@ -0,0 +1,262 @@
+//! Day 13: [Shuttle Search](https://adventofcode.com/2020/day/13).
+
+use crate::crosscheck;
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/13.txt");
+
+/// Parses the earliest departure time and the bus IDs, with `None` for each `x`.
+pub fn load_busses(input: &str) -> Result<(usize, Vec<Option<usize>>)> {
+    let mut lines = input.lines();
+    let timestamp = error::parse(input, lines.next().unwrap_or(input))?;
+    let busses = lines
+        .next()
+        .ok_or_else(|| Error::new("missing bus schedule"))?
+        .split(',')
+        .map(|b| match b {
+            "x" => Ok(None),
+            b => match error::parse(input, b)? {
+                0 => Err(Error::at(input, b, "bus ids must be positive")),
+                id => Ok(Some(id)),
+            },
+        })
+        .collect::<Result<_>>()?;
+
+    Ok((timestamp, busses))
+}
+
+/// Minutes from `timestamp` until `bus` next leaves, which is none if it leaves right then.
+fn wait(timestamp: usize, bus: usize) -> usize {
+    (bus - timestamp % bus) % bus
+}
+
+/// The ID of the first bus leaving at or after `timestamp` times the minutes to wait for it.
+pub fn part1(timestamp: usize, busses: &[Option<usize>]) -> Result<usize> {
+    let first = busses
+        .iter()
+        .filter_map(|b| *b)
+        .min_by_key(|&bus| wait(timestamp, bus))
+        .ok_or_else(|| Error::new("no busses in service"))?;
+    first
+        .checked_mul(wait(timestamp, first))
+        .ok_or_else(|| Error::new("the answer is too large"))
+}
+
+/// The earliest time each bus departs as many minutes later as its position in the list.
+pub fn part2(busses: &[Option<usize>]) -> Result<usize> {
+    let (indices, bus_ids): (Vec<_>, Vec<_>) = busses
+        .iter()
+        .enumerate()
+        .filter_map(|(offset, bus_id)| bus_id.map(|id| (offset as i128, id as i128)))
+        .unzip();
+
+    if bus_ids.is_empty() {
+        return Err(Error::new("no busses in service"));
+    }
+    // keeping the product within 64 bits keeps every step of the theorem within 128
+    let product = bus_ids
+        .iter()
+        .try_fold(1i128, |product, &id| {
+            product
+                .checked_mul(id)
+                .filter(|&product| product <= u64::MAX as i128)
+        })
+        .ok_or_else(|| Error::new("the busses only line up after too long"))?;
+
+    let mut res = ring_algorithm::chinese_remainder_theorem(&indices, &bus_ids)
+        .ok_or_else(|| Error::new("bus ids are not pairwise coprime"))?;
+
+    if res < 0 {
+        res += product;
+    }
+
+    Ok(((product - res) % product) as usize)
+}
+
+/// Part 1 the slow way, checking each minute from `timestamp` on for departing busses.
+pub fn reference_part1(timestamp: usize, busses: &[Option<usize>]) -> Result<usize> {
+    let busses = busses.iter().flatten().copied().collect::<Vec<_>>();
+    let latest = timestamp
+        .checked_add(*busses.iter().max().ok_or_else(|| Error::new("no busses"))?)
+        .ok_or_else(|| Error::new("the busses leave too late"))?;
+    let time = (timestamp..latest)
+        .find(|time| busses.iter().any(|bus| time % bus == 0))
+        .expect("every bus leaves within its ID's minutes");
+    let leaving = busses.iter().filter(|&bus| time % bus == 0);
+    crosscheck::only(leaving.map(|bus| bus * (time - timestamp)))
+}
+
+/// Part 2 the slow way, stepping through times that suit the busses so far by the least common
+/// multiple of their IDs until the next bus fits too.
+pub fn reference_part2(busses: &[Option<usize>]) -> Result<usize> {
+    let gcd = |mut a: u128, mut b: u128| {
+        while b != 0 {
+            let rem = a % b;
+            a = b;
+            b = rem;
+        }
+        a
+    };
+    let (mut time, mut step) = (0u128, 1u128);
+    for (offset, bus) in busses.iter().enumerate() {
+        let bus = match bus {
+            Some(bus) => *bus as u128,
+            None => continue,
+        };
+        let mut tries = 0;
+        while !(time + offset as u128).is_multiple_of(bus) {
+            time += step;
+            tries += 1;
+            if tries > bus {
+                return Err(Error::new("no time suits every bus"));
+            }
+        }
+        step = step / gcd(step, bus) * bus;
+        if step > u64::MAX as u128 {
+            return Err(Error::new("the busses only line up after too long"));
+        }
+    }
+    Ok(time as usize)
+}
+
+const PRIMES: [usize; 24] = [
+    13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97, 101, 103, 107,
+    109,
+];
+
+/// A departure time and a schedule `size` entries long, with a handful of busses whose IDs are
+/// distinct primes, so they are pairwise coprime, and whose product fits in 64 bits.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut primes = PRIMES.to_vec();
+    rng.shuffle(&mut primes);
+    let mut product = 1u64;
+    let mut busses = vec!["x".to_string(); size.max(1)];
+    let mut positions = (1..busses.len()).collect::<Vec<_>>();
+    rng.shuffle(&mut positions);
+    for (&prime, position) in primes.iter().zip(std::iter::once(0).chain(positions)) {
+        product = match product.checked_mul(prime as u64) {
+            Some(product) if product < 1 << 60 => product,
+            _ => break,
+        };
+        busses[position] = prime.to_string();
+    }
+    format!("{}\n{}", rng.range(100_000..=9_999_999), busses.join(","))
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed<'a> = (usize, Vec<Option<usize>>);
+
+    fn day(&self) -> usize {
+        13
+    }
+
+    fn title(&self) -> &'static str {
+        "Shuttle Search"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<(usize, Vec<Option<usize>>)> {
+        load_busses(input)
+    }
+
+    fn part1(&self, (timestamp, busses): &(usize, Vec<Option<usize>>)) -> Result<String> {
+        part1(*timestamp, busses).map(|n| n.to_string())
+    }
+
+    fn part2(&self, (_, busses): &(usize, Vec<Option<usize>>)) -> Result<String> {
+        part2(busses).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        let answer =
+            load_busses(input).and_then(|(timestamp, busses)| reference_part1(timestamp, &busses));
+        Some(answer.map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        let answer = load_busses(input).and_then(|(_, busses)| reference_part2(&busses));
+        Some(answer.map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day13::{load_busses, part1, part2, reference_part1, INPUT};
+
+    #[test]
+    fn test_actual() {
+        let (timestamp, busses) = load_busses(INPUT).expect("valid input");
+        assert_eq!(part1(timestamp, &busses), Ok(174));
+        assert_eq!(part2(&busses), Ok(780_601_154_795_940))
+    }
+
+    #[test]
+    fn test_example() {
+        let s = "939
+7,13,x,x,59,x,31,19";
+        let (t, b) = load_busses(s).expect("valid input");
+        assert_eq!(part1(t, &b), Ok(295));
+        assert_eq!(part2(&b), Ok(1_068_781));
+
+        let (t, b) = load_busses("14\n7,x,x,3").expect("valid input");
+        assert_eq!(part1(t, &b), Ok(0));
+        assert_eq!(part2(&b), Ok(0));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        let s = "123
+17,x,13,19";
+        let (_, b) = load_busses(s).expect("valid input");
+        assert_eq!(part2(&b), Ok(3_417));
+
+        let s = "123
+67,7,59,61";
+        let (_, b) = load_busses(s).expect("valid input");
+        assert_eq!(part2(&b), Ok(754_018));
+
+        let s = "123
+67,x,7,59,61";
+        let (_, b) = load_busses(s).expect("valid input");
+        assert_eq!(part2(&b), Ok(779_210));
+
+        let s = "123
+67,7,x,59,61";
+        let (_, b) = load_busses(s).expect("valid input");
+        assert_eq!(part2(&b), Ok(1_261_476));
+
+        let s = "123
+1789,37,47,1889";
+        let (_, b) = load_busses(s).expect("valid input");
+        assert_eq!(part2(&b), Ok(1_202_161_486));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(load_busses("939\n7,0,x").is_err());
+        assert!(load_busses("939").is_err());
+        let (_, b) = load_busses("1\n4,x,6").expect("valid input");
+        assert!(part2(&b).is_err());
+
+        let (t, b) = load_busses("0\nx").expect("valid input");
+        assert!(part1(t, &b).is_err() && part2(&b).is_err());
+        let (t, b) = load_busses("1\n4294967311,x,4294967357").expect("valid input");
+        assert!(part1(t, &b).is_err() && part2(&b).is_err());
+        assert!(reference_part1(usize::MAX, &b).is_err());
+        let (_, b) = load_busses("1\nx,18446744073709551557").expect("valid input");
+        assert_eq!(part2(&b), Ok(18_446_744_073_709_551_556));
+    }
+}
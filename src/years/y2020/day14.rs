@@ -0,0 +1,311 @@
+//! Day 14: [Docking Data](https://adventofcode.com/2020/day/14).
+
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/14.txt");
+
+/// One bit of a bitmask.
+#[derive(Debug, Copy, Clone)]
+pub enum Mask {
+    /// `X`
+    Unset,
+    /// `1`
+    One,
+    /// `0`
+    Zero,
+}
+
+/// A line of the initialization program.
+#[derive(Debug)]
+pub enum InputLine {
+    /// A new mask, least significant bit first.
+    Mask([Mask; 36]),
+    /// A write of a value to an address.
+    Setting(usize, usize),
+}
+
+fn p2_applier(
+    mem: &mut HashMap<usize, usize>,
+    orig_addr: usize,
+    addr: usize,
+    ind: usize,
+    mask: &[Mask; 36],
+    val: usize,
+) {
+    if ind == mask.len() {
+        mem.insert(addr, val);
+        return;
+    }
+
+    match mask[ind] {
+        Mask::Unset => {
+            p2_applier(mem, orig_addr, addr + (1 << ind), ind + 1, mask, val);
+            p2_applier(mem, orig_addr, addr, ind + 1, mask, val);
+        }
+        Mask::One => p2_applier(mem, orig_addr, addr + (1 << ind), ind + 1, mask, val),
+        Mask::Zero => p2_applier(
+            mem,
+            orig_addr,
+            addr | (orig_addr & (1 << ind)),
+            ind + 1,
+            mask,
+            val,
+        ),
+    }
+}
+
+/// Parses a line such as `mask = XXX1XX0X` (36 bits long) or `mem[8] = 11`.
+pub fn load_line(input: &str, line: &str) -> Result<InputLine> {
+    if let Some(mask) = line.strip_prefix("mask = ") {
+        let mask: [Mask; 36] = mask
+            .char_indices()
+            .rev()
+            .map(|(i, c)| match c {
+                'X' => Ok(Mask::Unset),
+                '1' => Ok(Mask::One),
+                '0' => Ok(Mask::Zero),
+                _ => Err(Error::at(input, &mask[i..], format!("unexpected {:?}", c))),
+            })
+            .collect::<Result<Vec<_>>>()?
+            .try_into()
+            .map_err(|_| Error::at(input, mask, "mask must be 36 bits long"))?;
+        return Ok(InputLine::Mask(mask));
+    }
+
+    let (mem, val) = line
+        .split_once(" = ")
+        .ok_or_else(|| Error::at(input, line, "expected mask or memory write"))?;
+    let addr = mem
+        .strip_prefix("mem[")
+        .and_then(|mem| mem.strip_suffix(']'))
+        .ok_or_else(|| Error::at(input, mem, "expected mem[address]"))?;
+    let addr = error::parse::<usize>(input, addr)?;
+    if addr >= 1 << 36 {
+        return Err(Error::at(input, mem, "address must fit in 36 bits"));
+    }
+    Ok(InputLine::Setting(addr, error::parse(input, val)?))
+}
+
+/// Parses one [`InputLine`] per line.
+pub fn load_program(input: &str) -> Result<Vec<InputLine>> {
+    input.lines().map(|line| load_line(input, line)).collect()
+}
+
+/// The sum of memory after running the program with masks applied to values.
+pub fn part1(program: &[InputLine]) -> usize {
+    let mut mask = [Mask::Unset; 36];
+    let mut mem: HashMap<usize, usize> = HashMap::new();
+
+    for instruction in program {
+        match *instruction {
+            InputLine::Mask(m) => mask = m,
+            InputLine::Setting(addr, val) => {
+                let apply = mask.iter().enumerate().fold(0, |acc, (i, m)| match m {
+                    Mask::Unset => acc + (val & (1 << i)),
+                    Mask::One => acc + (1 << i),
+                    Mask::Zero => acc,
+                });
+                mem.insert(addr, apply);
+            }
+        }
+    }
+
+    mem.values().sum()
+}
+
+/// Most addresses [`part2`] writes to in total. Each floating bit in a mask doubles the addresses
+/// every write goes to, so a few masks with many floating bits would otherwise take hours.
+pub const MAX_ADDRESSES: usize = 1 << 26;
+
+/// The sum of memory after running the program with masks applied to addresses. Fails if that
+/// means writing to more than [`MAX_ADDRESSES`] addresses, or the sum doesn't fit in 64 bits.
+pub fn part2(program: &[InputLine]) -> Result<usize> {
+    let mut mask = [Mask::Unset; 36];
+    let mut mem: HashMap<usize, usize> = HashMap::new();
+    let mut written = 0usize;
+
+    for instruction in program {
+        match *instruction {
+            InputLine::Mask(m) => mask = m,
+            InputLine::Setting(addr, val) => {
+                let floating = mask.iter().filter(|m| matches!(m, Mask::Unset)).count();
+                written = written.saturating_add(1 << floating);
+                if written > MAX_ADDRESSES {
+                    return Err(Error::new(format!(
+                        "the masks float too many bits to write to more than {} addresses",
+                        MAX_ADDRESSES
+                    )));
+                }
+                p2_applier(&mut mem, addr, 0, 0, &mask, val)
+            }
+        }
+    }
+
+    mem.values()
+        .try_fold(0usize, |sum, &val| sum.checked_add(val))
+        .ok_or_else(|| Error::new("memory adds up to more than 64 bits"))
+}
+
+/// Runs the program the slow way, with `write` storing each value in memory given the current
+/// mask as a string. Fails if a value is written before any mask or doesn't fit in 36 bits.
+fn reference_run(input: &str, write: fn(&mut HashMap<u64, u64>, &str, u64, u64)) -> Result<u64> {
+    let mut mask = None;
+    let mut mem = HashMap::new();
+    for line in input.lines() {
+        match load_line(input, line)? {
+            InputLine::Mask(_) => mask = line.strip_prefix("mask = "),
+            InputLine::Setting(addr, val) if val < 1 << 36 => {
+                let mask = mask.ok_or_else(|| Error::new("written to before any mask"))?;
+                write(&mut mem, mask, addr as u64, val as u64);
+            }
+            InputLine::Setting(..) => return Err(Error::new("values must fit in 36 bits")),
+        }
+    }
+    Ok(mem.values().sum())
+}
+
+/// Part 1 the slow way, setting the value's bits one mask character at a time.
+pub fn reference_part1(input: &str) -> Result<u64> {
+    reference_run(input, |mem, mask, addr, mut val| {
+        for (i, c) in mask.chars().rev().enumerate() {
+            match c {
+                '1' => val |= 1 << i,
+                '0' => val &= !(1 << i),
+                _ => {}
+            }
+        }
+        mem.insert(addr, val);
+    })
+}
+
+/// Part 2 the slow way, counting through every way to fill in the floating bits.
+pub fn reference_part2(input: &str) -> Result<u64> {
+    reference_run(input, |mem, mask, addr, val| {
+        let mask = mask.chars().rev().collect::<Vec<_>>();
+        let floating = (0..mask.len())
+            .filter(|&i| mask[i] == 'X')
+            .collect::<Vec<_>>();
+        for n in 0..1u64 << floating.len() {
+            let mut addr = addr;
+            for (i, &c) in mask.iter().enumerate() {
+                if c == '1' {
+                    addr |= 1 << i;
+                }
+            }
+            for (bit, &i) in floating.iter().enumerate() {
+                match n >> bit & 1 {
+                    1 => addr |= 1 << i,
+                    _ => addr &= !(1 << i),
+                }
+            }
+            mem.insert(addr, val);
+        }
+    })
+}
+
+/// Largest program [`generate`] writes. Part 2 writes to more addresses the bigger programs get,
+/// and much past this would go over [`MAX_ADDRESSES`].
+const MAX_SIZE: usize = 10_000;
+
+/// A program of `size` lines, up to 10,000, each mask followed by a few writes. Every floating
+/// bit doubles the addresses part 2 writes to, so masks float up to as many bits as `size` has
+/// and part 2 slows down faster than the program grows.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(1, MAX_SIZE);
+    let max_floating = (usize::BITS - size.leading_zeros()) as usize;
+    let mut lines = Vec::new();
+    while lines.len() < size {
+        let mut mask = (0..36)
+            .map(|_| if rng.chance(0.5) { '1' } else { '0' })
+            .collect::<Vec<_>>();
+        for _ in 0..rng.range(0..=max_floating) {
+            mask[rng.below(36)] = 'X';
+        }
+        lines.push(format!("mask = {}", mask.into_iter().collect::<String>()));
+        for _ in 0..rng.range(1..=6) {
+            lines.push(format!(
+                "mem[{}] = {}",
+                rng.below(1 << 16),
+                rng.below(1 << 30)
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed<'a> = Vec<InputLine>;
+
+    fn day(&self) -> usize {
+        14
+    }
+
+    fn title(&self) -> &'static str {
+        "Docking Data"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<InputLine>> {
+        load_program(input)
+    }
+
+    fn part1(&self, program: &Vec<InputLine>) -> Result<String> {
+        Ok(part1(program).to_string())
+    }
+
+    fn part2(&self, program: &Vec<InputLine>) -> Result<String> {
+        part2(program).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn max_size(&self) -> Option<usize> {
+        Some(MAX_SIZE)
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day14::{load_program, part1, part2, INPUT};
+
+    #[test]
+    fn test_actual() {
+        let program = load_program(INPUT).expect("valid input");
+        assert_eq!(part1(&program), 15_018_100_062_885);
+        assert_eq!(part2(&program), Ok(5_724_245_857_696));
+    }
+
+    #[test]
+    fn test_part2_limits() {
+        let program = load_program("mem[0] = 1").expect("valid input");
+        assert_eq!(part1(&program), 1);
+        assert!(part2(&program).is_err());
+
+        let floating = format!("mask = {}XX", "0".repeat(34));
+        let program = load_program(&format!("{}\nmem[0] = 1\nmem[4] = 2", floating));
+        assert_eq!(part2(&program.expect("valid input")), Ok(12));
+        let program = format!("{}\nmem[0] = {}\nmem[4] = 1", floating, usize::MAX / 4);
+        assert!(part2(&load_program(&program).expect("valid input")).is_err());
+    }
+}
@@ -0,0 +1,199 @@
+//! Day 15: [Rambunctious Recitation](https://adventofcode.com/2020/day/15).
+
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::collections::HashMap;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/15.txt");
+
+/// The turn part 2 asks about, which bounds the starting numbers too.
+pub const PART2_TURNS: u32 = 30_000_000;
+
+/// Parses the comma separated starting numbers.
+pub fn load_numbers(input: &str) -> Result<Vec<u32>> {
+    input
+        .trim()
+        .split(',')
+        .map(|n| match error::parse(input, n)? {
+            n if n < PART2_TURNS => Ok(n),
+            _ => Err(Error::at(
+                input,
+                n,
+                format!("starting numbers must be below {}", PART2_TURNS),
+            )),
+        })
+        .collect()
+}
+
+/// The number spoken on turn `turns` of the memory game starting with `nums`.
+pub fn play(nums: &[u32], turns: u32) -> usize {
+    let (&last, before) = match nums.split_last() {
+        Some(split) => split,
+        None => return 0,
+    };
+    if turns as usize <= nums.len() {
+        return nums[turns.max(1) as usize - 1] as usize;
+    }
+    let largest = nums.iter().copied().max().unwrap_or(0);
+    let mut spoken = vec![0; u32::max(largest, turns) as usize + 1];
+    // the last starting number is recorded by the first turn of the game proper, which looks it up
+    for (i, next) in (1..).zip(before) {
+        spoken[*next as usize] = i;
+    }
+
+    let mut last_spoken = last;
+    for i in (nums.len()) as u32..turns {
+        let d = &mut spoken[last_spoken as usize];
+        let mut result = *d;
+        if result != 0 {
+            result = i - result;
+        }
+
+        *d = i;
+        last_spoken = result;
+    }
+
+    last_spoken as usize
+}
+
+/// Part 1 the slow way, looking back through every number spoken so far each turn.
+pub fn reference_part1(nums: &[u32]) -> Result<u32> {
+    let mut spoken = nums.to_vec();
+    if spoken.is_empty() {
+        return Err(Error::new("no starting numbers"));
+    }
+    while spoken.len() < 2_020 {
+        let (last, before) = spoken.split_last().expect("not empty");
+        let age = match before.iter().rposition(|n| n == last) {
+            Some(turn) => before.len() - turn,
+            None => 0,
+        };
+        spoken.push(age as u32);
+    }
+    Ok(spoken[2_019])
+}
+
+/// [`play`] with a `HashMap` of when each number was last spoken rather than a table covering
+/// every number up to `turns`. Looking back like [`reference_part1`] would never finish part 2, and
+/// even this takes a few seconds for each input crosschecked.
+pub fn reference_play(nums: &[u32], turns: u32) -> Result<u32> {
+    let (&first, _) = nums
+        .split_first()
+        .ok_or_else(|| Error::new("no starting numbers"))?;
+    let mut last_seen = HashMap::new();
+    let mut last = first;
+    for turn in 1..turns {
+        let age = match last_seen.insert(last, turn) {
+            Some(seen) => turn - seen,
+            None => 0,
+        };
+        last = nums.get(turn as usize).copied().unwrap_or(age);
+    }
+    Ok(last)
+}
+
+/// Most starting numbers generated.
+const MAX_STARTING: usize = 1_000;
+
+/// `size` distinct starting numbers, up to 1000. Part 2 always plays 30 million turns, so the
+/// starting numbers barely change how long it takes.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = size.clamp(1, MAX_STARTING);
+    let mut nums = (0..count * 3).collect::<Vec<_>>();
+    rng.shuffle(&mut nums);
+    nums[..count]
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed<'a> = Vec<u32>;
+
+    fn day(&self) -> usize {
+        15
+    }
+
+    fn title(&self) -> &'static str {
+        "Rambunctious Recitation"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<u32>> {
+        load_numbers(input)
+    }
+
+    fn part1(&self, nums: &Vec<u32>) -> Result<String> {
+        Ok(play(nums, 2_020).to_string())
+    }
+
+    fn part2(&self, nums: &Vec<u32>) -> Result<String> {
+        Ok(play(nums, PART2_TURNS).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn max_size(&self) -> Option<usize> {
+        Some(MAX_STARTING)
+    }
+
+    fn unscaled(&self) -> Option<&'static str> {
+        Some("the 30 million turns part 2 plays")
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        let answer = load_numbers(input).and_then(|nums| reference_part1(&nums));
+        Some(answer.map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        let answer = load_numbers(input).and_then(|nums| reference_play(&nums, PART2_TURNS));
+        Some(answer.map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::Rng;
+    use crate::years::y2020::day15::{generate, load_numbers, play, reference_play, INPUT};
+
+    #[test]
+    fn test_actual() {
+        let nums = load_numbers(INPUT).expect("valid input");
+        assert_eq!(play(&nums, 2_020), 536);
+        assert_eq!(play(&nums, 30_000_000), 24_065_124);
+    }
+
+    #[test]
+    fn test_short_games() {
+        assert_eq!(play(&[0, 3, 6], 10), 0);
+        assert_eq!(play(&[0, 0], 3), 1);
+        assert_eq!(play(&[4, 5, 6], 2), 5);
+    }
+
+    #[test]
+    fn test_reference_play() {
+        assert_eq!(reference_play(&[0, 3, 6], 10), Ok(0));
+        assert_eq!(reference_play(&[0, 0], 3), Ok(1));
+        assert_eq!(reference_play(&[4, 5, 6], 2), Ok(5));
+        for seed in 0..5 {
+            let nums = load_numbers(&generate(&mut Rng::new(seed), 1 + seed as usize * 7))
+                .expect("valid input");
+            assert_eq!(
+                reference_play(&nums, 200_000),
+                Ok(play(&nums, 200_000) as u32)
+            );
+        }
+    }
+}
@@ -1,7 +1,12 @@
+//! Day 16: [Ticket Translation](https://adventofcode.com/2020/day/16).
+
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
 use std::collections::{HashMap, HashSet};
-use std::time::{Duration, Instant};
 
-const INPUT: &str = include_str!("../../files/16.txt");
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/16.txt");
 
 #[derive(Debug)]
 struct Range {
@@ -9,14 +14,16 @@ struct Range {
     max: usize,
 }
 
+/// A ticket field's rule, such as `class: 1-3 or 5-7`.
 #[derive(Debug)]
-struct Row<'a> {
+pub struct Row<'a> {
     name: &'a str,
     a: Range,
     b: Range,
 }
 
-type Ticket = Vec<usize>;
+/// A ticket's values, in the order they were written.
+pub type Ticket = Vec<usize>;
 
 impl Range {
     fn valid(&self, val: usize) -> bool {
@@ -25,91 +32,115 @@ impl Range {
 }
 
 impl Row<'_> {
-    fn valid(&self, val: usize) -> bool {
+    /// The field's name.
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Whether `val` is in either of the rule's ranges.
+    pub fn valid(&self, val: usize) -> bool {
         self.a.valid(val) || self.b.valid(val)
     }
 }
 
-fn load_tickets(input: &str) -> (Vec<Row>, Ticket, Vec<Ticket>) {
-    let mut input = input.split('\n');
-
-    let rows = (0..20)
-        .map(|_| {
-            let row = input.next().expect("pls");
-            let colon = row.find(':').expect("should have colon");
-            let name = &row[..colon];
-            let nums = &row[colon + 2..];
-            let or = nums.find(' ').expect("should have spaces");
-            let first_nums = &nums[..or];
-            let second_nums = &nums[or + 4..];
-            let hypa = first_nums.find('-').expect("should be hyphen seperated");
-            let mina = first_nums[..hypa].parse::<usize>().expect("shoud be int");
-            let maxa = first_nums[hypa + 1..]
-                .parse::<usize>()
-                .expect("shoud be int");
-            let hypb = second_nums.find('-').expect("should be hyphen seperated");
-            let minb = second_nums[..hypb].parse::<usize>().expect("shoud be int");
-            let maxb = second_nums[hypb + 1..]
-                .parse::<usize>()
-                .expect("shoud be int");
-            Row {
-                name,
-                a: Range {
-                    min: mina,
-                    max: maxa,
-                },
-                b: Range {
-                    min: minb,
-                    max: maxb,
-                },
-            }
-        })
-        .collect();
+fn load_range(input: &str, range: &str) -> Result<Range> {
+    let (min, max) = range
+        .split_once('-')
+        .ok_or_else(|| Error::at(input, range, "expected min-max range"))?;
+    Ok(Range {
+        min: error::parse(input, min)?,
+        max: error::parse(input, max)?,
+    })
+}
 
-    let mut input = input.skip(2);
+fn load_row<'a>(input: &str, row: &'a str) -> Result<Row<'a>> {
+    let (name, ranges) = row
+        .split_once(": ")
+        .ok_or_else(|| Error::at(input, row, "expected name: ranges"))?;
+    let (a, b) = ranges
+        .split_once(" or ")
+        .ok_or_else(|| Error::at(input, ranges, "expected two ranges"))?;
+    Ok(Row {
+        name,
+        a: load_range(input, a)?,
+        b: load_range(input, b)?,
+    })
+}
 
-    let ticket = input
-        .next()
-        .expect("should exist in input")
+fn load_ticket(input: &str, line: &str, fields: usize) -> Result<Ticket> {
+    let ticket = line
         .split(',')
-        .map(|v| v.parse().expect("should be valid int"))
-        .collect::<Vec<_>>();
+        .map(|v| error::parse(input, v))
+        .collect::<Result<Ticket>>()?;
+    match ticket.len() == fields {
+        true => Ok(ticket),
+        false => Err(Error::at(
+            input,
+            line,
+            format!("expected {} fields, found {}", fields, ticket.len()),
+        )),
+    }
+}
 
-    input.next();
-    input.next();
+/// Parses the rules, your ticket and the nearby tickets.
+pub fn load_tickets(input: &str) -> Result<(Vec<Row<'_>>, Ticket, Vec<Ticket>)> {
+    let mut sections = input.split("\n\n");
+    let mut section = |header: &str| {
+        let section = sections
+            .next()
+            .ok_or_else(|| Error::new(format!("missing {:?} section", header)))?;
+        match section.strip_prefix(header) {
+            Some(rest) => Ok(rest.trim_start_matches('\n')),
+            None => Err(Error::at(input, section, format!("expected {:?}", header))),
+        }
+    };
 
-    let tickets = input
-        .map(|t| {
-            t.split(',')
-                .map(|v| v.parse().expect("should be valid int"))
-                .collect()
-        })
-        .collect();
+    let rows = section("")?
+        .lines()
+        .map(|row| load_row(input, row))
+        .collect::<Result<Vec<_>>>()?;
+    let ticket = load_ticket(input, section("your ticket:")?, rows.len())?;
+    let tickets = section("nearby tickets:")?
+        .lines()
+        .map(|t| load_ticket(input, t, rows.len()))
+        .collect::<Result<_>>()?;
 
-    (rows, ticket, tickets)
+    Ok((rows, ticket, tickets))
 }
 
-fn part1<'a>(rows: &[Row], tickets: &'a [Ticket]) -> (usize, Vec<&'a Ticket>) {
-    let mut valid = Vec::new();
-    let mut res = 0;
+fn too_big(what: &str) -> Error {
+    Error::new(format!("{} to more than 64 bits", what))
+}
 
-    for ticket in tickets {
-        let mut v = true;
-        for &field in ticket {
-            if !rows.iter().any(|r| r.valid(field)) {
-                res += field;
-                v = false
-            }
-        }
-        if v {
-            valid.push(ticket);
-        }
-    }
+/// The sum of values that no rule allows. Fails if it doesn't fit in 64 bits.
+pub fn part1(rows: &[Row], tickets: &[Ticket]) -> Result<usize> {
+    tickets
+        .iter()
+        .flatten()
+        .filter(|&&field| !rows.iter().any(|r| r.valid(field)))
+        .try_fold(0usize, |sum, &field| sum.checked_add(field))
+        .ok_or_else(|| too_big("the invalid values add up"))
+}
 
-    (res, valid)
+/// The tickets whose values every rule allows.
+pub fn valid_tickets<'a>(rows: &[Row], tickets: &'a [Ticket]) -> Vec<&'a Ticket> {
+    tickets
+        .iter()
+        .filter(|ticket| {
+            ticket
+                .iter()
+                .all(|&field| rows.iter().any(|r| r.valid(field)))
+        })
+        .collect()
 }
 
-fn reduce_to_unique<'a>(rows: &'a [Row], tickets: &'_ [&Ticket]) -> Vec<&'a str> {
+/// The name of each field in ticket order, worked out from the valid tickets.
+pub fn reduce_to_unique<'a>(rows: &'a [Row], tickets: &'_ [&Ticket]) -> Result<Vec<&'a str>> {
+    let fields = tickets
+        .first()
+        .ok_or_else(|| Error::new("no valid tickets"))?
+        .len();
+
     let mut fixing_possibilities = HashMap::with_capacity(rows.len());
 
     for row in rows {
@@ -117,7 +148,7 @@ fn reduce_to_unique<'a>(rows: &'a [Row], tickets: &'_ [&Ticket]) -> Vec<&'a str>
             .entry(row.name)
             .or_insert_with(HashSet::new);
 
-        for i in 0..tickets[0].len() {
+        for i in 0..fields {
             if tickets.iter().all(|t| row.valid(t[i])) {
                 entry.insert(i);
             }
@@ -138,6 +169,9 @@ fn reduce_to_unique<'a>(rows: &'a [Row], tickets: &'_ [&Ticket]) -> Vec<&'a str>
                 }
             })
             .collect::<Vec<_>>();
+        if singulars.is_empty() {
+            return Err(Error::new("fields can't be matched up unambiguously"));
+        }
         for (label, index) in singulars {
             fixing_possibilities.remove(label);
             for possibilities in fixing_possibilities.values_mut() {
@@ -148,12 +182,18 @@ fn reduce_to_unique<'a>(rows: &'a [Row], tickets: &'_ [&Ticket]) -> Vec<&'a str>
     }
 
     (0..rows.len())
-        .map(|i| *fixed.get(&i).expect("exist"))
+        .map(|i| {
+            fixed
+                .get(&i)
+                .copied()
+                .ok_or_else(|| Error::new(format!("no rule matches field {}", i)))
+        })
         .collect()
 }
 
-fn part2(rows: &[Row], valid: &[&Ticket], ticket: Ticket) -> usize {
-    let keys = reduce_to_unique(rows, valid);
+/// The product of the `departure` fields on your ticket. Fails if it doesn't fit in 64 bits.
+pub fn part2(rows: &[Row], valid: &[&Ticket], ticket: Ticket) -> Result<usize> {
+    let keys = reduce_to_unique(rows, valid)?;
 
     keys.iter()
         .zip(ticket.iter())
@@ -164,22 +204,258 @@ fn part2(rows: &[Row], valid: &[&Ticket], ticket: Ticket) -> usize {
                 None
             }
         })
-        .product()
+        .try_fold(1usize, |product, &v| product.checked_mul(v))
+        .ok_or_else(|| too_big("the departure fields multiply"))
+}
+
+/// Part 1 the slow way, checking every value against every rule.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    let (rows, _, tickets) = load_tickets(input)?;
+    let mut invalid = tickets.iter().flatten().filter(|&&value| {
+        !rows.iter().any(|row| {
+            (row.a.min..=row.a.max).contains(&value) || (row.b.min..=row.b.max).contains(&value)
+        })
+    });
+    invalid
+        .try_fold(0usize, |sum, &value| sum.checked_add(value))
+        .ok_or_else(|| too_big("the invalid values add up"))
+}
+
+/// Part 2 the slow way, searching every way of matching fields to columns that the valid tickets
+/// allow. Fails unless there is exactly one.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    let (rows, ticket, tickets) = load_tickets(input)?;
+    let names = rows.iter().map(|row| row.name).collect::<HashSet<_>>();
+    if names.len() != rows.len() {
+        return Err(Error::new("rules must have different names"));
+    }
+    let valid = tickets
+        .iter()
+        .filter(|ticket| ticket.iter().all(|&v| rows.iter().any(|row| row.valid(v))))
+        .collect::<Vec<_>>();
+    let fits = |row: &Row, column: usize| valid.iter().all(|ticket| row.valid(ticket[column]));
+
+    // rules with the fewest columns they could be go first, so there's less to undo
+    let mut order = (0..rows.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&r| (0..ticket.len()).filter(|&c| fits(&rows[r], c)).count());
+    let mut matchings = Vec::new();
+    let mut columns = vec![None; rows.len()];
+    search(&order, &mut columns, &mut matchings, &|r, c| {
+        fits(&rows[r], c)
+    });
+
+    match matchings.as_slice() {
+        [columns] => rows
+            .iter()
+            .zip(columns)
+            .filter(|(row, _)| row.name.starts_with("departure"))
+            .try_fold(1usize, |product, (_, &column)| {
+                product.checked_mul(ticket[column])
+            })
+            .ok_or_else(|| too_big("the departure fields multiply")),
+        [] => Err(Error::new("no way to match rules to columns")),
+        _ => Err(Error::new("more than one way to match rules to columns")),
+    }
+}
+
+/// Tries every free column for each rule in `order` in turn, adding each complete matching to
+/// `matchings` and stopping once there are two.
+fn search(
+    order: &[usize],
+    columns: &mut Vec<Option<usize>>,
+    matchings: &mut Vec<Vec<usize>>,
+    fits: &dyn Fn(usize, usize) -> bool,
+) {
+    let (&rule, rest) = match order.split_first() {
+        Some(split) => split,
+        None => {
+            matchings.push(columns.iter().flatten().copied().collect());
+            return;
+        }
+    };
+    for column in 0..columns.len() {
+        if matchings.len() < 2 && !columns.contains(&Some(column)) && fits(rule, column) {
+            columns[rule] = Some(column);
+            search(rest, columns, matchings, fits);
+            columns[rule] = None;
+        }
+    }
+}
+
+const FIELDS: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+const LOWEST: usize = 25;
+const BAND: usize = 30;
+
+/// Rules for 20 fields, your ticket and `size` nearby tickets, about a quarter of them invalid.
+///
+/// Each field is given a rank, and the values in its column are drawn from a band of values for
+/// that rank. A field's rules allow its own band and every band above it, so the field with the
+/// top rank fits only its own column, the next only its own once that one is known, and so on.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let highest = LOWEST + BAND * FIELDS.len() - 1;
+    let mut ranks = (0..FIELDS.len()).collect::<Vec<_>>();
+    rng.shuffle(&mut ranks);
+    let rules = FIELDS
+        .iter()
+        .zip(&ranks)
+        .map(|(name, rank)| {
+            let low = LOWEST + BAND * rank;
+            let split = rng.range(low..=highest - 1);
+            format!("{}: {}-{} or {}-{}", name, low, split, split + 1, highest)
+        })
+        .collect::<Vec<_>>();
+    let mut columns = ranks;
+    rng.shuffle(&mut columns);
+
+    let ticket = |rng: &mut Rng, valid: bool| {
+        let mut values = columns
+            .iter()
+            .map(|rank| rng.range(LOWEST + BAND * rank..=LOWEST + BAND * (rank + 1) - 1))
+            .collect::<Vec<_>>();
+        if !valid {
+            let column = rng.below(values.len());
+            values[column] = match rng.chance(0.5) {
+                true => rng.range(1..=LOWEST - 1),
+                false => rng.range(highest + 1..=999),
+            };
+        }
+        values
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let yours = ticket(rng, true);
+    let nearby = (0..size.max(1))
+        .map(|i| {
+            let valid = i == 0 || rng.chance(0.75);
+            ticket(rng, valid)
+        })
+        .collect::<Vec<_>>();
+    format!(
+        "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+        rules.join("\n"),
+        yours,
+        nearby.join("\n")
+    )
 }
 
-pub fn run() -> (String, String, Duration) {
-    let start = Instant::now();
+/// Runs this day through [`Solution`].
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Parsed<'a> = (Vec<Row<'a>>, Ticket, Vec<Ticket>);
+
+    fn day(&self) -> usize {
+        16
+    }
+
+    fn title(&self) -> &'static str {
+        "Ticket Translation"
+    }
 
-    let (rows, ticket, tickets) = load_tickets(INPUT);
-    let (p1, valid) = part1(&rows, &tickets);
-    let p2 = part2(&rows, &valid, ticket);
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<(Vec<Row<'a>>, Ticket, Vec<Ticket>)> {
+        load_tickets(input)
+    }
+
+    fn part1(&self, (rows, _, tickets): &(Vec<Row<'_>>, Ticket, Vec<Ticket>)) -> Result<String> {
+        part1(rows, tickets).map(|n| n.to_string())
+    }
+
+    fn part2(
+        &self,
+        (rows, ticket, tickets): &(Vec<Row<'_>>, Ticket, Vec<Ticket>),
+    ) -> Result<String> {
+        let valid = valid_tickets(rows, tickets);
+        part2(rows, &valid, ticket.clone()).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
 
-    (p1.to_string(), p2.to_string(), start.elapsed())
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::days::day16::{load_tickets, part1, part2, reduce_to_unique, Range, Row};
+    use crate::years::y2020::day16::{
+        load_tickets, part1, part2, reduce_to_unique, reference_part1, reference_part2,
+        valid_tickets, Range, Row, FIELDS,
+    };
+    use proptest::prelude::*;
+
+    /// Rules for a few fields and a few tickets with values below `max`, your ticket first.
+    fn notes(max: usize) -> impl Strategy<Value = (Vec<[usize; 4]>, Vec<Vec<usize>>)> {
+        (1usize..6).prop_flat_map(move |fields| {
+            let rule = prop::array::uniform4(0..max).prop_map(|mut rule| {
+                rule.sort_unstable();
+                rule
+            });
+            (
+                prop::collection::vec(rule, fields),
+                prop::collection::vec(prop::collection::vec(0..max, fields), 2..12),
+            )
+        })
+    }
+
+    fn notes_input(rules: &[[usize; 4]], tickets: &[Vec<usize>]) -> String {
+        let rules = rules
+            .iter()
+            .enumerate()
+            .map(|(i, r)| {
+                let name = FIELDS[i * 7 % FIELDS.len()];
+                format!("{}: {}-{} or {}-{}", name, r[0], r[1], r[2], r[3])
+            })
+            .collect::<Vec<_>>();
+        let tickets = tickets
+            .iter()
+            .map(|t| {
+                t.iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+            rules.join("\n"),
+            tickets[0],
+            tickets[1..].join("\n")
+        )
+    }
 
     #[test]
     fn test_part1() {
@@ -208,7 +484,8 @@ mod tests {
             vec![38, 6, 12],
         ];
 
-        assert_eq!(part1(&rows, &tickets), (71, vec![&vec![7, 3, 47]]));
+        assert_eq!(part1(&rows, &tickets), Ok(71));
+        assert_eq!(valid_tickets(&rows, &tickets), vec![&vec![7, 3, 47]]);
     }
     #[test]
     fn test_generation() {
@@ -230,9 +507,10 @@ mod tests {
             },
         ];
 
-        let tickets = vec![vec![3, 9, 18], vec![15, 1, 5], vec![5, 14, 9]];
+        let tickets = [vec![3, 9, 18], vec![15, 1, 5], vec![5, 14, 9]];
 
-        reduce_to_unique(&rows, &(tickets.iter().collect::<Vec<_>>()));
+        let fields = reduce_to_unique(&rows, &(tickets.iter().collect::<Vec<_>>()));
+        assert_eq!(fields, Ok(vec!["row", "class", "seat"]));
     }
 
     #[test]
@@ -504,10 +782,10 @@ nearby tickets:
 269,382,107,487,233,532,58,471,787,69,216,895,596,314,284,194,662,842,52,839
 192,506,574,77,64,575,275,445,885,315,643,223,380,59,770,232,574,376,127,381
 198,890,449,749,80,275,114,305,131,754,943,150,211,140,561,761,58,4,158,210";
-        let (rows, ticket, tickets) = load_tickets(s);
+        let (rows, ticket, tickets) = load_tickets(s).expect("valid input");
 
-        let (_, valid) = part1(&rows, &tickets);
-        let res = part2(&rows, &valid, ticket);
+        let valid = valid_tickets(&rows, &tickets);
+        let res = part2(&rows, &valid, ticket).expect("fields are unambiguous");
         println!("{}", res);
     }
 
@@ -778,9 +1056,71 @@ nearby tickets:
 897,569,266,908,801,859,861,609,690,120,142,209,195,558,833,85,797,472,503,167
 910,282,800,842,182,146,88,210,768,133,276,815,349,787,582,283,172,833,942,712
 431,411,560,940,410,553,928,492,838,623,277,646,737,134,169,23,845,552,826,93";
-        let (rows, ticket, tickets) = load_tickets(s);
-        let (_, valid) = part1(&rows, &tickets);
-        let p2 = part2(&rows, &valid, ticket);
+        let (rows, ticket, tickets) = load_tickets(s).expect("valid input");
+        let valid = valid_tickets(&rows, &tickets);
+        let p2 = part2(&rows, &valid, ticket).expect("fields are unambiguous");
         println!("{}", p2)
     }
+    #[test]
+    fn test_huge_values() {
+        let input = format!(
+            "departure time: 0-{0} or 0-0\ndeparture date: 0-10 or 12-{0}\n\n\
+            your ticket:\n{1},{1}\n\nnearby tickets:\n11,1\n{2},{2}",
+            usize::MAX - 1,
+            1usize << 32,
+            usize::MAX
+        );
+        let (rows, ticket, tickets) = load_tickets(&input).expect("valid input");
+        assert!(part1(&rows, &tickets).is_err());
+        assert!(reference_part1(&input).is_err());
+        let valid = valid_tickets(&rows, &tickets);
+        assert_eq!(valid, vec![&vec![11, 1]]);
+        assert!(part2(&rows, &valid, ticket).is_err());
+        assert!(reference_part2(&input).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_notes_round_trip((rules, tickets) in notes(60)) {
+            let input = notes_input(&rules, &tickets);
+            let (rows, ticket, nearby) = load_tickets(&input).expect("valid input");
+            let ranges = rows
+                .iter()
+                .map(|row| [row.a.min, row.a.max, row.b.min, row.b.max])
+                .collect::<Vec<_>>();
+            prop_assert_eq!(ranges, rules);
+            prop_assert_eq!(&ticket, &tickets[0]);
+            prop_assert_eq!(&nearby, &tickets[1..]);
+        }
+
+        #[test]
+        fn test_parts_match_reference((rules, tickets) in notes(60)) {
+            let input = notes_input(&rules, &tickets);
+            let (rows, ticket, nearby) = load_tickets(&input).expect("valid input");
+            prop_assert_eq!(part1(&rows, &nearby), reference_part1(&input));
+            let valid = valid_tickets(&rows, &nearby);
+            for t in &nearby {
+                let fits = t.iter().all(|&v| rows.iter().any(|row| row.valid(v)));
+                prop_assert_eq!(valid.contains(&t), fits);
+            }
+            // part 2 only answers when the fields can be matched up just one way
+            if let Ok(answer) = part2(&rows, &valid, ticket) {
+                prop_assert_eq!(Ok(answer), reference_part2(&input));
+            }
+        }
+
+        #[test]
+        fn test_huge_values_match_reference((rules, tickets) in notes(usize::MAX)) {
+            let input = notes_input(&rules, &tickets);
+            let (rows, ticket, nearby) = load_tickets(&input).expect("valid input");
+            prop_assert_eq!(
+                part1(&rows, &nearby).is_ok(),
+                reference_part1(&input).is_ok()
+            );
+            let valid = valid_tickets(&rows, &nearby);
+            if let Ok(answer) = part2(&rows, &valid, ticket) {
+                prop_assert_eq!(Ok(answer), reference_part2(&input));
+            }
+        }
+    }
 }
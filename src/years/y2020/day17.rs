@@ -0,0 +1,283 @@
+//! Day 17: [Conway Cubes](https://adventofcode.com/2020/day/17).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::collections::{HashMap, HashSet};
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/17.txt");
+
+/// Largest starting slice that can grow for six cycles without overflowing the `i8` coordinates.
+const MAX_SIZE: usize = 100;
+
+/// Parses the starting slice into the coordinates of its active cubes.
+pub fn load_world(input: &str) -> Result<HashSet<(i8, i8, i8)>> {
+    let mut world = HashSet::new();
+    for (i, line) in input.lines().enumerate() {
+        for (j, c) in line.char_indices() {
+            if i >= MAX_SIZE || j >= MAX_SIZE {
+                return Err(Error::at(
+                    input,
+                    &line[j..],
+                    format!("starting slice must be at most {0}x{0}", MAX_SIZE),
+                ));
+            }
+            match c {
+                '#' => {
+                    world.insert((i as i8, j as i8, 0));
+                }
+                '.' => (),
+                _ => return Err(Error::at(input, &line[j..], format!("unexpected {:?}", c))),
+            }
+        }
+    }
+    Ok(world)
+}
+
+/// How many cubes are active after six cycles in three dimensions.
+pub fn part1(mut world: HashSet<(i8, i8, i8)>) -> usize {
+    let mut neighbour_count: HashMap<(i8, i8, i8), usize> = HashMap::new();
+
+    for &tile in &world {
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    if dx == 0 && dy == 0 && dz == 0 {
+                        continue;
+                    }
+                    let tile: (i8, i8, i8) = (tile.0 + dx, tile.1 + dy, tile.2 + dz);
+                    *neighbour_count.entry(tile).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    for _ in 0..6 {
+        let mut next_world = HashSet::new();
+        let mut next_neighbour_count: HashMap<(i8, i8, i8), usize> = HashMap::new();
+        for (&tile, &neighbours) in &neighbour_count {
+            if neighbours == 3 || (neighbours == 2 && world.contains(&tile)) {
+                next_world.insert(tile);
+                for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        for dz in -1..=1 {
+                            if dx == 0 && dy == 0 && dz == 0 {
+                                continue;
+                            }
+                            let neighbour = (tile.0 + dx, tile.1 + dy, tile.2 + dz);
+                            *next_neighbour_count.entry(neighbour).or_insert(0) += 1;
+                        }
+                    }
+                }
+            }
+        }
+        neighbour_count = next_neighbour_count;
+        world = next_world;
+    }
+
+    world.len()
+}
+
+fn increment_neighbours(
+    tile: (i8, i8, i8, i8),
+    neighbour_count: &mut HashMap<(i8, i8, i8, i8), usize>,
+) {
+    (-1..=1)
+        .flat_map(|dx| (-1..=1).map(move |dy| (dx, dy)))
+        .flat_map(|(dx, dy)| (-1..=1).map(move |dz| (dx, dy, dz)))
+        .flat_map(|(dx, dy, dz)| (-1..=1).map(move |dw| (dx, dy, dz, dw)))
+        .filter(|&(x, y, z, w)| x != 0 || y != 0 || z != 0 || w != 0)
+        .for_each(|(dx, dy, dz, dw)| {
+            let tile: (i8, i8, i8, i8) = (tile.0 + dx, tile.1 + dy, tile.2 + dz, tile.3 + dw);
+            *neighbour_count.entry(tile).or_insert(0) += 1;
+        });
+}
+
+/// How many cubes are active after six cycles in four dimensions.
+pub fn part2(world: HashSet<(i8, i8, i8)>) -> usize {
+    let mut world = {
+        let mut w = HashSet::with_capacity(world.len());
+        for (i, j, k) in world {
+            w.insert((i, j, k, 0));
+        }
+        w
+    };
+
+    let mut neighbour_count: HashMap<(i8, i8, i8, i8), usize> = HashMap::new();
+
+    world
+        .iter()
+        .for_each(|&t| increment_neighbours(t, &mut neighbour_count));
+
+    for _ in 0..6 {
+        let mut next_world = HashSet::new();
+        let mut next_neighbour_count: HashMap<(i8, i8, i8, i8), usize> = HashMap::new();
+        for (&tile, &neighbours) in &neighbour_count {
+            if neighbours == 3 || (neighbours == 2 && world.contains(&tile)) {
+                next_world.insert(tile);
+                increment_neighbours(tile, &mut next_neighbour_count)
+            }
+        }
+        neighbour_count = next_neighbour_count;
+        world = next_world;
+    }
+
+    world.len()
+}
+
+/// Runs six cycles the slow way in `dims` dimensions, up to four, checking every cube in the box
+/// around the active ones and counting its neighbours one by one.
+fn reference_cycles(input: &str, dims: usize) -> Result<usize> {
+    if input.lines().count() > MAX_SIZE || input.lines().any(|line| line.len() > MAX_SIZE) {
+        return Err(Error::new("starting slice is too big"));
+    }
+    let mut active = HashSet::new();
+    for (i, line) in input.lines().enumerate() {
+        for (j, c) in line.chars().enumerate() {
+            match c {
+                '#' => {
+                    active.insert([i as i64, j as i64, 0, 0]);
+                }
+                '.' => (),
+                _ => return Err(Error::new("slices are made of # and .")),
+            }
+        }
+    }
+
+    // unused dimensions stay at 0 with a bound of 0..=0
+    let spread = |d: usize| if d < dims { 1 } else { 0 };
+    let offsets = (0..81)
+        .map(|n| [n % 3 - 1, n / 3 % 3 - 1, n / 9 % 3 - 1, n / 27 - 1])
+        .filter(|offset| (0..4).all(|d| offset[d] == 0 || d < dims) && offset != &[0; 4])
+        .collect::<Vec<_>>();
+    for _ in 0..6 {
+        let bounds = (0..4)
+            .map(|d| {
+                let values = active.iter().map(|cube: &[i64; 4]| cube[d]);
+                let min = values.clone().min().unwrap_or(0) - spread(d);
+                min..=(values.max().unwrap_or(0) + spread(d))
+            })
+            .collect::<Vec<_>>();
+        let mut next = HashSet::new();
+        for x in bounds[0].clone() {
+            for y in bounds[1].clone() {
+                for z in bounds[2].clone() {
+                    for w in bounds[3].clone() {
+                        let cube = [x, y, z, w];
+                        let neighbours = offsets
+                            .iter()
+                            .filter(|offset| {
+                                let neighbour = [0, 1, 2, 3].map(|d| cube[d] + offset[d]);
+                                active.contains(&neighbour)
+                            })
+                            .count();
+                        if neighbours == 3 || (neighbours == 2 && active.contains(&cube)) {
+                            next.insert(cube);
+                        }
+                    }
+                }
+            }
+        }
+        active = next;
+    }
+    Ok(active.len())
+}
+
+/// Part 1 the slow way, in three dimensions.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    reference_cycles(input, 3)
+}
+
+/// Part 2 the slow way, in four dimensions.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    reference_cycles(input, 4)
+}
+
+/// A square starting slice `size` cubes across, up to 100, about a third of them active.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.clamp(1, MAX_SIZE);
+    (0..side)
+        .map(|_| {
+            (0..side)
+                .map(|_| if rng.chance(0.35) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed<'a> = HashSet<(i8, i8, i8)>;
+
+    fn day(&self) -> usize {
+        17
+    }
+
+    fn title(&self) -> &'static str {
+        "Conway Cubes"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<HashSet<(i8, i8, i8)>> {
+        load_world(input)
+    }
+
+    fn part1(&self, world: &HashSet<(i8, i8, i8)>) -> Result<String> {
+        Ok(part1(world.clone()).to_string())
+    }
+
+    fn part2(&self, world: &HashSet<(i8, i8, i8)>) -> Result<String> {
+        Ok(part2(world.clone()).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn max_size(&self) -> Option<usize> {
+        Some(MAX_SIZE)
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day17::{load_world, part1, part2, INPUT};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_actual() {
+        let world = load_world(INPUT).expect("valid input");
+        assert_eq!(part1(world.clone()), 286);
+        assert_eq!(part2(world), 960);
+    }
+
+    #[test]
+    fn test_example() {
+        let s = ".#.
+..#
+###";
+        let world = load_world(s).expect("valid input");
+        let expected = vec![(0, 1, 0), (1, 2, 0), (2, 0, 0), (2, 1, 0), (2, 2, 0)]
+            .into_iter()
+            .collect::<HashSet<_>>();
+        assert_eq!(world, expected);
+
+        assert_eq!(part1(world.clone()), 112);
+        assert_eq!(part2(world), 848);
+    }
+}
@@ -0,0 +1,412 @@
+//! Day 18: [Operation Order](https://adventofcode.com/2020/day/18).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::collections::VecDeque;
+use std::str::Chars;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/18.txt");
+
+/// An operator in an expression.
+#[derive(PartialEq, Debug)]
+pub enum Op {
+    /// `+`
+    Add,
+    /// `*`
+    Mul,
+}
+
+impl Op {
+    /// Applies the operator, failing if the result overflows.
+    pub fn apply(&self, a: usize, b: usize) -> Result<usize> {
+        match self {
+            Op::Add => a.checked_add(b),
+            Op::Mul => a.checked_mul(b),
+        }
+        .ok_or_else(|| Error::new("result overflowed"))
+    }
+}
+
+/// Evaluates the values and operators of a flat expression, in the order they were written.
+pub type Evaluator = fn(VecDeque<usize>, VecDeque<Op>) -> Result<usize>;
+
+/// Deepest parentheses can nest, which keeps [`evaluate`] from overflowing the stack.
+pub const MAX_DEPTH: usize = 1000;
+
+/// Checks that a line is a well formed expression with parentheses nested at most
+/// [`MAX_DEPTH`] deep, so evaluating it can only fail on overflow.
+pub fn check_line(input: &str, line: &str) -> Result<()> {
+    let mut depth = 0usize;
+    let mut expect_value = true;
+    for (i, c) in line.char_indices() {
+        let ok = match c {
+            ' ' => true,
+            '*' | '+' => !std::mem::replace(&mut expect_value, true),
+            '(' if depth >= MAX_DEPTH => {
+                let message = format!("parentheses nest more than {} deep", MAX_DEPTH);
+                return Err(Error::at(input, &line[i..], message));
+            }
+            '(' => {
+                depth += 1;
+                expect_value
+            }
+            ')' => {
+                let ok = depth > 0 && !expect_value;
+                depth = depth.saturating_sub(1);
+                ok
+            }
+            c if c.is_ascii_digit() => std::mem::replace(&mut expect_value, false),
+            _ => false,
+        };
+        if !ok {
+            return Err(Error::at(input, &line[i..], format!("unexpected {:?}", c)));
+        }
+    }
+    match (expect_value, depth) {
+        (true, _) => Err(Error::at(input, line, "expression is incomplete")),
+        (_, 0) => Ok(()),
+        _ => Err(Error::at(input, line, "unclosed parenthesis")),
+    }
+}
+
+/// Parses one expression per line, checking each with [`check_line`].
+pub fn load_lines(input: &str) -> Result<Vec<&str>> {
+    input
+        .lines()
+        .map(|line| check_line(input, line).map(|_| line))
+        .collect()
+}
+
+/// Evaluates the expression in `line` up to the end or an unmatched `)`, using `evaluator` for
+/// each parenthesised group. Fails if parentheses nest more than [`MAX_DEPTH`] deep.
+pub fn evaluate(line: &mut Chars, evaluator: Evaluator) -> Result<usize> {
+    evaluate_nested(line, evaluator, 0)
+}
+
+fn evaluate_nested(line: &mut Chars, evaluator: Evaluator, depth: usize) -> Result<usize> {
+    if depth > MAX_DEPTH {
+        return Err(Error::new(format!(
+            "parentheses nest more than {} deep",
+            MAX_DEPTH
+        )));
+    }
+    let mut vals = VecDeque::new();
+    let mut ops = VecDeque::new();
+
+    while let Some(char) = line.next() {
+        match char {
+            ' ' => continue,
+            '*' => ops.push_back(Op::Mul),
+            '+' => ops.push_back(Op::Add),
+            '(' => vals.push_back(evaluate_nested(line, evaluator, depth + 1)?),
+            ')' => break,
+            c if c.is_ascii_digit() => vals.push_back(c as usize - '0' as usize),
+            _ => return Err(Error::new(format!("unexpected {:?}", char))),
+        }
+    }
+
+    evaluator(vals, ops)
+}
+
+fn missing_operand() -> Error {
+    Error::new("operator is missing an operand")
+}
+
+/// Evaluates left to right, with `+` and `*` taking the same precedence.
+pub fn simple_eval(mut vals: VecDeque<usize>, mut ops: VecDeque<Op>) -> Result<usize> {
+    while let Some(op) = ops.pop_front() {
+        let a = vals.pop_front().ok_or_else(missing_operand)?;
+        let b = vals.pop_front().ok_or_else(missing_operand)?;
+        vals.push_front(op.apply(a, b)?);
+    }
+    vals.pop_front().ok_or_else(missing_operand)
+}
+
+/// Evaluates with `+` taking precedence over `*`.
+pub fn advanced_eval(mut vals: VecDeque<usize>, mut ops: VecDeque<Op>) -> Result<usize> {
+    while let Some(ind) = ops
+        .iter()
+        .enumerate()
+        .find(|(_, op)| **op == Op::Add)
+        .map(|(i, _)| i)
+    {
+        let a = vals.remove(ind).ok_or_else(missing_operand)?;
+        let b = vals.remove(ind).ok_or_else(missing_operand)?;
+        vals.insert(ind, Op::Add.apply(a, b)?);
+        ops.remove(ind);
+    }
+    vals.into_iter().try_fold(1, |acc, v| Op::Mul.apply(acc, v))
+}
+
+fn sum(lines: &[&str], evaluator: Evaluator) -> Result<usize> {
+    lines.iter().try_fold(0, |acc, line| {
+        Op::Add.apply(acc, evaluate(&mut line.chars(), evaluator)?)
+    })
+}
+
+/// The sum of the expressions, evaluated with [`simple_eval`].
+pub fn part1(lines: &[&str]) -> Result<usize> {
+    sum(lines, simple_eval)
+}
+
+/// The sum of the expressions, evaluated with [`advanced_eval`].
+pub fn part2(lines: &[&str]) -> Result<usize> {
+    sum(lines, advanced_eval)
+}
+
+/// Evaluates an expression without parentheses, given as alternating numbers and operators.
+/// Additions bind tighter if `addition_first`, and otherwise everything goes left to right.
+fn reference_flat(tokens: &[String], addition_first: bool) -> Result<usize> {
+    let number = |token: &String| {
+        token
+            .parse::<usize>()
+            .map_err(|_| Error::new(format!("expected a number, not {:?}", token)))
+    };
+    let first = tokens.first().ok_or_else(missing_operand)?;
+    let mut terms = vec![number(first)?];
+    for pair in tokens[1..].chunks(2) {
+        let value = number(pair.get(1).ok_or_else(missing_operand)?)?;
+        let last = terms.last_mut().expect("starts with a term");
+        match pair[0].as_str() {
+            "+" => *last = Op::Add.apply(*last, value)?,
+            "*" if addition_first => terms.push(value),
+            "*" => *last = Op::Mul.apply(*last, value)?,
+            op => return Err(Error::new(format!("expected an operator, not {:?}", op))),
+        }
+    }
+    terms
+        .into_iter()
+        .try_fold(1, |acc, v| Op::Mul.apply(acc, v))
+}
+
+/// Evaluates every line the slow way, replacing innermost parentheses by their value until there
+/// are none left.
+fn reference_sum(input: &str, addition_first: bool) -> Result<usize> {
+    input.lines().try_fold(0, |acc, line| {
+        let mut tokens = line
+            .chars()
+            .filter(|&c| c != ' ')
+            .map(String::from)
+            .collect::<Vec<_>>();
+        while let Some(close) = tokens.iter().position(|t| t == ")") {
+            let open = tokens[..close]
+                .iter()
+                .rposition(|t| t == "(")
+                .ok_or_else(|| Error::new("unopened parenthesis"))?;
+            let value = reference_flat(&tokens[open + 1..close], addition_first)?;
+            tokens.splice(open..=close, [value.to_string()]);
+        }
+        Op::Add.apply(acc, reference_flat(&tokens, addition_first)?)
+    })
+}
+
+/// Part 1 the slow way, with every operator going left to right.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    reference_sum(input, false)
+}
+
+/// Part 2 the slow way, adding before multiplying.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    reference_sum(input, true)
+}
+
+/// Most digits in one expression. Neither order of operations can make `n` digits worth more
+/// than `9^n`, so this keeps the sum of a million lines well within 64 bits.
+const MAX_DIGITS: usize = 12;
+
+/// An expression of at most `digits` digits, with groups nested at most `depth` deep. Returns
+/// the expression and how many digits it used.
+fn expression(rng: &mut Rng, digits: usize, depth: usize) -> (String, usize) {
+    let mut res = String::new();
+    let mut used = 0;
+    for i in 0..rng.range(2..=4) {
+        if used >= digits {
+            break;
+        }
+        if i > 0 {
+            res.push_str(if rng.chance(0.5) { " + " } else { " * " });
+        }
+        if depth > 0 && digits - used >= 2 && rng.chance(0.3) {
+            let (group, n) = expression(rng, digits - used, depth - 1);
+            res.push('(');
+            res.push_str(&group);
+            res.push(')');
+            used += n;
+        } else {
+            res.push((b'1' + rng.below(9) as u8) as char);
+            used += 1;
+        }
+    }
+    (res, used)
+}
+
+/// `size` expressions, each nested up to three groups deep.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| expression(rng, MAX_DIGITS, 3).0)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn day(&self) -> usize {
+        18
+    }
+
+    fn title(&self) -> &'static str {
+        "Operation Order"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<&'a str>> {
+        load_lines(input)
+    }
+
+    fn part1(&self, lines: &Vec<&'_ str>) -> Result<String> {
+        part1(lines).map(|n| n.to_string())
+    }
+
+    fn part2(&self, lines: &Vec<&'_ str>) -> Result<String> {
+        part2(lines).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day18::{
+        advanced_eval, evaluate, load_lines, part1, part2, simple_eval, INPUT, MAX_DEPTH,
+    };
+
+    #[test]
+    fn test_example_simple() {
+        let s = "1 + 2 * 3 + 4 * 5 + 6";
+        assert_eq!(evaluate(&mut s.chars(), simple_eval), Ok(71));
+
+        let s = "1 + (2 * 3) + (4 * (5 + 6))";
+        assert_eq!(evaluate(&mut s.chars(), simple_eval), Ok(51));
+    }
+
+    #[test]
+    fn test_example_adv() {
+        let s = "1 + (2 * 3) + (4 * (5 + 6))";
+        assert_eq!(evaluate(&mut s.chars(), advanced_eval), Ok(51));
+
+        let s = "2 * 3 + (4 * 5)";
+        assert_eq!(evaluate(&mut s.chars(), advanced_eval), Ok(46));
+
+        let s = "1 + 2 * 3 + 4 * 5 + 6";
+        assert_eq!(evaluate(&mut s.chars(), advanced_eval), Ok(231));
+    }
+
+    #[test]
+    fn test_hamzaan() {
+        let s = "2 * 3 + 5 + ((7 * 8 + 8 * 5 * 8 * 9) + 2 * 4 + 2 + 8)
+(8 + (9 * 9)) + (6 + 2 * 7)
+2 + 5 + 4 + 8 + 4
+8 + 2 + ((5 * 5 + 5) + (6 * 8 + 6 * 3) * 8) * 7 + 6
+9 * ((2 + 5) + 2 + 8 * (5 + 7 + 5 * 3) * 6) + 9
+9 + 6 + ((9 * 7 * 7) + 5 * 9 + 8 * (4 * 9 + 3 + 3) * 5) + 7 * 2
+(3 * 3 + 5 + 6 + 5 * 5) + 8 * 4 * 4 + 2
+7 * 2 + 3 + 9 * 4 + 4
+5 + (2 + 9) + 5 + 3 + 8 * 6
+9 * 3 + 5 + 3 * (6 * (4 * 8 + 6 * 9 * 9 + 6) * 7 + (9 * 2 * 3 + 7))
+7 * 8 + 3 + (7 + 3 + 9 + 7 * 2) + (9 + 6 * 3 * 3)
+((9 * 7) + 2) + 9 * (4 + 4)
+((9 + 5) * 2 + (2 + 4 * 4 * 3 + 9 * 9)) + (7 + (7 * 6 + 4) + (9 + 7) * 9) + 8 + 9
+4 + 7 * 3 + 2 * (7 + 2 * 8 + 9 * 3 * 3)
+5 + 3
+(2 + 3 * 5 * 6 * 2 * 2) + (8 * 8 + 3 * 3 + 7 * (7 + 3 + 9 * 8)) + 7 + 5
+(7 + 3 * 5) * 7
+4 * 6 + (6 + 7 + 9 * (3 + 4 * 4) + 3 + 2) * (6 + 8 * 9 * 6 * 5 + (2 * 5 * 8 + 8 + 5 * 2))
+8 * (7 + 5 * 8 * 7 + 6) * ((4 + 5) * 9 + 9 * 2 * 7) * 5 + 8
+6 + 8 * 5
+(8 * (7 * 4) + 5 * 9 * 9 + 3) * 7
+8 + 5 + 7 + (8 + (6 + 5) + 7 + (7 * 9 * 2 * 6 + 4) * 8) * 2
+((4 + 3 * 9 + 9) + 4 * (9 + 2 + 4 + 8 + 9) + 5 + 7 * 4) + 6 * 9
+9 + (9 * 8 * 3) * (8 * 6 * 7 * 8 * 6)
+5 * 7 * (9 * 3 + 6 + 8 + 8 + 5)
+2 + (2 + 7 * (5 * 7 * 3 + 2 * 7 * 8) + 7 + (5 + 8 * 5)) * 8 * 3 * 3 + ((6 * 5 * 8 + 8 + 9) + 6 * (8 + 3) * 6)
+9 * 5 * 9 * 8
+6 + 5 + 7 + 2 + (8 + (5 * 5 * 7 * 3 + 7 + 7) + 2 + 7)
+4 + 4 * 2 + 3 * 7 + 6
+4 + (5 + 3 + 2 * 8 * 5) + 2
+2 * 2 * 8 + (6 + 9 * 4 * 7 * (4 + 7 * 7 * 2) + 2) + 9
+4 * 7 * 8 + (3 * (9 * 2 + 4 * 4 * 5 * 5) + 5 * 2 * 7 + 7) + 8 + 5
+(8 + (7 + 9)) * 6
+9 + ((8 * 7 + 5 * 3) + 7 + (6 * 4 * 6 + 8)) * 5
+3 + (6 + 5 * 7 + 4 * 2) * (3 * 3 + 7 * 6 + 4 * (6 + 7 * 3)) * (4 + 8 * 9)
+7 * (7 + (4 * 3) + 4 + 5 * 4)
+8 * 2 * (9 * 7 + 5 + 8) + 4 * 8 + (3 * 3)
+7 * (3 + 2 * 3) + ((2 * 8) * 4) * 8
+((8 + 3 + 4 + 7 * 5 * 6) + (3 + 2) * (8 + 6 * 9 * 4 + 9 + 2)) + 3 + 8 * (8 + (4 + 9 * 2 + 8 + 6 + 9) * 9 * 3 * 3 + 3) + 9
+4 * (7 * 4 * 8 * 5) + 7 * 8 * (2 * 4 * 5 * 4)
+(3 + 8 + 6 * 6 * 4) * (4 * 6 + 8 + 3 * 6) * 5
+2 + (3 + (6 + 9 + 6 * 3) * 7 + (9 + 7 + 8)) + 9 * 7
+9 + 7 * (4 * 8 + 3 * 2 * 9 + 4) + ((6 * 8) + 3 + 7 + 7 * 8 * (9 + 5)) * 9 + ((3 * 9 * 8 * 8) + (5 + 2) + 2 * 4 * 3 + 8)
+6 * 3 + 3 * (8 * 7 + (2 * 8 + 4 + 6 + 5 * 6) + 3 + 3 * (9 * 9 * 8 + 2 * 5)) + (3 + (3 + 5 + 6) + 8 + 9 * 4) + 8
+9 * ((7 * 6) * 9 * 2) * ((8 * 9 * 3 + 8 * 2) * 6 + 8 + 9 * 5 + 3) + (5 * 4) * (7 + 4) * 7
+(4 * 7 + 8 * 9) + (7 + (3 + 9 * 2 * 9 * 4) * 9 * 3 * 4 * 7) * 4 * 6 + 2
+6 + 9 * 6 + (2 + 9 * 4) * 8
+3 * (6 * 5 + (7 * 6 * 5 * 8) + 7 * 5 * 4) + (3 * (6 * 5 + 7 * 9) + (4 * 9 + 2 * 3) * 7 * 8 * 3) + 9 + 5 + 9
+3 + (2 + (6 * 3 * 8 + 3) + 4) * (8 * 9) * 7 + 6
+(2 * (7 * 9)) * 6 + 7 * 2 + 9 * 4
+((5 + 4 * 2) + (3 * 2 * 6 + 3 + 5 * 5) * 4 + (2 + 5 * 9 * 8 + 3 * 5) * (7 * 2 * 8 * 2) + (5 * 4)) + 7 * 8 + 9
+(8 + 4 + (5 * 4 * 6) * 2 * 8) * (6 + (9 * 4 * 5) * 7 * 5 * 4) + 3 + ((2 + 2) + 7 + 5 * 7 * 2 + 4) * 5 + (9 * 3 * (9 + 3 * 4 * 8 * 7 + 9))
+6 * 7 * 3 * (7 * 2 + (6 * 4 * 5) * (5 * 3 * 8 + 8 + 3 + 9)) * (7 * 9 + 7 * 5)
+4 + 2 + (3 * (9 + 7) * 6)
+(8 + (8 * 4)) + (7 + 3 * 5) + 4 + 4 + 7
+(7 + 7 * 4 * 4 * 4) * 4 + 3".lines();
+        s.for_each(|l| println!("{:?}", evaluate(&mut l.chars(), simple_eval)));
+    }
+
+    #[test]
+    fn test_errors() {
+        let lines = load_lines(INPUT).expect("valid input");
+        assert!(part1(&lines).is_ok() && part2(&lines).is_ok());
+
+        let err = load_lines("1 + 2\n3 * (4 + 5").expect_err("unclosed");
+        assert_eq!(err.position.map(|p| p.line), Some(2));
+        let err = load_lines("1 + * 2").expect_err("missing operand");
+        assert_eq!(err.position.map(|p| p.column), Some(5));
+        assert!(load_lines("1 + 2)").is_err());
+        assert!(load_lines("1 +").is_err());
+        assert!(load_lines("12 + 3").is_err());
+        assert!(load_lines("1 - 3").is_err());
+    }
+
+    #[test]
+    fn test_nesting() {
+        let nested = |depth| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        let deep = nested(MAX_DEPTH);
+        let lines = load_lines(&deep).expect("deep but allowed");
+        assert_eq!(part1(&lines), Ok(1));
+        assert_eq!(part2(&lines), Ok(1));
+
+        let err = load_lines(&nested(MAX_DEPTH + 1)).expect_err("too deep");
+        assert_eq!(err.position.map(|p| p.column), Some(MAX_DEPTH + 1));
+        let too_deep = nested(MAX_DEPTH * 10);
+        assert!(evaluate(&mut too_deep.chars(), simple_eval).is_err());
+    }
+}
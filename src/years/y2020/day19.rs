@@ -0,0 +1,785 @@
+//! Day 19: [Monster Messages](https://adventofcode.com/2020/day/19).
+
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use arrayvec::ArrayVec;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/19.txt");
+
+#[derive(Clone, Debug, PartialEq)]
+enum Matcher {
+    Literal(char),
+    Recurse,
+    Grammar(Rc<Grammar>),
+}
+
+/// A compiled rule: a message matches if it matches any of the rule's alternatives.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grammar {
+    matchers: ArrayVec<[Vec<Matcher>; 2]>,
+}
+
+/// Deepest a message can be matched through rules inside rules before matching gives up, which
+/// keeps long chains of rules and long messages for looping rules from overflowing the stack.
+pub const MAX_DEPTH: usize = 1000;
+
+impl Grammar {
+    /// Whether the whole of `s` matches. Fails if matching goes more than [`MAX_DEPTH`] rules deep.
+    pub fn matches(&self, s: &str) -> Result<bool> {
+        Ok(self.part_match(s, 0)?.into_iter().any(|s| s.is_empty()))
+    }
+
+    fn part_match<'b>(&self, s: &'b str, depth: usize) -> Result<Vec<&'b str>> {
+        let mut res = Vec::new();
+
+        if s.is_empty() {
+            return Ok(res);
+        }
+        if depth >= MAX_DEPTH {
+            return Err(Error::new(format!(
+                "matching went more than {} rules deep",
+                MAX_DEPTH
+            )));
+        }
+
+        'matcher_loop: for matcher in &self.matchers {
+            let mut potential = vec![s];
+            for criteria in matcher {
+                let mut new_pot = Vec::new();
+                match criteria {
+                    Matcher::Literal(c) => {
+                        new_pot.extend(potential.iter().filter_map(|p| p.strip_prefix(*c)));
+                    }
+                    Matcher::Recurse => {
+                        for p in &potential {
+                            new_pot.extend(self.part_match(p, depth + 1)?);
+                        }
+                    }
+                    Matcher::Grammar(g) => {
+                        for p in &potential {
+                            new_pot.extend(g.part_match(p, depth + 1)?);
+                        }
+                    }
+                }
+                if new_pot.is_empty() {
+                    continue 'matcher_loop;
+                }
+                potential = new_pot;
+            }
+
+            res.extend(potential);
+        }
+
+        Ok(res)
+    }
+}
+
+fn too_many_alternatives(input: &str, line: &str) -> Error {
+    Error::at(input, line, "a rule can have at most two alternatives")
+}
+
+/// Parses the numbered rules and compiles rule 0, returning it with the messages to check.
+/// Rules may refer to themselves, but not first thing in an alternative, and not to each other
+/// in a cycle. Rules can nest at most [`MAX_DEPTH`] deep.
+pub fn load_input(input: &str) -> Result<(Rc<Grammar>, Vec<&str>)> {
+    let (samples, ruleset): (Vec<&str>, Vec<&str>) = input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .partition(|line| line.starts_with('a') || line.starts_with('b'));
+
+    let mut grammars = HashMap::with_capacity(ruleset.len());
+    let mut to_do = HashMap::with_capacity(ruleset.len());
+
+    let mut lines = HashMap::with_capacity(ruleset.len());
+
+    for line in ruleset {
+        let (rule_num, terms) = line
+            .split_once(": ")
+            .ok_or_else(|| Error::at(input, line, "expected \"N: rule\""))?;
+        let rule_num = error::parse::<usize>(input, rule_num)?;
+        lines.insert(rule_num, line);
+
+        let entry = grammars.entry(rule_num).or_insert(Grammar {
+            matchers: ArrayVec::new(),
+        });
+        let to_apply = to_do.entry(rule_num).or_insert_with(Vec::new);
+
+        let mut curr = Vec::new();
+
+        for term in terms.split(' ') {
+            if term.starts_with('\"') {
+                let literal = term
+                    .strip_prefix('\"')
+                    .and_then(|t| t.strip_suffix('\"'))
+                    .filter(|t| t.chars().count() == 1)
+                    .and_then(|t| t.chars().next())
+                    .ok_or_else(|| Error::at(input, term, "expected a single quoted character"))?;
+                entry
+                    .matchers
+                    .try_push(vec![Matcher::Literal(literal)])
+                    .map_err(|_| too_many_alternatives(input, line))?;
+            } else if term == "|" {
+                to_apply.push(curr.clone());
+                curr.clear();
+            } else {
+                curr.push(error::parse::<usize>(input, term)?);
+            }
+        }
+        if !curr.is_empty() {
+            to_apply.push(curr.clone());
+        }
+    }
+
+    if !to_do.contains_key(&0) {
+        return Err(Error::new("there is no rule 0"));
+    }
+
+    let mut done: HashMap<usize, Rc<Grammar>> = HashMap::with_capacity(to_do.len());
+    let mut depths = HashMap::with_capacity(to_do.len());
+
+    while !to_do.is_empty() {
+        let ind = to_do
+            .iter()
+            .find(|(ind, grammar)| {
+                grammar
+                    .iter()
+                    .all(|g| g.iter().all(|i| done.contains_key(i) || **ind == *i))
+            })
+            .map(|(&ind, _)| ind);
+        let ind = match ind {
+            Some(ind) => ind,
+            None => {
+                // Either a rule refers to a missing rule, or rules refer to each other in a cycle.
+                let (ind, missing) = to_do
+                    .iter()
+                    .flat_map(|(ind, patterns)| patterns.iter().flatten().map(move |i| (ind, i)))
+                    .find(|(_, i)| !lines.contains_key(i))
+                    .map_or_else(
+                        || (to_do.keys().min().copied().unwrap_or(0), None),
+                        |(&ind, &i)| (ind, Some(i)),
+                    );
+                let message = match missing {
+                    Some(i) => format!("rule {} refers to missing rule {}", ind, i),
+                    None => format!("rule {} is part of a cycle of rules", ind),
+                };
+                return Err(Error::at(input, lines[&ind], message));
+            }
+        };
+
+        let line = lines[&ind];
+        let patterns = to_do.remove(&ind).unwrap_or_default();
+        let mut grammar = grammars.remove(&ind).unwrap_or(Grammar {
+            matchers: ArrayVec::new(),
+        });
+        let mut depth = 1;
+        for pattern in patterns {
+            // matching would recurse without using up any of the message, and never finish
+            if pattern.first() == Some(&ind) {
+                return Err(Error::at(
+                    input,
+                    line,
+                    format!("rule {} refers to itself before matching anything", ind),
+                ));
+            }
+            let mut created_pattern = Vec::new();
+            for subpattern_id in &pattern {
+                depth = depth.max(depths.get(subpattern_id).map_or(0, |d| d + 1));
+                match done.get(subpattern_id) {
+                    None => created_pattern.push(Matcher::Recurse),
+                    Some(subpattern) => {
+                        // a recursing rule can't be copied in, as it would recurse into this one
+                        if subpattern.matchers.len() == 1
+                            && !subpattern.matchers[0].contains(&Matcher::Recurse)
+                        {
+                            created_pattern.extend(subpattern.matchers[0].clone());
+                        } else {
+                            created_pattern.push(Matcher::Grammar(Rc::clone(subpattern)))
+                        }
+                    }
+                };
+            }
+            grammar
+                .matchers
+                .try_push(created_pattern)
+                .map_err(|_| too_many_alternatives(input, line))?;
+        }
+
+        if depth > MAX_DEPTH {
+            return Err(Error::at(
+                input,
+                line,
+                format!("rules nest more than {} deep", MAX_DEPTH),
+            ));
+        }
+        depths.insert(ind, depth);
+        done.insert(ind, Rc::new(grammar));
+    }
+
+    Ok((Rc::clone(&done[&0]), samples))
+}
+
+/// Replaces rules 8 and 11 with the looping versions part 2 asks for.
+pub fn loop_rules(input: &str) -> String {
+    input
+        .lines()
+        .map(|line| match line {
+            "8: 42" => "8: 42 | 42 8",
+            "11: 42 31" => "11: 42 31 | 42 11 31",
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Rule 0 as written and with [`loop_rules`] applied, and the messages to check.
+pub struct Rules<'a> {
+    grammar: Rc<Grammar>,
+    looped: Rc<Grammar>,
+    messages: Vec<&'a str>,
+}
+
+/// Parses the rules both as written and with [`loop_rules`] applied.
+pub fn load_rules(input: &str) -> Result<Rules<'_>> {
+    let (grammar, messages) = load_input(input)?;
+    let (looped, _) = load_input(&loop_rules(input))?;
+    Ok(Rules {
+        grammar,
+        looped,
+        messages,
+    })
+}
+
+/// How many of the messages in `to_check` match `rules`.
+pub fn solver(rules: Rc<Grammar>, to_check: &[&str]) -> Result<usize> {
+    to_check
+        .iter()
+        .try_fold(0, |count, t| Ok(count + rules.matches(t)? as usize))
+}
+
+/// A rule read the slow way: a letter, or alternatives that are each rules in a row.
+enum Reference {
+    Literal(char),
+    Alternatives(Vec<Vec<usize>>),
+}
+
+/// Reads the rules and messages the slow way, with each rule kept as written.
+#[allow(clippy::type_complexity)]
+fn reference_rules(input: &str) -> Result<(HashMap<usize, Reference>, Vec<Vec<char>>)> {
+    let (rules, messages) = input
+        .split_once("\n\n")
+        .ok_or_else(|| Error::new("rules and messages must be split by a blank line"))?;
+    let mut parsed = HashMap::new();
+    for line in rules.lines() {
+        let (number, rule) = line
+            .split_once(": ")
+            .ok_or_else(|| Error::new("rules look like \"N: rule\""))?;
+        let number = number
+            .parse::<usize>()
+            .map_err(|_| Error::new("rule numbers must be numbers"))?;
+        let chars = rule.chars().collect::<Vec<_>>();
+        let rule = match chars.as_slice() {
+            ['"', c, '"'] => Reference::Literal(*c),
+            _ => Reference::Alternatives(
+                rule.split(" | ")
+                    .map(|alternative| {
+                        alternative
+                            .split(' ')
+                            .map(|n| n.parse().map_err(|_| Error::new("bad rule number")))
+                            .collect()
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+        };
+        if parsed.insert(number, rule).is_some() {
+            return Err(Error::new(format!("rule {} appears twice", number)));
+        }
+    }
+    let messages = messages
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    match messages.iter().flatten().all(|c| *c == 'a' || *c == 'b') {
+        true => Ok((parsed, messages)),
+        false => Err(Error::new("messages must be made of a and b")),
+    }
+}
+
+/// Every `j` where `rule` matches `message[i..j]`, remembering them in `ends`. Fails on a rule
+/// that could refer back to itself without matching anything first.
+fn reference_ends(
+    rules: &HashMap<usize, Reference>,
+    message: &[char],
+    rule: usize,
+    i: usize,
+    ends: &mut HashMap<(usize, usize), Option<Vec<usize>>>,
+) -> Result<Vec<usize>> {
+    match ends.get(&(rule, i)) {
+        Some(Some(found)) => return Ok(found.clone()),
+        Some(None) => return Err(Error::new(format!("rule {} loops", rule))),
+        None => ends.insert((rule, i), None),
+    };
+    let found = match rules.get(&rule) {
+        None => return Err(Error::new(format!("there is no rule {}", rule))),
+        Some(Reference::Literal(c)) => match message.get(i) == Some(c) {
+            true => vec![i + 1],
+            false => vec![],
+        },
+        Some(Reference::Alternatives(alternatives)) => {
+            let mut found = Vec::new();
+            for alternative in alternatives {
+                let mut at = vec![i];
+                for &sub in alternative {
+                    let mut next = Vec::new();
+                    for start in at {
+                        next.extend(reference_ends(rules, message, sub, start, ends)?);
+                    }
+                    at = next;
+                }
+                found.extend(at);
+            }
+            found.sort_unstable();
+            found.dedup();
+            found
+        }
+    };
+    ends.insert((rule, i), Some(found.clone()));
+    Ok(found)
+}
+
+/// How many messages match rule 0, found the slow way after `change` has had its go at the rules.
+fn reference_count(
+    input: &str,
+    change: fn(&mut HashMap<usize, Reference>) -> Result<()>,
+) -> Result<usize> {
+    let (mut rules, messages) = reference_rules(input)?;
+    change(&mut rules)?;
+    let mut count = 0;
+    for message in messages {
+        let ends = reference_ends(&rules, &message, 0, 0, &mut HashMap::new())?;
+        if ends.contains(&message.len()) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Part 1 the slow way, finding everywhere each rule could end at each point in a message.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    reference_count(input, |_| Ok(()))
+}
+
+/// Part 2 the slow way, like [`reference_part1`] once rules 8 and 11 loop. Fails unless they were
+/// `8: 42` and `11: 42 31` to begin with, as the puzzle expects.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    reference_count(input, |rules| {
+        for (rule, was, looped) in [
+            (8, vec![42], vec![42, 8]),
+            (11, vec![42, 31], vec![42, 11, 31]),
+        ] {
+            match rules.get_mut(&rule) {
+                Some(Reference::Alternatives(alternatives))
+                    if alternatives == std::slice::from_ref(&was) =>
+                {
+                    alternatives.push(looped)
+                }
+                _ => return Err(Error::new(format!("rule {} isn't as part 2 expects", rule))),
+            }
+        }
+        Ok(())
+    })
+}
+
+/// A rule while generating, with its alternatives as indices of other rules.
+enum GenRule {
+    Literal(char),
+    Alternatives(Vec<Vec<usize>>),
+}
+
+/// Length of the messages rules 42 and 31 match.
+const CHUNK: usize = 8;
+
+/// A message matching rule `rule`.
+fn message(rng: &mut Rng, rules: &[GenRule], rule: usize, res: &mut String) {
+    match &rules[rule] {
+        GenRule::Literal(c) => res.push(*c),
+        GenRule::Alternatives(alternatives) => {
+            for &sub in rng.pick(alternatives) {
+                message(rng, rules, sub, res);
+            }
+        }
+    }
+}
+
+/// Rules and `size` messages, where rules 42 and 31 each match some messages of eight letters
+/// and rules 0, 8 and 11 are as in the puzzle. A third of the messages match rule 0, a third
+/// only match once rules 8 and 11 loop, and the rest are random.
+///
+/// Every rule matching more than one letter either is two rules in a row or is `a` then one rule
+/// or `b` then another, so a message can only match a rule one way and matching stays fast.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut rules = vec![
+        GenRule::Literal('a'),
+        GenRule::Literal('b'),
+        GenRule::Alternatives(vec![vec![0], vec![1]]),
+    ];
+    // rules matching messages of each length, starting from 1
+    let mut by_length = vec![vec![0, 1, 2]];
+    for length in 2..=CHUNK {
+        let mut made = Vec::new();
+        for i in 0..3 {
+            let rule = match i == 0 || rng.chance(0.5) {
+                true => vec![
+                    vec![0, *rng.pick(&by_length[length - 2])],
+                    vec![1, *rng.pick(&by_length[length - 2])],
+                ],
+                false => {
+                    let split = rng.range(1..=length - 1);
+                    vec![vec![
+                        *rng.pick(&by_length[split - 1]),
+                        *rng.pick(&by_length[length - split - 1]),
+                    ]]
+                }
+            };
+            made.push(rules.len());
+            rules.push(GenRule::Alternatives(rule));
+        }
+        by_length.push(made);
+    }
+    let chunks = &by_length[CHUNK - 1];
+    let (r42, r31) = (chunks[0], *rng.pick(&chunks[1..]));
+
+    let mut numbers = (1..rules.len() * 2)
+        .filter(|n| ![8, 11, 42, 31].contains(n))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut numbers);
+    numbers[r42] = 42;
+    numbers[r31] = 31;
+    let mut lines = rules
+        .iter()
+        .enumerate()
+        .map(|(i, rule)| match rule {
+            GenRule::Literal(c) => format!("{}: \"{}\"", numbers[i], c),
+            GenRule::Alternatives(alternatives) => format!(
+                "{}: {}",
+                numbers[i],
+                alternatives
+                    .iter()
+                    .map(|a| {
+                        a.iter()
+                            .map(|&sub| numbers[sub].to_string())
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            ),
+        })
+        .collect::<Vec<_>>();
+    lines.extend(
+        ["0: 8 11", "8: 42", "11: 42 31"]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    rng.shuffle(&mut lines);
+
+    let messages = (0..size.max(1))
+        .map(|_| {
+            let mut res = String::new();
+            let (fours, threes) = match rng.below(3) {
+                0 => (2, 1),
+                1 => {
+                    let threes = rng.range(1..=3);
+                    (rng.range(threes + 1..=5), threes)
+                }
+                _ => {
+                    for _ in 0..CHUNK * rng.range(2..=5) + rng.below(3) {
+                        res.push(if rng.chance(0.5) { 'a' } else { 'b' });
+                    }
+                    (0, 0)
+                }
+            };
+            for _ in 0..fours {
+                message(rng, &rules, r42, &mut res);
+            }
+            for _ in 0..threes {
+                message(rng, &rules, r31, &mut res);
+            }
+            res
+        })
+        .collect::<Vec<_>>();
+    format!("{}\n\n{}", lines.join("\n"), messages.join("\n"))
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed<'a> = Rules<'a>;
+
+    fn day(&self) -> usize {
+        19
+    }
+
+    fn title(&self) -> &'static str {
+        "Monster Messages"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Rules<'a>> {
+        load_rules(input)
+    }
+
+    fn part1(&self, rules: &Rules<'_>) -> Result<String> {
+        solver(Rc::clone(&rules.grammar), &rules.messages).map(|n| n.to_string())
+    }
+
+    fn part2(&self, rules: &Rules<'_>) -> Result<String> {
+        solver(Rc::clone(&rules.looped), &rules.messages).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day19::{
+        load_input, load_rules, loop_rules, reference_part1, reference_part2, solver, Grammar,
+        Matcher, INPUT, MAX_DEPTH,
+    };
+    use proptest::prelude::*;
+    use std::rc::Rc;
+
+    /// Rules that each refer only to later ones, ending in `"a"` and `"b"`, as the alternatives
+    /// of each rule before those two.
+    fn rules() -> impl Strategy<Value = Vec<Vec<Vec<usize>>>> {
+        (2usize..8).prop_flat_map(|count| {
+            (0..count - 2)
+                .map(|i| {
+                    let sequence = prop::collection::vec(i + 1..count, 1..=3);
+                    prop::collection::vec(sequence, 1..=2)
+                })
+                .collect::<Vec<_>>()
+        })
+    }
+
+    /// The puzzle's rules 0, 8 and 11 around `rules` numbered from 100, with 42 and 31 as the first
+    /// two of them, then `messages`.
+    fn rules_input(rules: &[Vec<Vec<usize>>], messages: &[String]) -> String {
+        let mut lines = vec![
+            "0: 8 11".to_string(),
+            "8: 42".to_string(),
+            "11: 42 31".to_string(),
+            "42: 100".to_string(),
+            "31: 101".to_string(),
+        ];
+        for (i, alternatives) in rules.iter().enumerate() {
+            let alternatives = alternatives
+                .iter()
+                .map(|sequence| {
+                    sequence
+                        .iter()
+                        .map(|rule| (100 + rule).to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>();
+            lines.push(format!("{}: {}", 100 + i, alternatives.join(" | ")));
+        }
+        lines.push(format!("{}: \"a\"", 100 + rules.len()));
+        lines.push(format!("{}: \"b\"", 101 + rules.len()));
+        format!("{}\n\n{}", lines.join("\n"), messages.join("\n"))
+    }
+
+    #[test]
+    fn test_actual() {
+        let (rules, to_check) = load_input(INPUT).expect("valid input");
+        assert_eq!(solver(rules, &to_check), Ok(113));
+        let looped = loop_rules(INPUT);
+        let (rules, to_check) = load_input(&looped).expect("valid input");
+        assert_eq!(solver(rules, &to_check), Ok(253));
+    }
+
+    #[test]
+    fn test_parse() {
+        let s = "0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: \"a\"
+5: \"b\"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb";
+        let (grammar, _) = load_input(s).expect("valid input");
+        let expected = make_grammar();
+        assert_eq!(expected, grammar);
+    }
+
+    #[test]
+    fn test_errors() {
+        let message = |s| load_input(s).map(|_| ()).expect_err("invalid").to_string();
+        assert_eq!(
+            message("0: 1 2\n1: \"a\"\n\nab"),
+            "line 1, column 1: rule 0 refers to missing rule 2"
+        );
+        assert_eq!(
+            message("0: 1\n1: 0 | \"a\"\n\na"),
+            "line 1, column 1: rule 0 is part of a cycle of rules"
+        );
+        assert_eq!(message("1: \"a\"\n\na"), "there is no rule 0");
+        assert_eq!(
+            message("0: \"ab\""),
+            "line 1, column 4: expected a single quoted character"
+        );
+        assert_eq!(
+            message("0: 1 | 1 | 1\n1: \"a\""),
+            "line 1, column 1: a rule can have at most two alternatives"
+        );
+    }
+
+    #[test]
+    fn test_recursion() {
+        let message = |s| load_input(s).map(|_| ()).expect_err("invalid").to_string();
+        assert_eq!(
+            message("0: 0 1 | 1\n1: \"a\"\n\naa"),
+            "line 1, column 1: rule 0 refers to itself before matching anything"
+        );
+        assert_eq!(
+            message("0: 0\n\na"),
+            "line 1, column 1: rule 0 refers to itself before matching anything"
+        );
+        assert_eq!(
+            message("0: 2\n2: 1 | 2 1\n1: \"a\"\n\na"),
+            "line 2, column 1: rule 2 refers to itself before matching anything"
+        );
+
+        // recursing after matching something is fine, until the message is too long
+        let (grammar, to_check) = load_input("0: 1 0 | 1\n1: \"a\"\n\naaa\naab").expect("valid");
+        assert_eq!(solver(Rc::clone(&grammar), &to_check), Ok(1));
+        assert!(grammar.matches(&"a".repeat(MAX_DEPTH * 2)).is_err());
+
+        // copying in a recursing rule must not make it recurse into the rule it's copied into
+        let (grammar, to_check) =
+            load_input("0: 2 | 3\n2: 1 2\n3: 1 1\n1: \"a\"\n\naa\naaa").expect("valid");
+        assert_eq!(solver(grammar, &to_check), Ok(1));
+
+        let chain = (0..=MAX_DEPTH)
+            .map(|i| format!("{}: {} | {}", i, i + 1, i + 1))
+            .chain(std::iter::once(format!("{}: \"a\"", MAX_DEPTH + 1)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(message(&chain).ends_with(&format!("rules nest more than {} deep", MAX_DEPTH)));
+    }
+
+    #[test]
+    fn test_non_ascii() {
+        let (grammar, to_check) =
+            load_input("0: 1 2\n1: \"a\"\n2: \"é\"\n\naé\nab\naéé").expect("valid input");
+        assert_eq!(solver(grammar, &to_check), Ok(1));
+    }
+
+    #[test]
+    fn test_valid_check() {
+        let g = make_grammar();
+
+        let g = |s| g.matches(s).expect("shallow grammar");
+        assert!(!g("hello"));
+        assert!(g("aaaabb"));
+        assert!(g("aaabab"));
+        assert!(g("abbabb"));
+        assert!(g("abbbab"));
+        assert!(g("aabaab"));
+        assert!(g("aabbbb"));
+        assert!(g("abaaab"));
+        assert!(g("ababbb"));
+    }
+
+    fn make_grammar() -> Rc<Grammar> {
+        let r3 = Rc::new(Grammar {
+            matchers: vec![
+                vec![Matcher::Literal('a'), Matcher::Literal('b')],
+                vec![Matcher::Literal('b'), Matcher::Literal('a')],
+            ]
+            .into_iter()
+            .collect(),
+        });
+        let r2 = Rc::new(Grammar {
+            matchers: vec![
+                vec![Matcher::Literal('a'), Matcher::Literal('a')],
+                vec![Matcher::Literal('b'), Matcher::Literal('b')],
+            ]
+            .into_iter()
+            .collect(),
+        });
+        let r1 = Rc::new(Grammar {
+            matchers: vec![
+                vec![
+                    Matcher::Grammar(Rc::clone(&r2)),
+                    Matcher::Grammar(Rc::clone(&r3)),
+                ],
+                vec![
+                    Matcher::Grammar(Rc::clone(&r3)),
+                    Matcher::Grammar(Rc::clone(&r2)),
+                ],
+            ]
+            .into_iter()
+            .collect(),
+        });
+        Rc::new(Grammar {
+            matchers: vec![vec![
+                Matcher::Literal('a'),
+                Matcher::Grammar(Rc::clone(&r1)),
+                Matcher::Literal('b'),
+            ]]
+            .into_iter()
+            .collect(),
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_parts_match_reference(
+            rules in rules(),
+            messages in prop::collection::vec("[ab]{1,12}", 1..10),
+        ) {
+            let input = rules_input(&rules, &messages);
+            let (grammar, to_check) = load_input(&input).expect("valid input");
+            prop_assert_eq!(solver(grammar, &to_check), reference_part1(&input));
+            let looped = loop_rules(&input);
+            let (looped, to_check) = load_input(&looped).expect("valid input");
+            prop_assert_eq!(solver(looped, &to_check), reference_part2(&input));
+        }
+
+        #[test]
+        fn test_parse_never_panics(
+            input in "([0-9]{1,3}: (\"[ab]\"|[0-9 |]{0,10})\n){0,8}\n([ab]{0,8}\n){0,4}",
+        ) {
+            if let Ok(rules) = load_rules(&input) {
+                let _ = solver(rules.grammar, &rules.messages);
+                let _ = solver(rules.looped, &rules.messages);
+            }
+        }
+    }
+}
@@ -0,0 +1,1057 @@
+//! Day 20: [Jurassic Jigsaw](https://adventofcode.com/2020/day/20).
+
+use crate::crosscheck;
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt::{Debug, Formatter};
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/20.txt");
+
+/// A 10x10 image tile of `#` and `.` pixels, indexed by row and then column.
+#[derive(Default, Clone)]
+pub struct Tile([[char; 10]; 10]);
+
+impl Debug for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, line) in self.0.iter().enumerate() {
+            for char in line {
+                write!(f, "{}", char)?;
+            }
+            if i < self.0.len() - 1 {
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Tile {
+    /// The left edge, top to bottom.
+    pub fn left(&self) -> [char; 10] {
+        std::array::from_fn(|i| self.0[i][0])
+    }
+
+    /// The right edge, top to bottom.
+    pub fn right(&self) -> [char; 10] {
+        std::array::from_fn(|i| self.0[i][9])
+    }
+
+    /// The top edge, left to right.
+    pub fn top(&self) -> [char; 10] {
+        self.0[0]
+    }
+
+    /// The bottom edge, left to right.
+    pub fn bottom(&self) -> [char; 10] {
+        self.0[9]
+    }
+
+    /// The tile mirrored left to right.
+    pub fn flip_hori(&self) -> Tile {
+        let mut res = self.clone();
+        for row in res.0.iter_mut() {
+            row.reverse();
+        }
+        res
+    }
+
+    /// The tile turned a quarter turn clockwise.
+    pub fn rotate(&self) -> Tile {
+        let mut res = Tile::default();
+
+        for (i, row) in self.0.iter().enumerate() {
+            for (j, tile) in row.iter().enumerate() {
+                res.0[j][9 - i] = *tile;
+            }
+        }
+
+        res
+    }
+
+    /// All eight ways the tile can be turned and flipped.
+    pub fn rotations(&self) -> Vec<Tile> {
+        let mut res = Vec::with_capacity(8);
+
+        for mut tile in [self.clone(), self.flip_hori()] {
+            for _ in 0..4 {
+                let next = tile.rotate();
+                res.push(tile);
+                tile = next;
+            }
+        }
+
+        res
+    }
+}
+
+/// Parses a `Tile N:` header followed by the tile, where `tileset` is a slice of `input`.
+pub fn load_tile(input: &str, tileset: &str) -> Result<(usize, Tile)> {
+    let mut lines = tileset.lines();
+    let title = lines.next().unwrap_or(tileset);
+    let id = title
+        .strip_prefix("Tile ")
+        .and_then(|t| t.strip_suffix(':'))
+        .ok_or_else(|| Error::at(input, title, "expected \"Tile N:\""))?;
+    let id = error::parse::<usize>(input, id)?;
+
+    let mut tile = Tile::default();
+    let mut rows = 0;
+    for (i, line) in lines.enumerate() {
+        if i >= 10 || line.len() != 10 {
+            return Err(Error::at(input, line, "tiles must be 10x10"));
+        }
+        for (j, c) in line.char_indices() {
+            if c != '#' && c != '.' {
+                return Err(Error::at(input, &line[j..], format!("unexpected {:?}", c)));
+            }
+            tile.0[i][j] = c;
+        }
+        rows += 1;
+    }
+    match rows {
+        10 => Ok((id, tile)),
+        _ => Err(Error::at(input, title, "tiles must be 10x10")),
+    }
+}
+
+/// Parses tiles separated by blank lines, keyed by ID.
+pub fn load_tiles(input: &str) -> Result<HashMap<usize, Tile>> {
+    let mut res = HashMap::new();
+
+    for tileset in input.trim_end().split("\n\n") {
+        let (id, tile) = load_tile(input, tileset)?;
+        if res.insert(id, tile).is_some() {
+            return Err(Error::at(input, tileset, format!("duplicate tile {}", id)));
+        }
+    }
+
+    Ok(res)
+}
+
+/// The product of the IDs of the four corner tiles.
+pub fn part1(tiles: &HashMap<usize, Tile>) -> Result<usize> {
+    let mut seen_counts = HashMap::new();
+
+    for (id, tile) in tiles {
+        for rot in tile.rotations() {
+            seen_counts
+                .entry(rot.top())
+                .or_insert_with(HashSet::new)
+                .insert(*id);
+        }
+    }
+
+    let res = seen_counts
+        .iter()
+        .filter(|(_, v)| v.len() == 1)
+        .flat_map(|kv| kv.1.iter().copied())
+        .collect::<Vec<_>>();
+
+    let mut options = HashMap::with_capacity(res.len());
+    for id in res {
+        *options.entry(id).or_insert(0) += 1;
+    }
+
+    let corners = options
+        .iter()
+        .filter(|(_, v)| **v == 4)
+        .map(|kv| *kv.0)
+        .collect::<Vec<_>>();
+    if corners.len() != 4 {
+        return Err(Error::new(format!(
+            "expected 4 corner tiles, found {}",
+            corners.len()
+        )));
+    }
+    corner_product(&corners)
+}
+
+/// The product of the corner tiles' IDs.
+fn corner_product(corners: &[usize]) -> Result<usize> {
+    corners
+        .iter()
+        .try_fold(1usize, |product, &id| product.checked_mul(id))
+        .ok_or_else(|| Error::new("the corner tile IDs multiply to more than 64 bits"))
+}
+
+fn rotate(coords: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let x = coords.iter().fold(0, |acc, &(x, _)| Ord::max(acc, x));
+    coords.iter().map(|&(a, b)| (b, x - a)).collect()
+}
+
+fn mirror_flip(coords: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let x = coords.iter().fold(0, |acc, &(x, _)| Ord::max(acc, x));
+    coords.iter().map(|&(a, b)| (x - a, b)).collect()
+}
+
+fn edge_count(
+    tiles_that_have_this_edge: &HashMap<[char; 10], HashSet<usize>>,
+    edge: &[char; 10],
+) -> usize {
+    tiles_that_have_this_edge.get(edge).map_or(0, HashSet::len)
+}
+
+fn no_fit(i: usize, j: usize) -> Error {
+    Error::new(format!("no tile fits at row {}, column {}", i + 1, j + 1))
+}
+
+/// How many `#` are not part of a sea monster once the tiles are assembled into a square.
+pub fn part2(tiles: &HashMap<usize, Tile>) -> Result<usize> {
+    let side = (tiles.len() as f64).sqrt().round() as usize;
+    if side == 0 || side * side != tiles.len() {
+        return Err(Error::new(format!(
+            "expected a square number of tiles, found {}",
+            tiles.len()
+        )));
+    }
+
+    let mut tiles_that_have_this_edge: HashMap<[char; 10], HashSet<usize>> =
+        HashMap::with_capacity(tiles.len());
+
+    for (id, tile) in tiles {
+        for tile in tile.rotations() {
+            tiles_that_have_this_edge
+                .entry(tile.top())
+                .or_default()
+                .insert(*id);
+        }
+    }
+
+    let singular_solutions = tiles_that_have_this_edge
+        .iter()
+        .filter(|(_, v)| v.len() == 1)
+        .flat_map(|kv| kv.1.iter().copied())
+        .collect::<Vec<_>>();
+
+    let mut options = HashMap::with_capacity(singular_solutions.len());
+    for id in singular_solutions {
+        *options.entry(id).or_insert(0) += 1;
+    }
+
+    let (&corner_id, corner) = options
+        .iter()
+        .filter(|(_, v)| **v == 4)
+        .find_map(|(id, _)| tiles.get_key_value(id))
+        .ok_or_else(|| Error::new("no corner tile found"))?;
+
+    let left_corner = corner
+        .rotations()
+        .into_iter()
+        .find(|t| {
+            edge_count(&tiles_that_have_this_edge, &t.left()) == 1
+                && edge_count(&tiles_that_have_this_edge, &t.top()) == 1
+        })
+        .ok_or_else(|| no_fit(0, 0))?;
+
+    let mut grid = vec![vec![Tile::default(); side]; side];
+    grid[0][0] = left_corner;
+
+    let mut placed = HashSet::new();
+    placed.insert(corner_id);
+
+    let unplaced = |placed: &HashSet<usize>, edge: &[char; 10]| {
+        tiles_that_have_this_edge
+            .get(edge)
+            .and_then(|ids| ids.iter().find(|id| !placed.contains(*id)))
+            .copied()
+    };
+
+    for i in 1..side {
+        let above = grid[i - 1][0].bottom();
+
+        let connecting_id = unplaced(&placed, &above).ok_or_else(|| no_fit(i, 0))?;
+        placed.insert(connecting_id);
+
+        grid[i][0] = tiles[&connecting_id]
+            .rotations()
+            .into_iter()
+            .find(|rotation| {
+                rotation.top() == above
+                    && edge_count(&tiles_that_have_this_edge, &rotation.left()) == 1
+            })
+            .ok_or_else(|| no_fit(i, 0))?;
+    }
+
+    for j in 1..side {
+        let to_left = grid[0][j - 1].right();
+
+        let connecting_id = unplaced(&placed, &to_left).ok_or_else(|| no_fit(0, j))?;
+        placed.insert(connecting_id);
+
+        grid[0][j] = tiles[&connecting_id]
+            .rotations()
+            .into_iter()
+            .find(|rotation| {
+                rotation.left() == to_left
+                    && edge_count(&tiles_that_have_this_edge, &rotation.top()) == 1
+            })
+            .ok_or_else(|| no_fit(0, j))?;
+    }
+
+    for i in 1..side {
+        for j in 1..side {
+            let top_edge = grid[i - 1][j].bottom();
+            let left_edge = grid[i][j - 1].right();
+
+            let connecting_id = match (
+                tiles_that_have_this_edge.get(&top_edge),
+                tiles_that_have_this_edge.get(&left_edge),
+            ) {
+                (Some(match_top), Some(match_left)) => {
+                    HashSet::intersection(match_top, match_left).next()
+                }
+                _ => None,
+            }
+            .ok_or_else(|| no_fit(i, j))?;
+
+            grid[i][j] = tiles[connecting_id]
+                .rotations()
+                .into_iter()
+                .find(|rotation| rotation.top() == top_edge && rotation.left() == left_edge)
+                .ok_or_else(|| no_fit(i, j))?;
+        }
+    }
+
+    let pixels = side * 8;
+    let mut fixed_grid = vec![vec![0 as char; pixels]; pixels];
+    for (x, row) in grid.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            for (w, tr) in tile.0[1..9].iter().enumerate() {
+                for (q, tt) in tr[1..9].iter().enumerate() {
+                    fixed_grid[x * 8 + w][y * 8 + q] = *tt;
+                }
+            }
+        }
+    }
+
+    let monster = "                  #
+#    ##    ##    ###
+ #  #  #  #  #  #   ";
+    let mut monster_coords = monster
+        .lines()
+        .enumerate()
+        .flat_map(|(i, line)| line.chars().enumerate().map(move |(j, c)| (i, j, c)))
+        .filter(|(_, _, c)| *c == '#')
+        .map(|(i, j, _)| (i, j))
+        .collect::<Vec<_>>();
+
+    // monsters can overlap, so count the pixels they cover rather than the monsters
+    let mut covered = HashSet::new();
+
+    for _ in 0..4 {
+        monster_coords = rotate(&monster_coords);
+
+        let max_coords = monster_coords
+            .iter()
+            .fold((0usize, 0usize), |(ax, ay), &(x, y)| {
+                (Ord::max(ax, x), Ord::max(ay, y))
+            });
+
+        for i in 0..pixels.saturating_sub(max_coords.0) {
+            for j in 0..pixels.saturating_sub(max_coords.1) {
+                if monster_coords
+                    .iter()
+                    .map(|(x, y)| (x + i, y + j))
+                    .all(|(i, j)| fixed_grid[i][j] == '#')
+                {
+                    covered.extend(monster_coords.iter().map(|(x, y)| (x + i, y + j)));
+                }
+            }
+        }
+
+        let monster_coords = mirror_flip(&monster_coords);
+
+        let max_coords = monster_coords
+            .iter()
+            .fold((0usize, 0usize), |(ax, ay), &(x, y)| {
+                (Ord::max(ax, x), Ord::max(ay, y))
+            });
+
+        for i in 0..pixels.saturating_sub(max_coords.0) {
+            for j in 0..pixels.saturating_sub(max_coords.1) {
+                if monster_coords
+                    .iter()
+                    .map(|(x, y)| (x + i, y + j))
+                    .all(|(i, j)| fixed_grid[i][j] == '#')
+                {
+                    covered.extend(monster_coords.iter().map(|(x, y)| (x + i, y + j)));
+                }
+            }
+        }
+    }
+
+    Ok(fixed_grid
+        .iter()
+        .flat_map(|row| row.iter())
+        .filter(|c| **c == '#')
+        .count()
+        - covered.len())
+}
+
+/// Every turned and flipped tile, indexed by its left and top edges, for fitting an image
+/// together the slow way.
+struct Pieces {
+    side: usize,
+    oriented: Vec<(usize, Tile)>,
+    by_left: HashMap<[char; 10], Vec<usize>>,
+    by_top: HashMap<[char; 10], Vec<usize>>,
+}
+
+impl Pieces {
+    /// The pieces that fit next after `placed`, which fill the square row by row.
+    fn candidates(&self, placed: &[usize]) -> Vec<usize> {
+        let at = placed.len();
+        let above = at
+            .checked_sub(self.side)
+            .map(|i| self.oriented[placed[i]].1.bottom());
+        let candidates = match (at % self.side, above) {
+            (0, None) => return (0..self.oriented.len()).collect(),
+            (0, Some(above)) => self.by_top.get(&above),
+            _ => self.by_left.get(&self.oriented[placed[at - 1]].1.right()),
+        };
+        let fits = |&&i: &&usize| above.is_none_or(|above| self.oriented[i].1.top() == above);
+        candidates
+            .into_iter()
+            .flatten()
+            .filter(fits)
+            .copied()
+            .collect()
+    }
+
+    /// Tries every unused candidate in the next free position, adding each full square to
+    /// `found` until there are more than `limit`.
+    fn place(
+        &self,
+        placed: &mut Vec<usize>,
+        used: &mut HashSet<usize>,
+        found: &mut Vec<Vec<(usize, Tile)>>,
+        limit: usize,
+    ) {
+        if placed.len() == self.side * self.side {
+            found.push(placed.iter().map(|&i| self.oriented[i].clone()).collect());
+            return;
+        }
+        for i in self.candidates(placed) {
+            if found.len() <= limit && used.insert(self.oriented[i].0) {
+                placed.push(i);
+                self.place(placed, used, found, limit);
+                placed.pop();
+                used.remove(&self.oriented[i].0);
+            }
+        }
+    }
+}
+
+/// Every way the tiles fit together into a square with matching edges, by trying each tile in
+/// each position in turn, stopping once there are more than `limit`.
+fn reference_arrangements(
+    tiles: &HashMap<usize, Tile>,
+    limit: usize,
+) -> Result<Vec<Vec<(usize, Tile)>>> {
+    let side = (1..=tiles.len())
+        .find(|side| side * side >= tiles.len())
+        .filter(|side| side * side == tiles.len())
+        .ok_or_else(|| Error::new("tiles must make a square"))?;
+    let oriented = tiles
+        .iter()
+        .flat_map(|(&id, tile)| tile.rotations().into_iter().map(move |t| (id, t)))
+        .collect::<Vec<_>>();
+    let mut pieces = Pieces {
+        side,
+        oriented,
+        by_left: HashMap::new(),
+        by_top: HashMap::new(),
+    };
+    for (i, (_, tile)) in pieces.oriented.iter().enumerate() {
+        pieces.by_left.entry(tile.left()).or_default().push(i);
+        pieces.by_top.entry(tile.top()).or_default().push(i);
+    }
+
+    let mut found = Vec::new();
+    pieces.place(&mut Vec::new(), &mut HashSet::new(), &mut found, limit);
+    Ok(found)
+}
+
+/// Any square of tiles can be turned and flipped eight ways, so only more than this many ways to
+/// fit them together means the input is ambiguous.
+const SYMMETRIES: usize = 8;
+
+/// Part 1 the slow way, fitting the whole image together and reading off its corners.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    let tiles = load_tiles(input)?;
+    let arrangements = reference_arrangements(&tiles, SYMMETRIES)?;
+    if arrangements.len() > SYMMETRIES {
+        return Err(Error::new("tiles fit together more than one way"));
+    }
+    crosscheck::only(
+        arrangements
+            .iter()
+            .map(|tiles| {
+                let side = (tiles.len() as f64).sqrt() as usize;
+                let corners = [0, side - 1, tiles.len() - side, tiles.len() - 1]
+                    .iter()
+                    .map(|&corner| tiles[corner].0)
+                    .collect::<Vec<_>>();
+                corner_product(&corners)
+            })
+            .collect::<Result<Vec<_>>>()?,
+    )
+}
+
+/// Part 2 the slow way, fitting the whole image together and marking every `#` that some sea
+/// monster covers, whichever way round it is.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    let tiles = load_tiles(input)?;
+    let arrangements = reference_arrangements(&tiles, SYMMETRIES)?;
+    if arrangements.len() > SYMMETRIES {
+        return Err(Error::new("tiles fit together more than one way"));
+    }
+    let monster = MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            line.char_indices()
+                .map(move |(j, c)| (i as i64, j as i64, c))
+        })
+        .filter(|&(_, _, c)| c == '#')
+        .map(|(i, j, _)| (i, j))
+        .collect::<Vec<_>>();
+    let monsters = (0..8)
+        .map(|turn| {
+            monster
+                .iter()
+                .map(|&(i, j)| {
+                    let (i, j) = if turn >= 4 { (i, -j) } else { (i, j) };
+                    (0..turn % 4).fold((i, j), |(i, j), _| (j, -i))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    crosscheck::only(arrangements.iter().map(|tiles| {
+        let side = (tiles.len() as f64).sqrt() as usize;
+        let mut image = vec![vec!['.'; side * 8]; side * 8];
+        for (n, (_, tile)) in tiles.iter().enumerate() {
+            for i in 0..8 {
+                for j in 0..8 {
+                    image[n / side * 8 + i][n % side * 8 + j] = tile.0[i + 1][j + 1];
+                }
+            }
+        }
+        let pixel = |i: i64, j: i64| {
+            let row = usize::try_from(i).ok().and_then(|i| image.get(i))?;
+            usize::try_from(j).ok().and_then(|j| row.get(j)).copied()
+        };
+        let mut covered = HashSet::new();
+        for monster in &monsters {
+            for i in -20..side as i64 * 8 + 20 {
+                for j in -20..side as i64 * 8 + 20 {
+                    let cells = monster.iter().map(|&(di, dj)| (i + di, j + dj));
+                    if cells.clone().all(|(i, j)| pixel(i, j) == Some('#')) {
+                        covered.extend(cells);
+                    }
+                }
+            }
+        }
+        image.iter().flatten().filter(|&&c| c == '#').count() - covered.len()
+    }))
+}
+
+/// Most tiles along each side of a generated image, as in the puzzle input. Edges have eight free
+/// pixels, which only go so far when every edge must be different.
+const MAX_SIDE: usize = 12;
+
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+fn pixel(rng: &mut Rng, p: f64) -> char {
+    match rng.chance(p) {
+        true => '#',
+        false => '.',
+    }
+}
+
+/// Edges between every pair of neighbouring tiles in a `side` by `side` square, as
+/// `(horizontal, vertical)` where
+/// `horizontal[i][j]` runs along the top of tile `(i, j)` and `vertical[i][j]` down its left.
+/// No edge reads the same both ways or matches any other edge either way round.
+#[allow(clippy::type_complexity)]
+fn edges(rng: &mut Rng, side: usize) -> (Vec<Vec<[char; 10]>>, Vec<Vec<[char; 10]>>) {
+    'attempt: loop {
+        let corners = (0..=side)
+            .map(|_| (0..=side).map(|_| pixel(rng, 0.5)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut seen = HashSet::new();
+        let mut edge = |rng: &mut Rng, from: char, to: char| {
+            for _ in 0..1000 {
+                let mut edge = [from; 10];
+                edge[9] = to;
+                for c in edge[1..9].iter_mut() {
+                    *c = pixel(rng, 0.5);
+                }
+                let mut reversed = edge;
+                reversed.reverse();
+                if edge != reversed && !seen.contains(&edge) && !seen.contains(&reversed) {
+                    seen.insert(edge);
+                    return Some(edge);
+                }
+            }
+            None
+        };
+        let mut horizontal = vec![Vec::new(); side + 1];
+        let mut vertical = vec![Vec::new(); side];
+        for i in 0..=side {
+            for j in 0..=side {
+                if j < side {
+                    match edge(rng, corners[i][j], corners[i][j + 1]) {
+                        Some(e) => horizontal[i].push(e),
+                        None => continue 'attempt,
+                    }
+                }
+                if i < side {
+                    match edge(rng, corners[i][j], corners[i + 1][j]) {
+                        Some(e) => vertical[i].push(e),
+                        None => continue 'attempt,
+                    }
+                }
+            }
+        }
+        return (horizontal, vertical);
+    }
+}
+
+/// Tiles with distinct IDs, randomly turned and flipped, that fit together into a square image
+/// `size` tiles across, from 2 up to 12, with sea monsters planted in about a quarter of the room.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size.clamp(2, MAX_SIDE);
+    let pixels = side * 8;
+    let mut image = (0..pixels)
+        .map(|_| (0..pixels).map(|_| pixel(rng, 0.4)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    // monsters are 3x20, so these slots never overlap
+    let mut slots = (0..pixels / 3)
+        .flat_map(|i| (0..pixels / 20).map(move |j| (i * 3, j * 20)))
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut slots);
+    for &(i, j) in slots.iter().take(slots.len() / 4) {
+        for (di, line) in MONSTER.iter().enumerate() {
+            for (dj, c) in line.chars().enumerate() {
+                if c == '#' {
+                    image[i + di][j + dj] = '#';
+                }
+            }
+        }
+    }
+
+    let (horizontal, vertical) = edges(rng, side);
+    let mut ids = (1000..10000).collect::<Vec<usize>>();
+    rng.shuffle(&mut ids);
+    let mut tiles = Vec::with_capacity(side * side);
+    for i in 0..side {
+        for j in 0..side {
+            let mut tile = Tile::default();
+            tile.0[0] = horizontal[i][j];
+            tile.0[9] = horizontal[i + 1][j];
+            for (k, row) in tile.0.iter_mut().enumerate() {
+                row[0] = vertical[i][j][k];
+                row[9] = vertical[i][j + 1][k];
+            }
+            for k in 1..9 {
+                for l in 1..9 {
+                    tile.0[k][l] = image[i * 8 + k - 1][j * 8 + l - 1];
+                }
+            }
+            let tile = rng.pick(&tile.rotations()).clone();
+            tiles.push(format!("Tile {}:\n{:?}", ids[i * side + j], tile));
+        }
+    }
+    rng.shuffle(&mut tiles);
+    tiles.join("\n\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed<'a> = HashMap<usize, Tile>;
+
+    fn day(&self) -> usize {
+        20
+    }
+
+    fn title(&self) -> &'static str {
+        "Jurassic Jigsaw"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<HashMap<usize, Tile>> {
+        load_tiles(input)
+    }
+
+    fn part1(&self, tiles: &HashMap<usize, Tile>) -> Result<String> {
+        part1(tiles).map(|n| n.to_string())
+    }
+
+    fn part2(&self, tiles: &HashMap<usize, Tile>) -> Result<String> {
+        part2(tiles).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn max_size(&self) -> Option<usize> {
+        Some(MAX_SIDE)
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generate::Rng;
+    use crate::years::y2020::day20::{
+        generate, load_tile, load_tiles, mirror_flip, part1, part2, reference_part1,
+        reference_part2, rotate, Tile,
+    };
+    use proptest::prelude::*;
+    use std::convert::TryInto;
+
+    fn tile() -> impl Strategy<Value = Tile> {
+        prop::collection::vec(any::<bool>(), 100).prop_map(|pixels| {
+            let mut tile = Tile::default();
+            for (i, &on) in pixels.iter().enumerate() {
+                tile.0[i / 10][i % 10] = if on { '#' } else { '.' };
+            }
+            tile
+        })
+    }
+
+    #[test]
+    fn can_rotate() {
+        let s = "Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###";
+        let tiles = load_tiles(s).expect("valid input");
+        let tile = tiles.get(&2311).expect("is a thing");
+        println!("{:?}", tile);
+        // println!("{:?}", tile.get_edges());
+    }
+
+    #[test]
+    fn test_errors() {
+        let message = |s| load_tiles(s).map(|_| ()).expect_err("invalid").to_string();
+        assert_eq!(message("Tle 1:"), "line 1, column 1: expected \"Tile N:\"");
+        assert_eq!(
+            message("Tile 1:\n..........\n...."),
+            "line 3, column 1: tiles must be 10x10"
+        );
+        assert_eq!(
+            message("Tile 1:\n....x....."),
+            "line 2, column 5: unexpected 'x'"
+        );
+
+        let two_tiles = format!("Tile 1:{0}\n\nTile 2:{0}", "\n..........".repeat(10));
+        let tiles = load_tiles(&two_tiles).expect("valid input");
+        assert_eq!(
+            part2(&tiles).map_err(|err| err.to_string()),
+            Err("expected a square number of tiles, found 2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sizes() {
+        for size in 2..=6 {
+            let input = generate(&mut Rng::new(size as u64), size);
+            let tiles = load_tiles(&input).expect("valid input");
+            assert_eq!(tiles.len(), size * size);
+            assert_eq!(part2(&tiles), reference_part2(&input), "size {}", size);
+        }
+    }
+
+    #[test]
+    fn test_corners() {
+        let input = generate(&mut Rng::new(2), 2);
+        let one = input.split("\n\n").next().expect("a tile");
+        let tiles = load_tiles(one).expect("valid input");
+        assert_eq!(
+            part1(&tiles).map_err(|err| err.to_string()),
+            Err("expected 4 corner tiles, found 0".to_string())
+        );
+
+        let huge = input
+            .split("\n\n")
+            .enumerate()
+            .map(|(i, tile)| {
+                let grid = tile.split_once('\n').expect("a title").1;
+                format!("Tile {}:\n{}", usize::MAX - i, grid)
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let tiles = load_tiles(&huge).expect("valid input");
+        assert!(part1(&tiles).is_err());
+        assert!(reference_part1(&huge).is_err());
+    }
+
+    #[test]
+    fn test_overlapping_monsters() {
+        // one of the planted monsters shares pixels with one that's there by chance
+        let input = generate(&mut Rng::new(8), 4);
+        let tiles = load_tiles(&input).expect("valid input");
+        assert_eq!(part2(&tiles), Ok(385));
+        assert_eq!(reference_part2(&input), Ok(385));
+    }
+
+    #[test]
+    fn find_monsters() {
+        let world = ".####...#####..#...###..
+#####..#..#.#.####..#.#.
+.#.#...#.###...#.##.##..
+#.#.##.###.#.##.##.#####
+..##.###.####..#.####.##
+...#.#..##.##...#..#..##
+#.##.#..#.#..#..##.#.#..
+.###.##.....#...###.#...
+#.####.#.#....##.#..#.#.
+##...#..#....#..#...####
+..#.##...###..#.#####..#
+....#.##.#.#####....#...
+..##.##.###.....#.##..#.
+#...#...###..####....##.
+.#.##...#.##.#.#.###...#
+#.###.#..####...##..#...
+#.###...#.##...#.######.
+.###.###.#######..#####.
+..##.#..#..#.#######.###
+#.#..##.########..#..##.
+#.#####..#.#...##..#....
+#....##..#.#########..##
+#...#.....#..##...###.##
+#..###....##.#...##.##.#"
+            .lines()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let monster = "                  #
+#    ##    ##    ###
+ #  #  #  #  #  #   ";
+        let monster_coords = monster
+            .lines()
+            .enumerate()
+            .flat_map(|(i, line)| line.chars().enumerate().map(move |(j, c)| (i, j, c)))
+            .filter(|(_, _, c)| *c == '#')
+            .map(|(i, j, _)| (i, j))
+            .collect::<Vec<_>>();
+
+        let max_coords = monster_coords
+            .iter()
+            .fold((0usize, 0usize), |(ax, ay), &(x, y)| {
+                (Ord::max(ax, x), Ord::max(ay, y))
+            });
+
+        let mut monster_count = 0;
+        for i in 0..=24 - max_coords.0 {
+            for j in 0..=24 - max_coords.1 {
+                if monster_coords
+                    .iter()
+                    .map(|(x, y)| (x + i, y + j))
+                    .all(|(i, j)| world[i][j] == '#')
+                {
+                    monster_count += 1;
+                }
+            }
+        }
+        assert_eq!(monster_count, 2);
+
+        assert_eq!(
+            world
+                .iter()
+                .flat_map(|line| line.iter())
+                .filter(|c| **c == '#')
+                .count()
+                - (monster_count * monster_coords.len()),
+            273
+        );
+    }
+
+    #[test]
+    fn test_rotations() {
+        let s: Vec<Vec<char>> = "abcdefghij
+abcdefghij
+abcdefghij
+abcdefghij
+abcdefghij
+abcdefghij
+abcdefghij
+abcdefghij
+abcdefghij
+abcdefghij"
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect();
+
+        let mut b: [[char; 10]; 10] = Default::default();
+        b.iter_mut()
+            .zip(s)
+            .for_each(|(q, w)| *q = w.try_into().expect("yes"));
+
+        let t = Tile(b);
+        println!("{:?}", t);
+        println!("{:?}", t.rotate());
+        println!("{:?}", t.rotate().rotate());
+        println!("{:?}", t.rotate().rotate().rotate());
+        println!("{:?}", t.flip_hori());
+        println!("{:?}", t.left());
+        println!("{:?}", t.right());
+        println!("{:?}", t.top());
+        println!("{:?}", t.bottom());
+    }
+
+    #[test]
+    fn test_rotate() {
+        let coords = vec![(0, 1), (1, 0), (2, 2)];
+        println!("{:?}", rotate(&coords));
+        println!("{:?}", mirror_flip(&coords));
+    }
+
+    #[test]
+    fn test_monster_rotations() {
+        let world = ".#.#..#.##...#.##..#####
+###....#.#....#..#......
+##.##.###.#.#..######...
+###.#####...#.#####.#..#
+##.#....#.##.####...#.##
+...########.#....#####.#
+....#..#...##..#.#.###..
+.####...#..#.....#......
+#..#.##..#..###.#.##....
+#.####..#.####.#.#.###..
+###.#.#...#.######.#..##
+#.####....##..########.#
+##..##.#...#...#.#.#.#..
+...#..#..#.#.##..###.###
+.#.#....#.##.#...###.##.
+###.#...#..#.##.######..
+.#.#.###.##.##.#..#.##..
+.####.###.#...###.#..#.#
+..#.#..#..#.#.#.####.###
+#..####...#.#.#.###.###.
+#####..#####...###....##
+#.##..#..#...#..####...#
+.#.###..##..##..####.##.
+...###...##...#...#..###"
+            .lines()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let monster = "                  #
+#    ##    ##    ###
+ #  #  #  #  #  #   ";
+        let mut monster_coords = monster
+            .lines()
+            .enumerate()
+            .flat_map(|(i, line)| line.chars().enumerate().map(move |(j, c)| (i, j, c)))
+            .filter(|(_, _, c)| *c == '#')
+            .map(|(i, j, _)| (i, j))
+            .collect::<Vec<_>>();
+
+        let mut monster_count = 0;
+
+        for _ in 0..4 {
+            monster_coords = rotate(&monster_coords);
+
+            let max_coords = monster_coords
+                .iter()
+                .fold((0usize, 0usize), |(ax, ay), &(x, y)| {
+                    (Ord::max(ax, x), Ord::max(ay, y))
+                });
+
+            for i in 0..24 - max_coords.0 {
+                for j in 0..24 - max_coords.1 {
+                    if monster_coords
+                        .iter()
+                        .map(|(x, y)| (x + i, y + j))
+                        .all(|(i, j)| world[i][j] == '#')
+                    {
+                        monster_count += 1;
+                    }
+                }
+            }
+
+            let monster_coords = mirror_flip(&monster_coords);
+
+            let max_coords = monster_coords
+                .iter()
+                .fold((0usize, 0usize), |(ax, ay), &(x, y)| {
+                    (Ord::max(ax, x), Ord::max(ay, y))
+                });
+
+            for i in 0..24 - max_coords.0 {
+                for j in 0..24 - max_coords.1 {
+                    if monster_coords
+                        .iter()
+                        .map(|(x, y)| (x + i, y + j))
+                        .all(|(i, j)| world[i][j] == '#')
+                    {
+                        monster_count += 1;
+                    }
+                }
+            }
+        }
+        println!("{}", monster_count);
+    }
+
+    proptest! {
+        #[test]
+        fn test_tile_round_trip(id in 0usize..10_000, tile in tile()) {
+            let input = format!("Tile {}:\n{:?}", id, tile);
+            let (loaded_id, loaded) = load_tile(&input, &input).expect("valid input");
+            prop_assert_eq!(loaded_id, id);
+            prop_assert_eq!(format!("{:?}", loaded), format!("{:?}", tile));
+        }
+
+        #[test]
+        fn test_turns_and_flips(tile in tile()) {
+            let turned = tile.rotate().rotate().rotate().rotate();
+            prop_assert_eq!(format!("{:?}", turned), format!("{:?}", tile));
+            let flipped = tile.flip_hori().flip_hori();
+            prop_assert_eq!(format!("{:?}", flipped), format!("{:?}", tile));
+            let rotations = tile.rotations();
+            prop_assert_eq!(rotations.len(), 8);
+            prop_assert_eq!(rotations[1].left(), tile.bottom());
+        }
+
+        #[test]
+        fn test_parts_match_reference(seed in any::<u64>(), size in 2usize..=3) {
+            let input = generate(&mut Rng::new(seed), size);
+            let tiles = load_tiles(&input).expect("valid input");
+            prop_assert_eq!(part1(&tiles), reference_part1(&input));
+            prop_assert_eq!(part2(&tiles), reference_part2(&input));
+        }
+    }
+}
@@ -0,0 +1,369 @@
+//! Day 21: [Allergen Assessment](https://adventofcode.com/2020/day/21).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::collections::{HashMap, HashSet};
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/21.txt");
+
+/// A food's ingredients and some of the allergens they contain.
+#[derive(Debug, Clone)]
+pub struct Food<'a> {
+    ingredients: HashSet<&'a str>,
+    allergens: HashSet<&'a str>,
+}
+
+/// Parses one food per line, e.g. `mxmxvkd kfcds (contains dairy, fish)`.
+pub fn load_foods(input: &str) -> Result<Vec<Food<'_>>> {
+    let mut res = Vec::new();
+    for line in input.lines() {
+        let (ingredients, allergens) = line
+            .split_once(" (contains ")
+            .and_then(|(i, a)| Some((i, a.strip_suffix(')')?)))
+            .ok_or_else(|| {
+                Error::at(input, line, "expected \"ingredients (contains allergens)\"")
+            })?;
+
+        let ingredients = ingredients.split(' ').collect::<HashSet<_>>();
+        let allergens = allergens.split(", ").collect::<HashSet<_>>();
+        if let Some(empty) = ingredients.iter().chain(&allergens).find(|s| s.is_empty()) {
+            return Err(Error::at(input, empty, "empty ingredient or allergen"));
+        }
+        res.push(Food {
+            ingredients,
+            allergens,
+        });
+    }
+    Ok(res)
+}
+
+/// Which ingredient contains each allergen, keyed by ingredient.
+pub fn identify_allergens<'a>(foods: &[Food<'a>]) -> Result<HashMap<&'a str, &'a str>> {
+    let mut known_allergen: HashMap<&str, &str> = HashMap::new();
+
+    let mut working_foods = foods.to_vec();
+
+    while working_foods.iter().any(|f| !f.allergens.is_empty()) {
+        // any allergen can narrow down to one ingredient, even if no food lists it alone
+        let allergens = working_foods
+            .iter()
+            .flat_map(|f| f.allergens.iter().copied())
+            .collect::<HashSet<_>>();
+
+        let mut solved = Vec::new();
+
+        for allergen in allergens {
+            let containing = working_foods
+                .iter()
+                .filter(|f| f.allergens.contains(&allergen))
+                .collect::<Vec<_>>();
+            let possible_ingredients =
+                containing
+                    .iter()
+                    .fold(containing[0].ingredients.clone(), |acc, food| {
+                        HashSet::intersection(&acc, &food.ingredients)
+                            .copied()
+                            .collect()
+                    });
+            if let [ingredient] = possible_ingredients.into_iter().collect::<Vec<_>>()[..] {
+                solved.push((allergen, ingredient));
+            }
+        }
+
+        if solved.is_empty() {
+            let mut unsolved = working_foods
+                .iter()
+                .flat_map(|f| f.allergens.iter().copied())
+                .collect::<Vec<_>>();
+            unsolved.sort_unstable();
+            unsolved.dedup();
+            return Err(Error::new(format!(
+                "could not tell which ingredients contain {}",
+                unsolved.join(", ")
+            )));
+        }
+
+        for (allergen, ingredient) in solved {
+            known_allergen.insert(ingredient, allergen);
+            for food in working_foods.iter_mut() {
+                food.ingredients.remove(ingredient);
+                food.allergens.remove(allergen);
+            }
+        }
+    }
+
+    Ok(known_allergen)
+}
+
+/// How many times ingredients without allergens appear.
+pub fn part1(foods: &[Food]) -> Result<usize> {
+    let known_allergen = identify_allergens(foods)?;
+
+    Ok(foods
+        .iter()
+        .map(|f| {
+            f.ingredients
+                .iter()
+                .filter(|i| !known_allergen.contains_key(*i))
+                .count()
+        })
+        .sum())
+}
+
+/// The ingredients with allergens, sorted by allergen and separated by commas.
+pub fn part2(foods: &[Food]) -> Result<String> {
+    let mut known_allergen = identify_allergens(foods)?;
+
+    let mut reversed = known_allergen
+        .iter_mut()
+        .map(|(k, v)| (v, k))
+        .collect::<Vec<_>>();
+    reversed.sort_by(|(aa, _), (ba, _)| aa.cmp(ba));
+    Ok(reversed.iter().map(|r| *r.1).collect::<Vec<_>>().join(","))
+}
+
+/// The one way of giving each allergen its own ingredient that every food agrees with, found the
+/// slow way by trying every ingredient for every allergen, with the foods as lists of words.
+#[allow(clippy::type_complexity)]
+fn reference_assign(input: &str) -> Result<(Vec<(Vec<&str>, Vec<&str>)>, Vec<(&str, &str)>)> {
+    let mut foods = Vec::new();
+    for line in input.lines() {
+        let (ingredients, allergens) = line
+            .strip_suffix(')')
+            .and_then(|line| line.split_once(" (contains "))
+            .ok_or_else(|| Error::new("foods look like \"ingredients (contains allergens)\""))?;
+        let ingredients = ingredients.split(' ').collect::<Vec<_>>();
+        let allergens = allergens.split(", ").collect::<Vec<_>>();
+        let distinct = |words: &[&str]| words.iter().collect::<HashSet<_>>().len() == words.len();
+        let empty = ingredients.iter().chain(&allergens).any(|w| w.is_empty());
+        if empty || !distinct(&ingredients) || !distinct(&allergens) {
+            return Err(Error::new("foods list distinct ingredients and allergens"));
+        }
+        foods.push((ingredients, allergens));
+    }
+
+    let mut allergens = foods
+        .iter()
+        .flat_map(|(_, a)| a.clone())
+        .collect::<Vec<_>>();
+    allergens.sort_unstable();
+    allergens.dedup();
+    let mut ingredients = foods
+        .iter()
+        .flat_map(|(i, _)| i.clone())
+        .collect::<Vec<_>>();
+    ingredients.sort_unstable();
+    ingredients.dedup();
+
+    let mut found = Vec::new();
+    assign(
+        &foods,
+        &allergens,
+        &ingredients,
+        &mut Vec::new(),
+        &mut found,
+    );
+    match found.len() {
+        1 => Ok((foods, found.remove(0))),
+        0 => Err(Error::new("no way to give every allergen an ingredient")),
+        _ => Err(Error::new(
+            "more than one way to give allergens ingredients",
+        )),
+    }
+}
+
+/// Tries every unused ingredient for the next allergen, adding each complete assignment to
+/// `found` and stopping once there are two.
+fn assign<'a>(
+    foods: &[(Vec<&'a str>, Vec<&'a str>)],
+    allergens: &[&'a str],
+    ingredients: &[&'a str],
+    assigned: &mut Vec<(&'a str, &'a str)>,
+    found: &mut Vec<Vec<(&'a str, &'a str)>>,
+) {
+    let allergen = match allergens.get(assigned.len()) {
+        Some(&allergen) => allergen,
+        None => return found.push(assigned.clone()),
+    };
+    for &ingredient in ingredients {
+        let unused = assigned.iter().all(|&(_, i)| i != ingredient);
+        let fits = foods
+            .iter()
+            .all(|(i, a)| !a.contains(&allergen) || i.contains(&ingredient));
+        if found.len() < 2 && unused && fits {
+            assigned.push((allergen, ingredient));
+            assign(foods, allergens, ingredients, assigned, found);
+            assigned.pop();
+        }
+    }
+}
+
+/// Part 1 the slow way, counting every ingredient not given an allergen.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    let (foods, assigned) = reference_assign(input)?;
+    Ok(foods
+        .iter()
+        .flat_map(|(ingredients, _)| ingredients)
+        .filter(|ingredient| assigned.iter().all(|(_, i)| i != *ingredient))
+        .count())
+}
+
+/// Part 2 the slow way, listing the ingredients given allergens in order of allergen.
+pub fn reference_part2(input: &str) -> Result<String> {
+    let (_, assigned) = reference_assign(input)?;
+    Ok(assigned
+        .iter()
+        .map(|(_, i)| *i)
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+const ALLERGENS: [&str; 9] = [
+    "dairy",
+    "eggs",
+    "fish",
+    "nuts",
+    "peanuts",
+    "sesame",
+    "shellfish",
+    "soy",
+    "wheat",
+];
+
+/// At least `size` foods, with more added until every allergen's ingredient can be identified.
+/// Foods sometimes contain an allergen without listing it, as in the puzzle.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut allergens = ALLERGENS.to_vec();
+    rng.shuffle(&mut allergens);
+    allergens.truncate(rng.range(3..=ALLERGENS.len()));
+    allergens.sort_unstable();
+
+    let mut words = HashSet::new();
+    while words.len() < allergens.len() + 20 + size / 2 {
+        words.insert(rng.word(4..=8));
+    }
+    let mut words = words.into_iter().collect::<Vec<_>>();
+    words.sort_unstable();
+    rng.shuffle(&mut words);
+    let (dangerous, safe) = words.split_at(allergens.len());
+
+    let mut foods = Vec::new();
+    loop {
+        let mut listed = allergens.iter().map(|_| false).collect::<Vec<_>>();
+        for _ in 0..rng.range(1..=3) {
+            listed[rng.below(allergens.len())] = true;
+        }
+        let mut ingredients = Vec::new();
+        for (i, &listed) in listed.iter().enumerate() {
+            if listed || rng.chance(0.2) {
+                ingredients.push(dangerous[i].as_str());
+            }
+        }
+        for _ in 0..rng.range(3..=10) {
+            let ingredient = rng.pick(safe).as_str();
+            if !ingredients.contains(&ingredient) {
+                ingredients.push(ingredient);
+            }
+        }
+        rng.shuffle(&mut ingredients);
+        let listed = allergens
+            .iter()
+            .zip(listed)
+            .filter(|&(_, listed)| listed)
+            .map(|(allergen, _)| *allergen)
+            .collect::<Vec<_>>();
+        foods.push(format!(
+            "{} (contains {})",
+            ingredients.join(" "),
+            listed.join(", ")
+        ));
+
+        if foods.len() >= size {
+            let input = foods.join("\n");
+            let identified = load_foods(&input).and_then(|foods| identify_allergens(&foods));
+            if identified.is_ok() {
+                return input;
+            }
+        }
+    }
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Parsed<'a> = Vec<Food<'a>>;
+
+    fn day(&self) -> usize {
+        21
+    }
+
+    fn title(&self) -> &'static str {
+        "Allergen Assessment"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<Food<'a>>> {
+        load_foods(input)
+    }
+
+    fn part1(&self, foods: &Vec<Food<'_>>) -> Result<String> {
+        part1(foods).map(|n| n.to_string())
+    }
+
+    fn part2(&self, foods: &Vec<Food<'_>>) -> Result<String> {
+        part2(foods)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day21::{load_foods, part1, part2};
+
+    #[test]
+    fn test_part1() {
+        let s = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+        let foods = load_foods(s).expect("valid input");
+        assert_eq!(part1(&foods), Ok(5));
+        assert_eq!(part2(&foods), Ok("mxmxvkd,sqjhc,fvjkl".to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = load_foods("a b (contains x)\nc d (contains y").expect_err("no )");
+        assert_eq!(err.position.map(|p| p.line), Some(2));
+
+        let foods = load_foods("a b (contains x)\na b (contains x)").expect("valid input");
+        assert_eq!(
+            part1(&foods).map_err(|err| err.to_string()),
+            Err("could not tell which ingredients contain x".to_string())
+        );
+    }
+
+    #[test]
+    fn test_no_single_allergens() {
+        let foods = load_foods("a c (contains x, z)\nb c (contains y, z)").expect("valid input");
+        assert_eq!(part1(&foods), Ok(0));
+        assert_eq!(part2(&foods), Ok("a,b,c".to_string()));
+    }
+}
@@ -0,0 +1,388 @@
+//! Day 22: [Crab Combat](https://adventofcode.com/2020/day/22).
+
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::collections::{HashSet, VecDeque};
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/22.txt");
+
+enum Player {
+    Player1,
+    Player2,
+}
+
+fn load_deck(
+    input: &str,
+    section: &str,
+    title: &str,
+    seen: &mut HashSet<u8>,
+) -> Result<VecDeque<u8>> {
+    let mut lines = section.lines();
+    match lines.next() {
+        Some(line) if line == title => (),
+        line => {
+            let line = line.unwrap_or(section);
+            return Err(Error::at(input, line, format!("expected {:?}", title)));
+        }
+    }
+
+    lines
+        .map(|line| match error::parse::<u8>(input, line)? {
+            0 => Err(Error::at(input, line, "cards must be positive")),
+            card if !seen.insert(card) => {
+                Err(Error::at(input, line, format!("duplicate card {}", card)))
+            }
+            card => Ok(card),
+        })
+        .collect()
+}
+
+/// Parses both players' decks, top card first. Cards must be distinct and positive.
+pub fn load_players(input: &str) -> Result<(VecDeque<u8>, VecDeque<u8>)> {
+    let mut players = input.trim_end().split("\n\n");
+    let mut seen = HashSet::new();
+    let mut deck = |title| match players.next() {
+        Some(section) => load_deck(input, section, title, &mut seen),
+        None => Err(Error::new(format!("missing {:?} section", title))),
+    };
+    let p1 = deck("Player 1:")?;
+    let p2 = deck("Player 2:")?;
+
+    Ok((p1, p2))
+}
+
+/// The winning deck's score, each card times its position from the bottom.
+pub fn score(winner: &VecDeque<u8>) -> usize {
+    (1usize..)
+        .zip(winner.iter().rev())
+        .map(|(i, v)| i * *v as usize)
+        .sum()
+}
+
+/// The winner's score after a game of Combat.
+pub fn part1(mut player_1: VecDeque<u8>, mut player_2: VecDeque<u8>) -> Result<usize> {
+    let mut seen_stacks = HashSet::new();
+
+    while let (Some(&a), Some(&b)) = (player_1.front(), player_2.front()) {
+        if !seen_stacks.insert((player_1.clone(), player_2.clone())) {
+            return Err(Error::new("the game never ends"));
+        }
+        player_1.pop_front();
+        player_2.pop_front();
+
+        if a > b {
+            player_1.push_back(a);
+            player_1.push_back(b);
+        } else {
+            player_2.push_back(b);
+            player_2.push_back(a);
+        }
+    }
+
+    let winner = if player_1.is_empty() {
+        player_2
+    } else {
+        player_1
+    };
+    Ok(score(&winner))
+}
+
+fn recursive_combat_loop(
+    player_1: &mut VecDeque<u8>,
+    player_2: &mut VecDeque<u8>,
+    sub_game: bool,
+) -> Player {
+    if sub_game && player_1.iter().max() > player_2.iter().max() {
+        return Player::Player1;
+    }
+
+    let mut seen_stacks: HashSet<(VecDeque<u8>, VecDeque<u8>)> = HashSet::new();
+
+    while !player_1.is_empty() && !player_2.is_empty() {
+        let player1_deck = player_1.clone();
+        let player2_deck = player_2.clone();
+        let decks = (player1_deck, player2_deck);
+        if seen_stacks.contains(&decks) {
+            return Player::Player1;
+        }
+
+        seen_stacks.insert(decks);
+
+        let (a, b) = match (player_1.pop_front(), player_2.pop_front()) {
+            (Some(a), Some(b)) => (a, b),
+            _ => unreachable!("both decks are non-empty"),
+        };
+
+        if a > player_1.len() as u8 || b > player_2.len() as u8 {
+            if a > b {
+                player_1.push_back(a);
+                player_1.push_back(b);
+            } else {
+                player_2.push_back(b);
+                player_2.push_back(a);
+            }
+            continue;
+        }
+
+        let mut sub_p1 = player_1.clone();
+        sub_p1.resize(a as usize, 0);
+        let mut sub_p2 = player_2.clone();
+        sub_p2.resize(b as usize, 0);
+
+        match recursive_combat_loop(&mut sub_p1, &mut sub_p2, true) {
+            Player::Player1 => {
+                player_1.push_back(a);
+                player_1.push_back(b);
+            }
+            Player::Player2 => {
+                player_2.push_back(b);
+                player_2.push_back(a);
+            }
+        }
+    }
+
+    match player_1.is_empty() {
+        true => Player::Player2,
+        false => Player::Player1,
+    }
+}
+
+/// The winner's score after a game of Recursive Combat.
+pub fn part2(mut player_1: VecDeque<u8>, mut player_2: VecDeque<u8>) -> usize {
+    let winner = match recursive_combat_loop(&mut player_1, &mut player_2, false) {
+        Player::Player1 => player_1,
+        Player::Player2 => player_2,
+    };
+
+    score(&winner)
+}
+
+/// Both decks read the slow way, top card first.
+fn reference_decks(input: &str) -> Result<(Vec<usize>, Vec<usize>)> {
+    let deck = |section: &str, title: &str| {
+        let mut lines = section.lines();
+        if lines.next() != Some(title) {
+            return Err(Error::new(format!("expected {:?}", title)));
+        }
+        lines
+            .map(|line| line.parse().map_err(|_| Error::new("cards are numbers")))
+            .collect::<Result<Vec<usize>>>()
+    };
+    match input
+        .trim_end()
+        .split("\n\n")
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [p1, p2] => Ok((deck(p1, "Player 1:")?, deck(p2, "Player 2:")?)),
+        _ => Err(Error::new("expected two decks")),
+    }
+}
+
+/// Plays a game the slow way, returning whether player 1 won and the winning deck. Sub-games are
+/// played out in full whenever `recursive`.
+fn reference_game(mut p1: Vec<usize>, mut p2: Vec<usize>, recursive: bool) -> (bool, Vec<usize>) {
+    let mut seen = HashSet::new();
+    while !p1.is_empty() && !p2.is_empty() {
+        if !seen.insert((p1.clone(), p2.clone())) {
+            return (true, p1);
+        }
+        let (a, b) = (p1.remove(0), p2.remove(0));
+        let p1_wins = match recursive && a <= p1.len() && b <= p2.len() {
+            true => reference_game(p1[..a].to_vec(), p2[..b].to_vec(), true).0,
+            false => a > b,
+        };
+        match p1_wins {
+            true => p1.extend([a, b]),
+            false => p2.extend([b, a]),
+        }
+    }
+    match p2.is_empty() {
+        true => (true, p1),
+        false => (false, p2),
+    }
+}
+
+fn reference_score(deck: &[usize]) -> usize {
+    deck.iter()
+        .rev()
+        .enumerate()
+        .map(|(i, card)| (i + 1) * card)
+        .sum()
+}
+
+/// Part 1 the slow way. Fails if the game never ends, rather than letting player 1 win as
+/// Recursive Combat does.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    let (p1, p2) = reference_decks(input)?;
+    let mut seen = HashSet::new();
+    let (mut a, mut b) = (p1.clone(), p2.clone());
+    while !a.is_empty() && !b.is_empty() {
+        if !seen.insert((a.clone(), b.clone())) {
+            return Err(Error::new("the game never ends"));
+        }
+        let (x, y) = (a.remove(0), b.remove(0));
+        match x > y {
+            true => a.extend([x, y]),
+            false => b.extend([y, x]),
+        }
+    }
+    Ok(reference_score(if a.is_empty() { &b } else { &a }))
+}
+
+/// Part 2 the slow way, playing out every sub-game rather than seeing who must win it.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    let (p1, p2) = reference_decks(input)?;
+    Ok(reference_score(&reference_game(p1, p2, true).1))
+}
+
+/// Most cards dealt to each player, since recursive games slow down quickly as decks grow.
+const MAX_DECK: usize = 50;
+
+/// Two decks of up to `size` cards each, shuffled from the cards 1 to twice that, redealt until
+/// the plain game of Combat ends.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let deck = size.clamp(1, MAX_DECK);
+    let mut cards = (1..=2 * deck as u8).collect::<Vec<_>>();
+    loop {
+        rng.shuffle(&mut cards);
+        let (p1, p2) = cards.split_at(deck);
+        if part1(p1.iter().copied().collect(), p2.iter().copied().collect()).is_ok() {
+            let list = |deck: &[u8]| {
+                deck.iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            return format!("Player 1:\n{}\n\nPlayer 2:\n{}", list(p1), list(p2));
+        }
+    }
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Parsed<'a> = (VecDeque<u8>, VecDeque<u8>);
+
+    fn day(&self) -> usize {
+        22
+    }
+
+    fn title(&self) -> &'static str {
+        "Crab Combat"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<(VecDeque<u8>, VecDeque<u8>)> {
+        load_players(input)
+    }
+
+    fn part1(&self, (a, b): &(VecDeque<u8>, VecDeque<u8>)) -> Result<String> {
+        part1(a.clone(), b.clone()).map(|n| n.to_string())
+    }
+
+    fn part2(&self, (a, b): &(VecDeque<u8>, VecDeque<u8>)) -> Result<String> {
+        Ok(part2(a.clone(), b.clone()).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn max_size(&self) -> Option<usize> {
+        Some(MAX_DECK)
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day22::{load_players, part1, part2, recursive_combat_loop};
+    use proptest::prelude::*;
+    use std::collections::VecDeque;
+
+    /// The cards 1 to some `2n`, shuffled and dealt into two non-empty decks.
+    fn decks() -> impl Strategy<Value = (VecDeque<u8>, VecDeque<u8>)> {
+        (1u8..12)
+            .prop_flat_map(|n| {
+                (
+                    Just((1..=2 * n).collect::<Vec<_>>()).prop_shuffle(),
+                    1..2 * n,
+                )
+            })
+            .prop_map(|(cards, split)| {
+                let (p1, p2) = cards.split_at(split as usize);
+                (p1.iter().copied().collect(), p2.iter().copied().collect())
+            })
+    }
+
+    #[test]
+    fn play_games() {
+        let s = "Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10";
+        let (p1, p2) = load_players(s).expect("valid input");
+        assert_eq!(part1(p1.clone(), p2.clone()), Ok(306));
+        assert_eq!(part2(p1, p2), 291);
+    }
+
+    #[test]
+    fn test_errors() {
+        let message = |s| {
+            load_players(s)
+                .map(|_| ())
+                .expect_err("invalid")
+                .to_string()
+        };
+        assert_eq!(message("Player 1:\n1"), "missing \"Player 2:\" section");
+        assert_eq!(
+            message("Player 1:\n1\n2\n\nPlayer 2:\n2"),
+            "line 6, column 1: duplicate card 2"
+        );
+        assert_eq!(
+            message("Player 2:\n1"),
+            "line 1, column 1: expected \"Player 1:\""
+        );
+
+        let (p1, p2) =
+            load_players("Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14").expect("valid input");
+        assert!(part1(p1, p2).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_recursive_combat_keeps_every_card((p1, p2) in decks()) {
+            let (mut after1, mut after2) = (p1.clone(), p2.clone());
+            recursive_combat_loop(&mut after1, &mut after2, false);
+
+            let mut before = p1.iter().chain(&p2).copied().collect::<Vec<_>>();
+            let mut after = after1.iter().chain(&after2).copied().collect::<Vec<_>>();
+            before.sort_unstable();
+            after.sort_unstable();
+            prop_assert_eq!(after, before);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Day 23: [Crab Cups](https://adventofcode.com/2020/day/23).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/23.txt");
+
+/// Parses the cup labels, which must be the digits 1 to 9 in some order.
+pub fn load_cups(input: &str) -> Result<[u32; 9]> {
+    let mut res = [0; 9];
+    let mut seen = [false; 10];
+
+    let cups = input.trim();
+    if cups.chars().count() != 9 {
+        return Err(Error::at(input, cups, "expected 9 cups"));
+    }
+    for ((cup, (i, c)), n) in res.iter_mut().zip(cups.char_indices()).zip(1..) {
+        match c.to_digit(10) {
+            Some(d) if d > 0 && !std::mem::replace(&mut seen[d as usize], true) => *cup = d,
+            _ => {
+                return Err(Error::at(
+                    input,
+                    &cups[i..],
+                    format!(
+                        "cup {} must be a digit from 1 to 9 that is not used already",
+                        n
+                    ),
+                ))
+            }
+        }
+    }
+
+    Ok(res)
+}
+
+fn run_game(start: u32, tiles: &mut [u32], turns: usize, limit: u32) {
+    let mut curr = start;
+    for _ in 0..turns {
+        let a = tiles[curr as usize];
+        let b = tiles[a as usize];
+        let c = tiles[b as usize];
+
+        let mut destination = curr;
+        while destination == a || destination == b || destination == c || destination == curr {
+            destination -= 1;
+            if destination == 0 {
+                destination = limit;
+            }
+        }
+
+        let now_after_curr = tiles[c as usize];
+        tiles[curr as usize] = now_after_curr;
+        tiles[c as usize] = tiles[destination as usize];
+        tiles[destination as usize] = a;
+
+        curr = tiles[curr as usize];
+    }
+}
+
+/// The labels after cup 1 once the crab has made `turns` moves. `cups` must hold each of 1 to 9.
+pub fn part1(cups: &[u32; 9], turns: usize) -> usize {
+    let mut tiles = vec![0u32; 10];
+    for (p, n) in cups.iter().skip(1).zip(cups.iter()) {
+        tiles[*n as usize] = *p;
+    }
+
+    let first = cups[0];
+    let last = cups[8] as usize;
+
+    tiles[last] = first;
+
+    run_game(first, &mut tiles, turns, 9);
+
+    let mut res = 0usize;
+    let mut c = 1;
+    for _ in 0..8 {
+        c = tiles[c as usize];
+        res *= 10;
+        res += c as usize;
+    }
+    res
+}
+
+/// The product of the two labels after cup 1 once the crab has made ten million moves with a
+/// million cups. `cups` must hold each of 1 to 9.
+pub fn part2(cups: &[u32; 9]) -> usize {
+    let mut tiles = (1..=1_000_001).collect::<Vec<_>>();
+    for (p, n) in cups.iter().skip(1).zip(cups.iter()) {
+        tiles[*n as usize] = *p;
+    }
+
+    let first = cups[0];
+    let last = cups[8] as usize;
+
+    tiles[1_000_000] = first;
+    tiles[last] = 10;
+
+    run_game(first, &mut tiles, 10_000_000, 1_000_000);
+
+    let a = tiles[1] as usize;
+    let b = tiles[a] as usize;
+
+    a * b
+}
+
+/// Part 1 the slow way, moving cups around a list that starts with the current cup. Part 2 has no
+/// reference: ten million moves around a list of a million cups would take days.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    let mut cups = input
+        .trim()
+        .chars()
+        .map(|c| c.to_digit(10).ok_or_else(|| Error::new("cups are digits")))
+        .collect::<Result<Vec<_>>>()?;
+    let mut sorted = cups.clone();
+    sorted.sort_unstable();
+    if sorted != (1..=9).collect::<Vec<_>>() {
+        return Err(Error::new("cups are 1 to 9"));
+    }
+
+    for _ in 0..100 {
+        let current = cups[0];
+        let picked = cups.drain(1..4).collect::<Vec<_>>();
+        let mut destination = current;
+        loop {
+            destination = if destination == 1 { 9 } else { destination - 1 };
+            if !picked.contains(&destination) {
+                break;
+            }
+        }
+        let at = cups
+            .iter()
+            .position(|&c| c == destination)
+            .expect("still there")
+            + 1;
+        cups.splice(at..at, picked);
+        cups.rotate_left(1);
+    }
+
+    let one = cups.iter().position(|&c| c == 1).expect("cup 1 is there");
+    cups.rotate_left(one);
+    Ok(cups[1..].iter().fold(0, |acc, &c| acc * 10 + c as usize))
+}
+
+/// The cups 1 to 9 in a random order. There are always nine cups, so `size` is unused.
+pub fn generate(rng: &mut Rng, _size: usize) -> String {
+    let mut cups = (1..=9).collect::<Vec<_>>();
+    rng.shuffle(&mut cups);
+    cups.iter().map(|c| c.to_string()).collect()
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Parsed<'a> = [u32; 9];
+
+    fn day(&self) -> usize {
+        23
+    }
+
+    fn title(&self) -> &'static str {
+        "Crab Cups"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<[u32; 9]> {
+        load_cups(input)
+    }
+
+    fn part1(&self, cups: &[u32; 9]) -> Result<String> {
+        Ok(part1(cups, 100).to_string())
+    }
+
+    fn part2(&self, cups: &[u32; 9]) -> Result<String> {
+        Ok(part2(cups).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn unscaled(&self) -> Option<&'static str> {
+        Some("the cups or the moves, which are the same for every input")
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day23::{load_cups, part1, part2, run_game};
+    use proptest::prelude::*;
+
+    /// Cups 1 to some `n` from 5 up, in any order.
+    fn cups() -> impl Strategy<Value = Vec<u32>> {
+        (5u32..40).prop_flat_map(|n| Just((1..=n).collect::<Vec<_>>()).prop_shuffle())
+    }
+
+    #[test]
+    fn test_part1() {
+        let cups = load_cups("389125467").expect("valid input");
+        assert_eq!(part1(&cups, 10), 92658374);
+        assert_eq!(part1(&cups, 100), 67384529);
+    }
+
+    #[test]
+    fn test_part2() {
+        let cups = load_cups("389125467").expect("valid input");
+        assert_eq!(part2(&cups), 149245887792);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(load_cups("38912546").is_err());
+        assert!(load_cups("3891254670").is_err());
+        let err = load_cups("389125437").expect_err("3 is used twice");
+        assert_eq!(err.position.map(|p| p.column), Some(8));
+    }
+
+    proptest! {
+        #[test]
+        fn test_ring_stays_one_cycle(cups in cups(), turns in 0usize..300) {
+            let limit = cups.len() as u32;
+            let mut tiles = vec![0; cups.len() + 1];
+            for (cup, next) in cups.iter().zip(cups.iter().cycle().skip(1)) {
+                tiles[*cup as usize] = *next;
+            }
+            run_game(cups[0], &mut tiles, turns, limit);
+
+            let mut seen = vec![false; tiles.len()];
+            let mut cup = 1;
+            for _ in 0..limit {
+                prop_assert!(!seen[cup as usize], "cup {} is reached twice", cup);
+                seen[cup as usize] = true;
+                cup = tiles[cup as usize];
+            }
+            prop_assert_eq!(cup, 1);
+        }
+
+        #[test]
+        fn test_part1_lists_other_cups(cups in Just((1..=9).collect::<Vec<u32>>()).prop_shuffle(), turns in 0usize..100) {
+            let cups = cups.iter().map(|c| c.to_string()).collect::<String>();
+            let cups = load_cups(&cups).expect("valid cups");
+            let mut labels = part1(&cups, turns).to_string().into_bytes();
+            labels.sort_unstable();
+            prop_assert_eq!(labels, b"23456789".to_vec());
+        }
+    }
+}
@@ -0,0 +1,351 @@
+//! Day 24: [Lobby Layout](https://adventofcode.com/2020/day/24).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+use std::collections::{HashMap, HashSet};
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/24.txt");
+const HEX_OFFSETS: [(i16, i16); 6] = [(0, 1), (1, 1), (-1, 0), (1, 0), (-1, -1), (0, -1)];
+/// Longest path whose tile, and its neighbourhood after 100 days, still fits in `i16` coordinates.
+const MAX_PATH: usize = 30_000;
+
+/// A step to a neighbouring hexagonal tile.
+#[derive(Debug)]
+pub enum Direction {
+    /// `e`
+    East,
+    /// `se`
+    SouthEast,
+    /// `sw`
+    SouthWest,
+    /// `w`
+    West,
+    /// `nw`
+    NorthWest,
+    /// `ne`
+    NorthEast,
+}
+
+impl Direction {
+    fn offset(&self) -> (i16, i16) {
+        match self {
+            Direction::East => (1, 0),
+            Direction::SouthEast => (0, -1),
+            Direction::SouthWest => (-1, -1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (0, 1),
+            Direction::NorthEast => (1, 1),
+        }
+    }
+}
+
+/// The steps from the reference tile to a tile to flip.
+pub type Path = Vec<Direction>;
+
+/// Which side of a tile is up.
+#[derive(PartialEq)]
+pub enum Tile {
+    /// Flipped an odd number of times.
+    Black,
+    /// Flipped an even number of times.
+    White,
+}
+
+impl Tile {
+    fn swap(&mut self) {
+        *self = match self {
+            Tile::Black => Tile::White,
+            Tile::White => Tile::Black,
+        }
+    }
+}
+
+/// Parses one path per line, e.g. `esenee`.
+pub fn load_paths(input: &str) -> Result<Vec<Path>> {
+    input
+        .lines()
+        .map(|mut line| {
+            if line.len() > MAX_PATH {
+                return Err(Error::at(
+                    input,
+                    line,
+                    format!("paths can be at most {} characters long", MAX_PATH),
+                ));
+            }
+            let mut res = Vec::new();
+            while !line.is_empty() {
+                let (direction, len) = match line.as_bytes() {
+                    [b'e', ..] => (Direction::East, 1),
+                    [b'w', ..] => (Direction::West, 1),
+                    [b's', b'w', ..] => (Direction::SouthWest, 2),
+                    [b's', b'e', ..] => (Direction::SouthEast, 2),
+                    [b'n', b'w', ..] => (Direction::NorthWest, 2),
+                    [b'n', b'e', ..] => (Direction::NorthEast, 2),
+                    _ => return Err(Error::at(input, line, "expected e, se, sw, w, nw or ne")),
+                };
+                res.push(direction);
+                line = &line[len..];
+            }
+            Ok(res)
+        })
+        .collect()
+}
+
+/// How many tiles end up black, and every flipped tile by axial coordinates.
+pub fn part1(paths: &[Path]) -> (usize, HashMap<(i16, i16), Tile>) {
+    let mut seen = HashMap::with_capacity(paths.len());
+
+    for path in paths {
+        let mut i = 0i16;
+        let mut j = 0i16;
+
+        for dir in path {
+            let (a, b) = dir.offset();
+            i += a;
+            j += b;
+        }
+
+        seen.entry((i, j)).or_insert(Tile::White).swap();
+    }
+
+    (seen.values().filter(|t| **t == Tile::Black).count(), seen)
+}
+
+/// How many tiles are black after 100 days, starting from the tiles [`part1`] flipped.
+pub fn part2(seen: HashMap<(i16, i16), Tile>) -> usize {
+    let mut black_coords = seen
+        .into_iter()
+        .filter(|(_, colour)| *colour == Tile::Black)
+        .map(|kv| kv.0)
+        .collect::<HashSet<(i16, i16)>>();
+
+    let mut neighbours = HashMap::new();
+    for &(i, j) in &black_coords {
+        HEX_OFFSETS
+            .iter()
+            .map(|&(a, b)| (a + i, b + j))
+            .for_each(|neighbour_coord| {
+                *neighbours.entry(neighbour_coord).or_insert(0i8) += 1;
+            });
+    }
+
+    for _ in 0..100 {
+        let mut new_black = HashSet::with_capacity(black_coords.len());
+        let mut new_neighbours = HashMap::with_capacity(neighbours.len());
+        for (coord, black_neighbour_count) in neighbours {
+            if black_neighbour_count == 2
+                || (black_neighbour_count == 1 && black_coords.contains(&coord))
+            {
+                new_black.insert(coord);
+                HEX_OFFSETS
+                    .iter()
+                    .map(|&(a, b)| (a + coord.0, b + coord.1))
+                    .for_each(|neighbour_coord| {
+                        *new_neighbours.entry(neighbour_coord).or_insert(0i8) += 1;
+                    });
+            }
+        }
+
+        black_coords = new_black;
+        neighbours = new_neighbours;
+    }
+
+    black_coords.len()
+}
+
+/// Steps in doubled coordinates, where east and west move two columns and the diagonals move one
+/// column and one row, as a check on the axial coordinates above.
+const REFERENCE_STEPS: [(&str, (i64, i64)); 6] = [
+    ("e", (2, 0)),
+    ("w", (-2, 0)),
+    ("se", (1, -1)),
+    ("sw", (-1, -1)),
+    ("ne", (1, 1)),
+    ("nw", (-1, 1)),
+];
+
+/// The black tiles after following every path the slow way, in doubled coordinates.
+fn reference_black(input: &str) -> Result<HashSet<(i64, i64)>> {
+    let mut black = HashSet::new();
+    for line in input.lines() {
+        let (mut x, mut y, mut rest) = (0, 0, line);
+        while !rest.is_empty() {
+            let (step, (dx, dy)) = REFERENCE_STEPS
+                .iter()
+                .find(|(step, _)| rest.starts_with(step))
+                .ok_or_else(|| Error::new("paths are made of e, se, sw, w, nw and ne"))?;
+            x += dx;
+            y += dy;
+            rest = &rest[step.len()..];
+        }
+        if !black.remove(&(x, y)) {
+            black.insert((x, y));
+        }
+    }
+    Ok(black)
+}
+
+/// Part 1 the slow way, flipping each tile in a set.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    reference_black(input).map(|black| black.len())
+}
+
+/// Part 2 the slow way, counting the black neighbours of every tile next to a black one each day.
+pub fn reference_part2(input: &str) -> Result<usize> {
+    let mut black = reference_black(input)?;
+    let neighbours = |(x, y): (i64, i64)| {
+        REFERENCE_STEPS
+            .iter()
+            .map(move |(_, (dx, dy))| (x + dx, y + dy))
+    };
+    for _ in 0..100 {
+        let candidates = black
+            .iter()
+            .flat_map(|&tile| neighbours(tile).chain([tile]))
+            .collect::<HashSet<_>>();
+        black = candidates
+            .into_iter()
+            .filter(|&tile| {
+                let count = neighbours(tile).filter(|n| black.contains(n)).count();
+                count == 2 || (count == 1 && black.contains(&tile))
+            })
+            .collect();
+    }
+    Ok(black.len())
+}
+
+/// `size` paths of 10 to 20 steps.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            (0..rng.range(10..=20))
+                .map(|_| *rng.pick(&["e", "se", "sw", "w", "nw", "ne"]))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Parsed<'a> = Vec<Path>;
+
+    fn day(&self) -> usize {
+        24
+    }
+
+    fn title(&self) -> &'static str {
+        "Lobby Layout"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<Vec<Path>> {
+        load_paths(input)
+    }
+
+    fn part1(&self, paths: &Vec<Path>) -> Result<String> {
+        let (p1, _) = part1(paths);
+        Ok(p1.to_string())
+    }
+
+    fn part2(&self, paths: &Vec<Path>) -> Result<String> {
+        let (_, generated) = part1(paths);
+        Ok(part2(generated).to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+
+    fn reference_part2(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part2(input).map(|n| n.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y2020::day24::{load_paths, part1, part2};
+    use proptest::prelude::*;
+
+    fn path() -> impl Strategy<Value = Vec<&'static str>> {
+        prop::collection::vec(
+            prop::sample::select(vec!["e", "se", "sw", "w", "nw", "ne"]),
+            0..20,
+        )
+    }
+
+    #[test]
+    fn test_example() {
+        let s = "sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew";
+        let paths = load_paths(s).expect("valid input");
+        let (p1, gen) = part1(&paths);
+        assert_eq!(p1, 10);
+        assert_eq!(part2(gen), 2208);
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = load_paths("esew\nnwwsx").expect_err("invalid direction");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 4: expected e, se, sw, w, nw or ne"
+        );
+        assert!(load_paths("en").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_step_order_does_not_matter(
+            paths in prop::collection::vec(path(), 1..30),
+            shuffled in any::<u64>(),
+        ) {
+            let input = paths.iter().map(|p| p.concat()).collect::<Vec<_>>().join("\n");
+            let reordered = paths
+                .iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    let mut p = p.clone();
+                    let len = p.len();
+                    p.rotate_left((shuffled as usize).wrapping_add(i) % len.max(1));
+                    p.reverse();
+                    p.concat()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let (black, _) = part1(&load_paths(&input).expect("valid input"));
+            let (reordered, _) = part1(&load_paths(&reordered).expect("valid input"));
+            prop_assert_eq!(reordered, black);
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Day 25: [Combo Breaker](https://adventofcode.com/2020/day/25).
+
+use crate::crosscheck;
+use crate::error::{self, Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/2020/25.txt");
+/// The modulus of the handshake's transforms.
+pub const MODULO: usize = 20_201_227;
+
+fn load_key(input: &str, line: &str) -> Result<usize> {
+    // 7 generates every non-zero residue, so any key in this range has a loop size
+    match error::parse(input, line)? {
+        pk if pk > 0 && pk < MODULO => Ok(pk),
+        _ => Err(Error::at(
+            input,
+            line,
+            format!("public keys must be between 1 and {}", MODULO - 1),
+        )),
+    }
+}
+
+/// Parses the card's and then the door's public key.
+pub fn load_keys(input: &str) -> Result<(usize, usize)> {
+    match input.lines().collect::<Vec<_>>().as_slice() {
+        &[card_pk, door_pk] => Ok((load_key(input, card_pk)?, load_key(input, door_pk)?)),
+        lines => Err(Error::new(format!(
+            "expected 2 public keys, found {} lines",
+            lines.len()
+        ))),
+    }
+}
+
+/// The loop size that transforms 7 into `pk`, which must be between 1 and `MODULO - 1`.
+pub fn find_loops(pk: usize) -> usize {
+    let mut res = 0;
+    let mut v = 1;
+    while v != pk {
+        v *= 7;
+        v %= MODULO;
+        res += 1;
+    }
+
+    res
+}
+
+/// Transforms `subject` with the given loop size.
+pub fn transform(subject: usize, loops: usize) -> usize {
+    let mut r = 1;
+
+    for _ in 0..loops {
+        r *= subject;
+        r %= MODULO;
+    }
+
+    r
+}
+
+/// The encryption key the card and door agree on.
+pub fn part1(pk_a: usize, pk_b: usize) -> usize {
+    let b_loops = find_loops(pk_b);
+    transform(pk_a, b_loops)
+}
+
+/// Part 1 the slow way, finding both loop sizes and checking that the card and door really do work
+/// out the same key.
+pub fn reference_part1(input: &str) -> Result<usize> {
+    let keys = input
+        .lines()
+        .map(|line| {
+            line.parse::<usize>()
+                .map_err(|_| Error::new("keys are numbers"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let (card_pk, door_pk) = match keys.as_slice() {
+        &[card_pk, door_pk] => (card_pk, door_pk),
+        _ => return Err(Error::new("expected two keys")),
+    };
+    let loops = |pk: usize| {
+        let mut value = 1;
+        for loops in 0..MODULO {
+            if value == pk {
+                return Ok(loops);
+            }
+            value = value * 7 % MODULO;
+        }
+        Err(Error::new(format!("no loop size gives {}", pk)))
+    };
+    crosscheck::only([
+        transform(door_pk, loops(card_pk)?),
+        transform(card_pk, loops(door_pk)?),
+    ])
+}
+
+/// Public keys with loop sizes of up to a thousand times `size`, so finding them takes time in
+/// proportion to it.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let most = (size.max(1) * 1000).min(MODULO - 2);
+    let mut key = || transform(7, rng.range(most / 2 + 1..=most));
+    format!("{}\n{}", key(), key())
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Parsed<'a> = (usize, usize);
+
+    fn day(&self) -> usize {
+        25
+    }
+
+    fn title(&self) -> &'static str {
+        "Combo Breaker"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse(&self, input: &str) -> Result<(usize, usize)> {
+        load_keys(input)
+    }
+
+    fn part1(&self, &(card_pk, door_pk): &(usize, usize)) -> Result<String> {
+        Ok(part1(card_pk, door_pk).to_string())
+    }
+
+    fn part2(&self, _: &(usize, usize)) -> Result<String> {
+        Ok("there is no part 2 lol".to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn reference_part1(&self, input: &str) -> Option<Result<String>> {
+        Some(reference_part1(input).map(|n| n.to_string()))
+    }
+}
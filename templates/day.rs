@@ -0,0 +1,91 @@
+//! [Day {{DAY}}](https://adventofcode.com/{{YEAR}}/day/{{DAY}}).
+
+use crate::error::{Error, Result};
+use crate::generate::Rng;
+use crate::solution::Solution;
+
+/// The puzzle input run by default.
+pub const INPUT: &str = include_str!("../../../files/{{YEAR}}/{{NN}}.txt");
+
+/// Parses one non-empty line at a time.
+pub fn load_input(input: &str) -> Result<Vec<&str>> {
+    input
+        .lines()
+        .map(|line| match line.is_empty() {
+            true => Err(Error::at(input, line, "empty line")),
+            false => Ok(line),
+        })
+        .collect()
+}
+
+/// How many lines there are.
+pub fn part1(lines: &[&str]) -> Result<usize> {
+    Ok(lines.len())
+}
+
+/// How many lines there are.
+pub fn part2(lines: &[&str]) -> Result<usize> {
+    Ok(lines.len())
+}
+
+/// `size` random lines of lowercase letters.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| rng.word(1..=10))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs this day through [`Solution`].
+pub struct Day{{NN}};
+
+impl Solution for Day{{NN}} {
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn day(&self) -> usize {
+        {{DAY}}
+    }
+
+    fn title(&self) -> &'static str {
+        "Day {{DAY}}"
+    }
+
+    fn input(&self) -> &'static str {
+        INPUT
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Vec<&'a str>> {
+        load_input(input)
+    }
+
+    fn part1(&self, lines: &Vec<&'_ str>) -> Result<String> {
+        part1(lines).map(|n| n.to_string())
+    }
+
+    fn part2(&self, lines: &Vec<&'_ str>) -> Result<String> {
+        part2(lines).map(|n| n.to_string())
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::years::y{{YEAR}}::day{{NN}}::{load_input, part1, part2};
+
+    const EXAMPLE: &str = "example";
+
+    #[test]
+    fn test_part1() {
+        let lines = load_input(EXAMPLE).expect("valid input");
+        assert_eq!(part1(&lines), Ok(1));
+    }
+
+    #[test]
+    fn test_part2() {
+        let lines = load_input(EXAMPLE).expect("valid input");
+        assert_eq!(part2(&lines), Ok(1));
+    }
+}
@@ -0,0 +1,132 @@
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_advent_of_code_2020"))
+        .args(args)
+        .output()
+        .expect("failed to run the binary")
+}
+
+#[test]
+fn test_input() {
+    assert_eq!(run(&["5", "--input"]).status.code(), Some(1));
+    assert_eq!(run(&["5", "--input-dir"]).status.code(), Some(1));
+    assert!(run(&["5", "--input-dir", "files"]).status.success());
+}
+
+#[test]
+fn test_input_files() {
+    // run from a scratch directory, so `files/` there is the one read by default
+    let dir = std::env::temp_dir().join(format!("aoc-cli-files-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("files/2020")).expect("can create dir");
+    let run_in_dir = || {
+        let output = Command::new(env!("CARGO_BIN_EXE_advent_of_code_2020"))
+            .arg("1")
+            .current_dir(&dir)
+            .output()
+            .expect("failed to run the binary");
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    };
+
+    // with no file, the built-in input is used
+    assert!(run_in_dir().contains("part 1: 319531"));
+
+    std::fs::write(dir.join("files/2020/01.txt"), "1000\n1010\n10\n1010\n").expect("can write");
+    let stdout = run_in_dir();
+    assert!(stdout.contains("part 1: 1020100"), "{}", stdout);
+    assert!(stdout.contains("part 2: 10100000"), "{}", stdout);
+
+    std::fs::remove_dir_all(&dir).expect("can clean up");
+}
+
+#[test]
+fn test_format() {
+    assert_eq!(run(&["--format", "xml", "1"]).status.code(), Some(1));
+    assert_eq!(run(&["1", "--format"]).status.code(), Some(1));
+    assert!(run(&["--format", "json", "1"]).status.success());
+}
+
+#[test]
+fn test_durations() {
+    assert_eq!(run(&["--warmup", "lots", "1:2"]).status.code(), Some(1));
+    assert_eq!(run(&["3:99999999999999999999999s"]).status.code(), Some(1));
+    assert_eq!(
+        run(&["--timeout", "99999999999999999999999s", "1"])
+            .status
+            .code(),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_baseline() {
+    assert_eq!(run(&["1", "--save-baseline"]).status.code(), Some(1));
+    assert_eq!(run(&["1", "--baseline"]).status.code(), Some(1));
+    assert_eq!(run(&["--threshold", "lots", "1"]).status.code(), Some(1));
+    assert_eq!(run(&["--threshold", "-5%", "1"]).status.code(), Some(1));
+}
+
+#[test]
+fn test_answers() {
+    let output = run(&["1", "--answers"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("missing path for --answers"));
+}
+
+#[test]
+fn test_jobs() {
+    assert_eq!(run(&["--jobs", "many", "1"]).status.code(), Some(1));
+    assert_eq!(run(&["1", "-j"]).status.code(), Some(1));
+    assert!(run(&["-j", "2", "1"]).status.success());
+}
+
+#[test]
+fn test_year() {
+    assert_eq!(run(&["--year", "abc", "5"]).status.code(), Some(1));
+    assert_eq!(run(&["--year", "1999", "5"]).status.code(), Some(1));
+    assert!(run(&["--year", "2020", "5"]).status.success());
+}
+
+#[test]
+fn test_timeout() {
+    let output = run(&["--timeout", "soon", "1"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("illegal value for --timeout"));
+
+    assert_eq!(run(&["--timeout", "0s", "1"]).status.code(), Some(1));
+    assert!(run(&["--timeout", "5s", "1"]).status.success());
+}
+
+#[test]
+fn test_runaway_days() {
+    let dir = std::env::temp_dir().join(format!("aoc-cli-runaway-{}", std::process::id()));
+    let year = dir.join("2020");
+    std::fs::create_dir_all(&year).expect("can create input dir");
+    for day in ["01", "15"] {
+        let input = format!("files/2020/{}.txt", day);
+        std::fs::copy(input, year.join(format!("{}.txt", day))).expect("can copy input");
+    }
+    // matching this would recurse forever, which used to overflow the stack and abort
+    std::fs::write(year.join("19.txt"), "0: 0 1 | 1\n1: \"a\"\n\naa").expect("can write input");
+
+    let dir_arg = dir.to_string_lossy();
+    let output = run(&[
+        "--input-dir",
+        &dir_arg,
+        "--timeout",
+        "200ms",
+        "15",
+        "19",
+        "1",
+    ]);
+    std::fs::remove_dir_all(&dir).expect("can remove input dir");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1), "{}", stdout);
+    assert!(stdout.contains("timed out after 200ms"), "{}", stdout);
+    assert!(
+        stdout.contains("rule 0 refers to itself before matching anything"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("part 1: 319531"), "{}", stdout);
+}